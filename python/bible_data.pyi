@@ -0,0 +1,85 @@
+from typing import Iterator, Optional, Union
+
+class ParseError(ValueError): ...
+class NoSuchBookError(ParseError): ...
+class NoChapterSpecified(ParseError): ...
+class NotANumber(ParseError): ...
+class ChapterOutOfRange(ParseError): ...
+class InvalidFormat(ParseError): ...
+class ImplicitRange(ParseError): ...
+class InvalidRange(ParseError): ...
+
+class Book:
+    def __init__(self, name: str, locale: Optional[str] = None) -> None: ...
+    @staticmethod
+    def from_number(number: int) -> Book: ...
+    @staticmethod
+    def from_osis(id: str) -> Book: ...
+    @staticmethod
+    def all() -> list[Book]: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def abbrev(self) -> str: ...
+    @property
+    def number(self) -> int: ...
+    @property
+    def chapters(self) -> int: ...
+    @property
+    def is_old_testament(self) -> bool: ...
+    @property
+    def is_new_testament(self) -> bool: ...
+    def verses(self, chapter: int) -> int: ...
+    def to_osis(self) -> str: ...
+    def format(self, locale: Optional[str] = None, abbrev: bool = False) -> str: ...
+    def __lt__(self, other: Book) -> bool: ...
+    def __le__(self, other: Book) -> bool: ...
+    def __gt__(self, other: Book) -> bool: ...
+    def __ge__(self, other: Book) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Verse:
+    def __init__(self, reference: str, locale: Optional[str] = None) -> None: ...
+    @staticmethod
+    def of(book: Book, chapter: int, verse: int) -> Verse: ...
+    @staticmethod
+    def from_osis(id: str) -> Verse: ...
+    @property
+    def book(self) -> Book: ...
+    @property
+    def chapter(self) -> int: ...
+    @property
+    def verse(self) -> int: ...
+    def is_valid(self) -> bool: ...
+    def to_osis(self) -> str: ...
+    def format(self, locale: Optional[str] = None) -> str: ...
+    def __lt__(self, other: Verse) -> bool: ...
+    def __le__(self, other: Verse) -> bool: ...
+    def __gt__(self, other: Verse) -> bool: ...
+    def __ge__(self, other: Verse) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class VerseRange:
+    def __init__(self, reference: str, locale: Optional[str] = None) -> None: ...
+    @staticmethod
+    def from_osis(reference: str) -> VerseRange: ...
+    @property
+    def book(self) -> Book: ...
+    @property
+    def start(self) -> Verse: ...
+    @property
+    def end(self) -> Verse: ...
+    def to_osis(self) -> str: ...
+    def format(self, locale: Optional[str] = None) -> str: ...
+    def __iter__(self) -> Iterator[Verse]: ...
+    def __len__(self) -> int: ...
+    def __contains__(self, verse: Verse) -> bool: ...
+    def __lt__(self, other: VerseRange) -> bool: ...
+    def __le__(self, other: VerseRange) -> bool: ...
+    def __gt__(self, other: VerseRange) -> bool: ...
+    def __ge__(self, other: VerseRange) -> bool: ...
+    def __hash__(self) -> int: ...
+
+def parse(
+    reference: str, locale: Optional[str] = None
+) -> Union[Book, Verse, VerseRange, list[VerseRange]]: ...
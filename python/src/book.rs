@@ -0,0 +1,149 @@
+use crate::errors::{no_such_book, out_of_range};
+use crate::parse_locale;
+use bible_data::{BibleBook, BibleChapter};
+use pyo3::exceptions::PyIndexError;
+use pyo3::prelude::*;
+
+/// A book of the Bible
+///
+/// Books compare in the order they occur in the Bible.
+#[pyclass(
+    name = "Book",
+    module = "bible_data",
+    eq,
+    ord,
+    hash,
+    frozen,
+    from_py_object
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Book(pub BibleBook);
+
+#[pymethods]
+impl Book {
+    /// Find a book by its name or abbreviation in the given locale
+    #[new]
+    #[pyo3(signature = (name, locale = None))]
+    fn new(name: &str, locale: Option<&str>) -> PyResult<Self> {
+        let book = match locale {
+            None => name.parse().map_err(no_such_book)?,
+            Some(code) => {
+                BibleBook::parse_in(name, parse_locale(Some(code))?).ok_or_else(|| {
+                    no_such_book(bible_data::errors::NoSuchBookError::new(name.to_string()))
+                })?
+            }
+        };
+        Ok(Book(book))
+    }
+
+    /// The book with the given number, from 1 for Genesis to 66 for Revelation
+    #[staticmethod]
+    fn from_number(number: u32) -> PyResult<Self> {
+        BibleBook::from_book_number(number)
+            .map(Book)
+            .map_err(out_of_range)
+    }
+
+    /// The book with the given OSIS identifier, such as `1Kgs`
+    #[staticmethod]
+    fn from_osis(id: &str) -> PyResult<Self> {
+        BibleBook::from_osis(id).map(Book).map_err(no_such_book)
+    }
+
+    /// All the books in order
+    #[staticmethod]
+    fn all() -> Vec<Book> {
+        BibleBook::iter().map(Book).collect()
+    }
+
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.0.name_in(bible_data::Locale::English)
+    }
+
+    #[getter]
+    fn abbrev(&self) -> &'static str {
+        self.0.abbrev_in(bible_data::Locale::English)
+    }
+
+    #[getter]
+    fn number(&self) -> u32 {
+        self.0.book_number()
+    }
+
+    #[getter]
+    fn chapters(&self) -> u32 {
+        self.0.number_of_chapters()
+    }
+
+    #[getter]
+    fn is_old_testament(&self) -> bool {
+        self.0.is_old_testament()
+    }
+
+    #[getter]
+    fn is_new_testament(&self) -> bool {
+        self.0.is_new_testament()
+    }
+
+    /// The number of verses in a chapter of the book
+    fn verses(&self, chapter: u8) -> PyResult<u32> {
+        BibleChapter::new(self.0, chapter)
+            .map(|chapter| chapter.number_of_verses())
+            .ok_or_else(|| PyIndexError::new_err(format!("{} has no chapter {}", self.0, chapter)))
+    }
+
+    #[pyo3(name = "to_osis")]
+    fn osis(&self) -> &'static str {
+        self.0.to_osis()
+    }
+
+    /// The name of the book in the given locale, or its abbreviation
+    #[pyo3(signature = (locale = None, abbrev = false))]
+    fn format(&self, locale: Option<&str>, abbrev: bool) -> PyResult<&'static str> {
+        let locale = parse_locale(locale)?;
+        Ok(match abbrev {
+            true => self.0.abbrev_in(locale),
+            false => self.0.name_in(locale),
+        })
+    }
+
+    fn __str__(&self) -> &'static str {
+        self.name()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Book('{}')", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::run;
+
+    #[test]
+    fn test_book() {
+        run(c"
+john = Book('John')
+assert john == Book('Jn') == Book.from_number(43) == Book.from_osis('John')
+assert str(john) == 'John' and repr(john) == \"Book('John')\"
+assert john.abbrev == 'Jn' and john.number == 43 and john.chapters == 21
+assert john.verses(3) == 36 and john.is_new_testament
+assert Book('Juan', locale='es') == john
+assert john.format(locale='de') == 'Johannes'
+assert Book('Mt') < john < Book('Rev')
+assert len({john, Book('Jn')}) == 1
+assert len(Book.all()) == 66
+try:
+    Book('Hezekiah')
+    assert False
+except NoSuchBookError as e:
+    assert isinstance(e, ParseError) and isinstance(e, ValueError)
+try:
+    Book.from_number(67)
+    assert False
+except IndexError:
+    pass
+");
+    }
+}
@@ -0,0 +1,92 @@
+use bible_data::errors::{self, OutOfRangeError};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+create_exception!(
+    bible_data,
+    ParseError,
+    PyValueError,
+    "A reference could not be parsed"
+);
+create_exception!(
+    bible_data,
+    NoSuchBookError,
+    ParseError,
+    "No book has the given name or abbreviation"
+);
+create_exception!(
+    bible_data,
+    NoChapterSpecified,
+    ParseError,
+    "A chapter was needed but not given"
+);
+create_exception!(
+    bible_data,
+    NotANumber,
+    ParseError,
+    "A chapter or verse was not a number"
+);
+create_exception!(
+    bible_data,
+    ChapterOutOfRange,
+    ParseError,
+    "The book has no such chapter"
+);
+create_exception!(
+    bible_data,
+    InvalidFormat,
+    ParseError,
+    "The reference is not in a recognised format"
+);
+create_exception!(
+    bible_data,
+    ImplicitRange,
+    ParseError,
+    "A range without chapters was given for a book with more than one"
+);
+create_exception!(
+    bible_data,
+    InvalidRange,
+    ParseError,
+    "The range ends before it starts or spans more than one book"
+);
+
+/// Convert a [ParseError](errors::ParseError) to the matching Python exception
+pub fn parse_error(error: errors::ParseError) -> PyErr {
+    match error {
+        errors::ParseError::NoSuchBookError(e) => NoSuchBookError::new_err(e.message().to_string()),
+        errors::ParseError::NoChapterSpecified(e) => {
+            NoChapterSpecified::new_err(e.message().to_string())
+        }
+        errors::ParseError::NotANumber(e) => NotANumber::new_err(e.message().to_string()),
+        errors::ParseError::ChapterOutOfRange(e) => {
+            ChapterOutOfRange::new_err(e.message().to_string())
+        }
+        errors::ParseError::InvalidFormat(e) => InvalidFormat::new_err(e.message().to_string()),
+        errors::ParseError::ImplicitRange(e) => ImplicitRange::new_err(e.to_string()),
+        errors::ParseError::InvalidRange(e) => InvalidRange::new_err(e.message().to_string()),
+    }
+}
+
+pub fn no_such_book(error: errors::NoSuchBookError) -> PyErr {
+    NoSuchBookError::new_err(error.message().to_string())
+}
+
+pub fn out_of_range(error: OutOfRangeError) -> PyErr {
+    PyIndexError::new_err(error.message().to_string())
+}
+
+/// Add the exceptions to the module
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add("ParseError", py.get_type::<ParseError>())?;
+    module.add("NoSuchBookError", py.get_type::<NoSuchBookError>())?;
+    module.add("NoChapterSpecified", py.get_type::<NoChapterSpecified>())?;
+    module.add("NotANumber", py.get_type::<NotANumber>())?;
+    module.add("ChapterOutOfRange", py.get_type::<ChapterOutOfRange>())?;
+    module.add("InvalidFormat", py.get_type::<InvalidFormat>())?;
+    module.add("ImplicitRange", py.get_type::<ImplicitRange>())?;
+    module.add("InvalidRange", py.get_type::<InvalidRange>())?;
+    Ok(())
+}
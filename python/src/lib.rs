@@ -0,0 +1,136 @@
+//! Python bindings for the bible-data crate
+//!
+//! Built with [maturin](https://www.maturin.rs) into a `bible_data` extension module:
+//!
+//! ```text
+//! cd python && maturin develop
+//! ```
+//!
+//! ```python
+//! import bible_data
+//! verse = bible_data.Verse("Jn 3:16")
+//! assert verse.book == bible_data.Book("John")
+//! assert [str(v) for v in bible_data.VerseRange("Ps 117:1-2")] == ["Ps 117:1", "Ps 117:2"]
+//! ```
+//!
+//! Parse failures raise a subclass of `bible_data.ParseError`, itself a `ValueError`,
+//! named after the variant of the crate's `ParseError`.
+use bible_data::{BibleReference, Locale};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+mod book;
+mod errors;
+mod range;
+mod verse;
+
+pub use book::Book;
+pub use range::{VerseIterator, VerseRange};
+pub use verse::Verse;
+
+/// Parse an optional locale identifier, defaulting to English
+fn parse_locale(locale: Option<&str>) -> PyResult<Locale> {
+    match locale {
+        None => Ok(Locale::English),
+        Some(code) => code
+            .parse()
+            .map_err(|e: bible_data::errors::NoSuchLocaleError| {
+                PyValueError::new_err(e.message().to_string())
+            }),
+    }
+}
+
+/// Parse any reference, returning the most specific kind it is
+///
+/// A book is returned as a `Book`, a verse as a `Verse`, a chapter or range as a
+/// `VerseRange` and a list of ranges as a list of `VerseRange`.
+#[pyfunction]
+#[pyo3(signature = (reference, locale = None))]
+fn parse(py: Python<'_>, reference: &str, locale: Option<&str>) -> PyResult<Py<PyAny>> {
+    let reference = BibleReference::from_str_in(reference, parse_locale(locale)?)
+        .map_err(errors::parse_error)?;
+    let object = match reference {
+        BibleReference::Book(book) => Book(book).into_pyobject(py)?.into_any(),
+        BibleReference::Verse(verse) => Verse(verse).into_pyobject(py)?.into_any(),
+        BibleReference::Chapter(_) | BibleReference::Range(_) => {
+            VerseRange(reference.ranges().remove(0))
+                .into_pyobject(py)?
+                .into_any()
+        }
+        BibleReference::List(list) => list
+            .iter()
+            .map(VerseRange)
+            .collect::<Vec<_>>()
+            .into_pyobject(py)?
+            .into_any(),
+    };
+    Ok(object.unbind())
+}
+
+#[pymodule(name = "bible_data")]
+fn bible_data_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Book>()?;
+    m.add_class::<Verse>()?;
+    m.add_class::<VerseRange>()?;
+    m.add_class::<VerseIterator>()?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    errors::register(m)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+    use std::ffi::{CStr, CString};
+
+    /// Run Python code with the contents of the module in scope, and the module
+    /// importable as `bible_data`
+    pub fn run(code: &CStr) {
+        Python::attach(|py| {
+            let bible_data = PyModule::new(py, "bible_data").unwrap();
+            bible_data_module(&bible_data).unwrap();
+            let modules = py.import("sys").unwrap().getattr("modules").unwrap();
+            modules.set_item("bible_data", &bible_data).unwrap();
+            let globals = PyDict::new(py);
+            for (name, value) in bible_data.dict() {
+                globals.set_item(name, value).unwrap();
+            }
+            if let Err(e) = py.run(code, Some(&globals), None) {
+                e.print(py);
+                panic!("{}", e);
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse() {
+        run(c"
+assert parse('John') == Book('John')
+assert parse('Jn 3:16') == Verse('Jn 3:16')
+assert parse('Ps 117') == VerseRange('Ps 117:1-2')
+assert parse('Jn 3:16-18') == VerseRange('Jn 3:16-18')
+assert parse('Ro 8:28, 31-39') == [VerseRange('Ro 8:28'), VerseRange('Ro 8:31-39')]
+assert parse('Juan 3:16', locale='es') == Verse('Jn 3:16')
+try:
+    parse('Jn 3:16', locale='xx')
+    assert False
+except ValueError:
+    pass
+");
+    }
+
+    /// The example in the module documentation runs as written
+    #[test]
+    fn test_docs() {
+        let example: Vec<&str> = include_str!("lib.rs")
+            .lines()
+            .skip_while(|line| *line != "//! ```python")
+            .skip(1)
+            .take_while(|line| *line != "//! ```")
+            .map(|line| line.strip_prefix("//! ").unwrap_or("").trim_end())
+            .collect();
+        assert!(example.len() > 1);
+        run(&CString::new(example.join("\n")).unwrap());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::book::Book;
+use crate::errors::parse_error;
+use crate::parse_locale;
+use crate::verse::Verse;
+use bible_data::{BibleVerse, BibleVerseRange, ReferenceStyle, StyledDisplay};
+use pyo3::basic::CompareOp;
+use pyo3::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// A range of verses within a book
+///
+/// Iterating over a range gives its verses. Ranges compare by their first verse
+/// and then their last.
+#[pyclass(name = "VerseRange", module = "bible_data", frozen, from_py_object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerseRange(pub BibleVerseRange);
+
+impl VerseRange {
+    fn start_verse(&self) -> BibleVerse {
+        let start = self.0.range.start();
+        BibleVerse::new(self.0.book, start.chapter, start.verse)
+    }
+
+    fn end_verse(&self) -> BibleVerse {
+        let end = self.0.range.end();
+        BibleVerse::new(self.0.book, end.chapter, end.verse)
+    }
+}
+
+#[pymethods]
+impl VerseRange {
+    /// Parse a range such as `Jn 3:16-18`, with the book names and separators of the
+    /// given locale
+    #[new]
+    #[pyo3(signature = (reference, locale = None))]
+    fn new(reference: &str, locale: Option<&str>) -> PyResult<Self> {
+        BibleVerseRange::from_str_in(reference, parse_locale(locale)?)
+            .map(VerseRange)
+            .map_err(parse_error)
+    }
+
+    /// The range with the given OSIS reference, such as `John.3.16-John.3.18`
+    #[staticmethod]
+    fn from_osis(reference: &str) -> PyResult<Self> {
+        BibleVerseRange::from_osis(reference)
+            .map(VerseRange)
+            .map_err(parse_error)
+    }
+
+    #[getter]
+    fn book(&self) -> Book {
+        Book(self.0.book)
+    }
+
+    #[getter]
+    fn start(&self) -> Verse {
+        Verse(self.start_verse())
+    }
+
+    #[getter]
+    fn end(&self) -> Verse {
+        Verse(self.end_verse())
+    }
+
+    fn to_osis(&self) -> String {
+        self.0.to_osis()
+    }
+
+    /// Write the range with the book names and separators of the given locale
+    #[pyo3(signature = (locale = None))]
+    fn format(&self, locale: Option<&str>) -> PyResult<String> {
+        let style = ReferenceStyle::for_locale(parse_locale(locale)?);
+        Ok(self.0.display_with(&style).to_string())
+    }
+
+    fn __iter__(&self) -> VerseIterator {
+        VerseIterator {
+            verses: self.0.verses().collect::<Vec<_>>().into_iter(),
+        }
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.verses().count()
+    }
+
+    fn __contains__(&self, verse: Verse) -> bool {
+        self.0.contains(&verse.0)
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp) -> bool {
+        let key = |range: &Self| (range.start_verse(), range.end_verse());
+        op.matches(key(self).cmp(&key(other)))
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("VerseRange('{}')", self.0)
+    }
+}
+
+/// Iterator over the verses of a [VerseRange]
+#[pyclass(module = "bible_data")]
+pub struct VerseIterator {
+    verses: std::vec::IntoIter<BibleVerse>,
+}
+
+#[pymethods]
+impl VerseIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<Verse> {
+        self.verses.next().map(Verse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::run;
+
+    #[test]
+    fn test_range() {
+        run(c"
+passage = VerseRange('Ps 117:2-118:2')
+assert str(passage) == 'Ps 117:2-118:2' and repr(passage) == \"VerseRange('Ps 117:2-118:2')\"
+assert passage.book == Book('Psalms')
+assert passage.start == Verse('Ps 117:2') and passage.end == Verse('Ps 118:2')
+assert [str(v) for v in passage] == ['Ps 117:2', 'Ps 118:1', 'Ps 118:2']
+assert len(passage) == 3
+assert Verse('Ps 118:1') in passage and Verse('Ps 118:3') not in passage
+assert passage == VerseRange.from_osis('Ps.117.2-Ps.118.2')
+assert passage.to_osis() == 'Ps.117.2-Ps.118.2'
+assert VerseRange('Ps 117:1-2') < passage < VerseRange('Ps 117:2-119:1')
+assert passage != VerseRange('Ps 117:2')
+assert len({passage, VerseRange('Ps 117:2-118:2')}) == 1
+assert VerseRange('Röm 8,31-39', locale='de').format(locale='de') == 'Röm 8,31-39'
+try:
+    VerseRange('Jn 3:18-16')
+    assert False
+except InvalidRange:
+    pass
+");
+    }
+}
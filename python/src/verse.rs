@@ -0,0 +1,123 @@
+use crate::book::Book;
+use crate::errors::parse_error;
+use crate::parse_locale;
+use bible_data::{BibleVerse, ReferenceStyle, StyledDisplay};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// A single verse
+///
+/// Verses compare in the order they occur in the Bible.
+#[pyclass(
+    name = "Verse",
+    module = "bible_data",
+    eq,
+    ord,
+    hash,
+    frozen,
+    from_py_object
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Verse(pub BibleVerse);
+
+#[pymethods]
+impl Verse {
+    /// Parse a verse such as `Jn 3:16`, with the book names and separators of the
+    /// given locale
+    #[new]
+    #[pyo3(signature = (reference, locale = None))]
+    fn new(reference: &str, locale: Option<&str>) -> PyResult<Self> {
+        BibleVerse::from_str_in(reference, parse_locale(locale)?)
+            .map(Verse)
+            .map_err(parse_error)
+    }
+
+    /// The verse with the given book, chapter and verse numbers
+    #[staticmethod]
+    fn of(book: Book, chapter: u8, verse: u8) -> PyResult<Self> {
+        let verse = BibleVerse::new(book.0, chapter, verse);
+        match verse.is_valid() {
+            true => Ok(Verse(verse)),
+            false => Err(PyValueError::new_err(format!("{} is not a verse", verse))),
+        }
+    }
+
+    /// The verse with the given OSIS identifier, such as `John.3.16`
+    #[staticmethod]
+    fn from_osis(id: &str) -> PyResult<Self> {
+        BibleVerse::from_osis(id).map(Verse).map_err(parse_error)
+    }
+
+    #[getter]
+    fn book(&self) -> Book {
+        Book(self.0.book)
+    }
+
+    #[getter]
+    fn chapter(&self) -> u8 {
+        self.0.chapter
+    }
+
+    #[getter]
+    fn verse(&self) -> u8 {
+        self.0.verse
+    }
+
+    /// Whether the book has this chapter and the chapter has this verse
+    fn is_valid(&self) -> bool {
+        self.0.is_valid()
+    }
+
+    #[pyo3(name = "to_osis")]
+    fn osis(&self) -> String {
+        self.0.to_osis()
+    }
+
+    /// Write the verse with the book names and separators of the given locale
+    #[pyo3(signature = (locale = None))]
+    fn format(&self, locale: Option<&str>) -> PyResult<String> {
+        let style = ReferenceStyle::for_locale(parse_locale(locale)?);
+        Ok(self.0.display_with(&style).to_string())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Verse('{}')", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::run;
+
+    #[test]
+    fn test_verse() {
+        run(c"
+verse = Verse('Jn 3:16')
+assert verse == Verse.of(Book('John'), 3, 16) == Verse.from_osis('John.3.16')
+assert str(verse) == 'Jn 3:16' and repr(verse) == \"Verse('Jn 3:16')\"
+assert verse.book == Book('John') and verse.chapter == 3 and verse.verse == 16
+assert verse.to_osis() == 'John.3.16' and verse.is_valid()
+assert verse.format(locale='de') == 'Joh 3,16'
+assert Verse('Joh 3,16', locale='de') == verse
+assert Verse('Jn 3:15') < verse < Verse('Ac 1:1')
+assert sorted([Verse('Rev 1:1'), Verse('Ge 1:1')])[0] == Verse('Ge 1:1')
+assert len({verse, Verse('Jn 3:16')}) == 1
+assert Verse('Jude 3') == Verse.of(Book('Jude'), 1, 3)
+for text, error in [('Hezekiah 1:1', NoSuchBookError), ('Jn', NoChapterSpecified), ('Jn 3:x', NotANumber)]:
+    try:
+        Verse(text)
+        assert False, text
+    except error:
+        pass
+try:
+    Verse.of(Book('John'), 3, 99)
+    assert False
+except ValueError:
+    pass
+");
+    }
+}
@@ -0,0 +1,195 @@
+//! Command-line argument parsing
+//!
+//! Hand-rolled rather than using an argument parsing crate so that the tool, like the
+//! library, has no dependencies.
+
+use std::fmt::Display;
+
+/// The long options that take a value, with their short forms
+const VALUE_OPTIONS: [(&str, Option<char>); 9] = [
+    ("format", Some('f')),
+    ("style", Some('s')),
+    ("from", None),
+    ("to", None),
+    ("locale", Some('l')),
+    ("testament", Some('t')),
+    ("canon", None),
+    ("width", Some('w')),
+    ("listen", None),
+];
+
+/// The long options that are flags, with their short forms
+const FLAG_OPTIONS: [(&str, Option<char>); 3] =
+    [("help", Some('h')), ("json", None), ("color", None)];
+
+/// Arguments that could not be understood
+#[derive(Debug, PartialEq, Eq)]
+pub struct UsageError(pub String);
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The parsed command line: a command followed by its positional arguments and options
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Args {
+    pub command: Option<String>,
+    pub positional: Vec<String>,
+    options: Vec<(&'static str, String)>,
+    flags: Vec<&'static str>,
+}
+
+impl Args {
+    /// Parse the arguments, not including the program name
+    ///
+    /// Options may come before or after positional arguments and their values may be
+    /// given as `--name value`, `--name=value` or `-n value`. Everything after `--`
+    /// is positional.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, UsageError> {
+        let mut result = Args::default();
+        let mut args = args.into_iter();
+        let mut options_ended = false;
+        while let Some(arg) = args.next() {
+            let (name, value) = match arg.strip_prefix("--") {
+                _ if options_ended => (None, None),
+                Some("") => {
+                    options_ended = true;
+                    continue;
+                }
+                Some(long) => match long.split_once('=') {
+                    Some((name, value)) => (Some(long_name(name)?), Some(value.to_string())),
+                    None => (Some(long_name(long)?), None),
+                },
+                None => match arg.strip_prefix('-') {
+                    Some(short) if short.chars().count() == 1 => {
+                        (Some(short_name(short.chars().next().unwrap())?), None)
+                    }
+                    _ => (None, None),
+                },
+            };
+            let Some(name) = name else {
+                match result.command {
+                    None => result.command = Some(arg),
+                    Some(_) => result.positional.push(arg),
+                }
+                continue;
+            };
+            if FLAG_OPTIONS.iter().any(|(flag, _)| *flag == name) {
+                if value.is_some() {
+                    return Err(UsageError(format!("--{} does not take a value", name)));
+                }
+                result.flags.push(name);
+                continue;
+            }
+            let value = match value {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .ok_or_else(|| UsageError(format!("--{} needs a value", name)))?,
+            };
+            result.options.push((name, value));
+        }
+        Ok(result)
+    }
+
+    /// Return the value of an option. If it was given more than once the last wins.
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(option, _)| *option == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Return if a flag was given
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(&name)
+    }
+}
+
+fn all_options() -> impl Iterator<Item = &'static (&'static str, Option<char>)> {
+    VALUE_OPTIONS.iter().chain(FLAG_OPTIONS.iter())
+}
+
+fn long_name(name: &str) -> Result<&'static str, UsageError> {
+    all_options()
+        .map(|(long, _)| *long)
+        .find(|long| *long == name)
+        .ok_or_else(|| UsageError(format!("Unknown option --{}", name)))
+}
+
+fn short_name(name: char) -> Result<&'static str, UsageError> {
+    all_options()
+        .find(|(_, short)| *short == Some(name))
+        .map(|(long, _)| *long)
+        .ok_or_else(|| UsageError(format!("Unknown option -{}", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, UsageError> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse(&[
+            "parse",
+            "Jn 3:16",
+            "-f",
+            "json",
+            "--style=continental",
+            "Ro 8",
+        ])
+        .unwrap();
+        assert_eq!(args.command.as_deref(), Some("parse"));
+        assert_eq!(args.positional, ["Jn 3:16", "Ro 8"]);
+        assert_eq!(args.option("format"), Some("json"));
+        assert_eq!(args.option("style"), Some("continental"));
+        assert_eq!(args.option("locale"), None);
+        assert!(!args.flag("json"));
+    }
+
+    #[test]
+    fn test_flags_and_end_of_options() {
+        let args = parse(&[
+            "normalize",
+            "--json",
+            "--to",
+            "en",
+            "--to",
+            "de",
+            "--",
+            "-h",
+        ])
+        .unwrap();
+        assert!(args.flag("json"));
+        assert!(!args.flag("help"));
+        assert_eq!(args.option("to"), Some("de"));
+        assert_eq!(args.positional, ["-h"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse(&["list", "--bible", "x"]),
+            Err(UsageError("Unknown option --bible".to_string()))
+        );
+        assert_eq!(
+            parse(&["list", "-x"]),
+            Err(UsageError("Unknown option -x".to_string()))
+        );
+        assert_eq!(
+            parse(&["list", "--testament"]),
+            Err(UsageError("--testament needs a value".to_string()))
+        );
+        assert_eq!(
+            parse(&["list", "--json=yes"]),
+            Err(UsageError("--json does not take a value".to_string()))
+        );
+    }
+}
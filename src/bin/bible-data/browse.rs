@@ -0,0 +1,58 @@
+//! The `browse` command, an interactive terminal browser of translation files
+//!
+//! Only built with the `tui` feature.
+
+mod app;
+mod suggest;
+mod ui;
+
+use crate::CliError;
+use crate::args::Args;
+use crate::read::load;
+use app::{App, Key};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use std::path::Path;
+
+fn key(code: KeyCode) -> Option<Key> {
+    Some(match code {
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        _ => return None,
+    })
+}
+
+/// Browse the translations in the files until the user quits
+pub fn browse(args: &Args) -> Result<(), CliError> {
+    if args.positional.is_empty() {
+        return Err(CliError::Usage("At least one file is needed".to_string()));
+    }
+    let texts = args
+        .positional
+        .iter()
+        .map(|file| load(Path::new(file)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut app = App::new(texts);
+    let mut terminal = ratatui::init();
+    let result = (|| {
+        while !app.quit {
+            terminal.draw(|frame| ui::draw(frame, &app))?;
+            if let Event::Key(event) = event::read()?
+                && event.kind == KeyEventKind::Press
+                && let Some(key) = key(event.code)
+            {
+                app.key(key);
+            }
+        }
+        Ok(())
+    })();
+    ratatui::restore();
+    result.map_err(CliError::Io)
+}
@@ -0,0 +1,431 @@
+//! The state of the browser and how it responds to keys, kept apart from drawing
+//! so it can be tested without a terminal
+
+use super::suggest::suggest;
+use bible_data::search::{Query, Scope, SearchIndex};
+use bible_data::text::BibleText;
+use bible_data::{BibleBook, BibleChapter, BibleReference, BibleVerse, Locale, ReferenceStyle};
+
+/// The number of books suggested for a reference that does not parse
+const SUGGESTIONS: usize = 3;
+
+/// The number of lines Page Up and Page Down scroll by
+const PAGE: u16 = 10;
+
+/// The keys the browser responds to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+}
+
+/// What the browser is showing and where keys go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Reading a chapter
+    Read,
+    /// Choosing a book from a list
+    Books,
+    /// Choosing a chapter of the book chosen
+    Chapters(BibleBook),
+    /// Typing a reference to go to
+    Jump,
+    /// Typing a search query
+    Search,
+    /// Choosing from the verses a search found
+    Results,
+}
+
+pub struct App {
+    pub texts: Vec<BibleText>,
+    /// The translation being read
+    pub translation: usize,
+    pub chapter: BibleChapter,
+    /// The verse gone to by a reference or search, which is highlighted
+    pub verse: Option<u8>,
+    pub scroll: u16,
+    pub mode: Mode,
+    /// The reference or query being typed
+    pub input: String,
+    /// The selected item of the book, chapter or result list
+    pub selected: usize,
+    pub results: Vec<BibleVerse>,
+    /// A message for the status line, such as an error
+    pub message: Option<String>,
+    pub quit: bool,
+    indexes: Vec<Option<SearchIndex>>,
+}
+
+impl App {
+    /// Start at the first chapter of the first translation that has any text
+    pub fn new(texts: Vec<BibleText>) -> Self {
+        let chapter = texts
+            .first()
+            .and_then(|text| text.iter().next())
+            .and_then(|(verse, _)| BibleChapter::new(verse.book, verse.chapter))
+            .unwrap_or(BibleChapter {
+                book: BibleBook::Genesis,
+                chapter: 1,
+            });
+        let indexes = texts.iter().map(|_| None).collect();
+        App {
+            texts,
+            translation: 0,
+            chapter,
+            verse: None,
+            scroll: 0,
+            mode: Mode::Read,
+            input: String::new(),
+            selected: 0,
+            results: Vec::new(),
+            message: None,
+            quit: false,
+            indexes,
+        }
+    }
+
+    pub fn text(&self) -> &BibleText {
+        &self.texts[self.translation]
+    }
+
+    /// The verses of the current chapter in the current translation
+    pub fn verses(&self) -> Vec<(BibleVerse, &str)> {
+        self.text().chapter(&self.chapter).collect()
+    }
+
+    /// Show a chapter, scrolled to a verse if one is given
+    pub fn go_to(&mut self, chapter: BibleChapter, verse: Option<u8>) {
+        self.chapter = chapter;
+        self.verse = verse;
+        self.scroll = verse.map_or(0, |verse| verse as u16 - 1);
+        self.mode = Mode::Read;
+    }
+
+    /// Go to the chapter after or before this one, moving into the next or previous book
+    pub fn step_chapter(&mut self, forward: bool) {
+        let BibleChapter { book, chapter } = self.chapter;
+        let next = match forward {
+            true if (chapter as u32) < book.number_of_chapters() => {
+                BibleChapter::new(book, chapter + 1)
+            }
+            true => BibleBook::from_index(book.index() + 1)
+                .ok()
+                .and_then(|book| BibleChapter::new(book, 1)),
+            false if chapter > 1 => BibleChapter::new(book, chapter - 1),
+            false => book
+                .index()
+                .checked_sub(1)
+                .and_then(|index| BibleBook::from_index(index).ok())
+                .and_then(|book| BibleChapter::new(book, book.number_of_chapters() as u8)),
+        };
+        match next {
+            Some(next) => self.go_to(next, None),
+            None => self.message = Some("No more chapters".to_string()),
+        }
+    }
+
+    /// The books suggested for the reference being typed
+    pub fn suggestions(&self) -> Vec<BibleBook> {
+        match BibleReference::from_str_with(self.input.trim(), &ReferenceStyle::ENGLISH) {
+            Ok(_) => Vec::new(),
+            Err(_) => suggest(&self.input, Locale::English, SUGGESTIONS),
+        }
+    }
+
+    /// The number of items in the list being chosen from
+    pub fn list_len(&self) -> usize {
+        match self.mode {
+            Mode::Books => 66,
+            Mode::Chapters(book) => book.number_of_chapters() as usize,
+            Mode::Results => self.results.len(),
+            _ => 0,
+        }
+    }
+
+    fn jump(&mut self) {
+        let input = self.input.trim();
+        let chapter = match BibleReference::from_str_with(input, &ReferenceStyle::ENGLISH) {
+            Ok(BibleReference::Book(book)) => BibleChapter::new(book, 1).map(|c| (c, None)),
+            Ok(BibleReference::Chapter(chapter)) => Some((chapter, None)),
+            Ok(BibleReference::Verse(verse)) => {
+                BibleChapter::new(verse.book, verse.chapter).map(|c| (c, Some(verse.verse)))
+            }
+            Ok(BibleReference::Range(range)) => {
+                BibleChapter::new(range.book, range.range.start().chapter)
+                    .map(|c| (c, Some(range.range.start().verse)))
+            }
+            Ok(BibleReference::List(list)) => list.iter().next().and_then(|range| {
+                BibleChapter::new(range.book, range.range.start().chapter)
+                    .map(|c| (c, Some(range.range.start().verse)))
+            }),
+            Err(_) => None,
+        };
+        match chapter {
+            Some((chapter, verse)) => self.go_to(chapter, verse),
+            None => {
+                let suggestions: Vec<&str> = self
+                    .suggestions()
+                    .iter()
+                    .map(|book| book.name_in(Locale::English))
+                    .collect();
+                self.message = Some(match suggestions.is_empty() {
+                    true => format!("No such reference {}", input),
+                    false => format!(
+                        "No such reference {}. Did you mean {}?",
+                        input,
+                        suggestions.join(", ")
+                    ),
+                });
+                self.mode = Mode::Read;
+            }
+        }
+    }
+
+    fn search(&mut self) {
+        let query = match Query::parse(&self.input) {
+            Ok(query) => query,
+            Err(e) => {
+                self.message = Some(e.to_string());
+                self.mode = Mode::Read;
+                return;
+            }
+        };
+        let text = &self.texts[self.translation];
+        let index = self.indexes[self.translation].get_or_insert_with(|| SearchIndex::from(text));
+        self.results = index
+            .search(&query, &Scope::All)
+            .into_iter()
+            .map(|hit| hit.verse)
+            .collect();
+        match self.results.is_empty() {
+            true => {
+                self.message = Some(format!("No verses match {}", self.input));
+                self.mode = Mode::Read;
+            }
+            false => {
+                self.message = Some(format!("{} verses", self.results.len()));
+                self.selected = 0;
+                self.mode = Mode::Results;
+            }
+        }
+    }
+
+    /// Choose the selected item of the list being shown
+    fn choose(&mut self) {
+        match self.mode {
+            Mode::Books => {
+                if let Ok(book) = BibleBook::from_index(self.selected) {
+                    self.mode = Mode::Chapters(book);
+                    self.selected = 0;
+                }
+            }
+            Mode::Chapters(book) => {
+                if let Some(chapter) = BibleChapter::new(book, self.selected as u8 + 1) {
+                    self.go_to(chapter, None);
+                }
+            }
+            Mode::Results => {
+                if let Some(verse) = self.results.get(self.selected).copied()
+                    && let Some(chapter) = BibleChapter::new(verse.book, verse.chapter)
+                {
+                    self.go_to(chapter, Some(verse.verse));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Respond to a key
+    pub fn key(&mut self, key: Key) {
+        if !matches!(self.mode, Mode::Results) {
+            self.message = None;
+        }
+        match (self.mode, key) {
+            (Mode::Jump | Mode::Search, Key::Esc) => self.mode = Mode::Read,
+            (Mode::Jump | Mode::Search, Key::Backspace) => {
+                self.input.pop();
+            }
+            (Mode::Jump | Mode::Search, Key::Char(c)) => self.input.push(c),
+            (Mode::Jump, Key::Enter) => self.jump(),
+            (Mode::Search, Key::Enter) => self.search(),
+            (Mode::Jump | Mode::Search, _) => {}
+            (Mode::Books | Mode::Chapters(_) | Mode::Results, Key::Esc | Key::Char('q')) => {
+                self.mode = Mode::Read
+            }
+            (Mode::Books | Mode::Chapters(_) | Mode::Results, Key::Up | Key::Char('k')) => {
+                self.selected = self.selected.saturating_sub(1)
+            }
+            (Mode::Books | Mode::Chapters(_) | Mode::Results, Key::Down | Key::Char('j')) => {
+                self.selected = (self.selected + 1).min(self.list_len() - 1)
+            }
+            (Mode::Books | Mode::Chapters(_) | Mode::Results, Key::Enter) => self.choose(),
+            (Mode::Books | Mode::Chapters(_) | Mode::Results, _) => {}
+            (Mode::Read, Key::Esc | Key::Char('q')) => self.quit = true,
+            (Mode::Read, Key::Right | Key::Char('n')) => self.step_chapter(true),
+            (Mode::Read, Key::Left | Key::Char('p')) => self.step_chapter(false),
+            (Mode::Read, Key::Up | Key::Char('k')) => self.scroll = self.scroll.saturating_sub(1),
+            (Mode::Read, Key::Down | Key::Char('j')) => self.scroll = self.scroll.saturating_add(1),
+            (Mode::Read, Key::PageUp) => self.scroll = self.scroll.saturating_sub(PAGE),
+            (Mode::Read, Key::PageDown) => self.scroll = self.scroll.saturating_add(PAGE),
+            (Mode::Read, Key::Char('b')) => {
+                self.selected = self.chapter.book.index();
+                self.mode = Mode::Books;
+            }
+            (Mode::Read, Key::Char('c')) => {
+                self.selected = self.chapter.chapter as usize - 1;
+                self.mode = Mode::Chapters(self.chapter.book);
+            }
+            (Mode::Read, Key::Char('g' | ':')) => {
+                self.input.clear();
+                self.mode = Mode::Jump;
+            }
+            (Mode::Read, Key::Char('/')) => {
+                self.input.clear();
+                self.mode = Mode::Search;
+            }
+            (Mode::Read, Key::Char('r')) if !self.results.is_empty() => self.mode = Mode::Results,
+            (Mode::Read, Key::Char('t')) => {
+                self.translation = (self.translation + 1) % self.texts.len();
+                self.results.clear();
+            }
+            (Mode::Read, _) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bible_data::text::{Metadata, VerseText};
+
+    fn app() -> App {
+        let verse = |book, chapter, verse, text| {
+            VerseText::new(BibleVerse::new(book, chapter, verse), text)
+        };
+        let kjv = BibleText::from_verses(
+            Metadata::new("King James Version", "KJV", "en"),
+            [
+                verse(
+                    BibleBook::Ruth,
+                    4,
+                    22,
+                    "And Obed begat Jesse, and Jesse begat David.",
+                ),
+                verse(BibleBook::John, 11, 35, "Jesus wept."),
+                verse(
+                    BibleBook::John,
+                    11,
+                    36,
+                    "Then said the Jews, Behold how he loved him!",
+                ),
+            ],
+        );
+        let web = BibleText::from_verses(
+            Metadata::new("World English Bible", "WEB", "en"),
+            [verse(BibleBook::John, 11, 35, "Jesus wept.")],
+        );
+        App::new(vec![kjv, web])
+    }
+
+    fn keys(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.key(Key::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_start_and_step() {
+        let mut app = app();
+        assert_eq!(app.chapter.to_string(), "Ru 4");
+        app.key(Key::Right);
+        assert_eq!(app.chapter.to_string(), "1Sa 1");
+        app.key(Key::Left);
+        app.key(Key::Char('p'));
+        assert_eq!(app.chapter.to_string(), "Ru 3");
+        app.go_to(BibleChapter::new(BibleBook::Revelation, 22).unwrap(), None);
+        app.key(Key::Char('n'));
+        assert_eq!(app.chapter.to_string(), "Rev 22");
+        assert_eq!(app.message.as_deref(), Some("No more chapters"));
+    }
+
+    #[test]
+    fn test_pickers() {
+        let mut app = app();
+        app.key(Key::Char('b'));
+        assert_eq!(app.mode, Mode::Books);
+        assert_eq!(app.selected, BibleBook::Ruth.index());
+        app.key(Key::Down);
+        app.key(Key::Enter);
+        assert_eq!(app.mode, Mode::Chapters(BibleBook::FirstSamuel));
+        assert_eq!(app.list_len(), 31);
+        app.key(Key::Down);
+        app.key(Key::Down);
+        app.key(Key::Enter);
+        assert_eq!(app.mode, Mode::Read);
+        assert_eq!(app.chapter.to_string(), "1Sa 3");
+        app.key(Key::Char('c'));
+        assert_eq!(app.selected, 2);
+        app.key(Key::Esc);
+        assert_eq!(app.mode, Mode::Read);
+    }
+
+    #[test]
+    fn test_jump() {
+        let mut app = app();
+        app.key(Key::Char('g'));
+        keys(&mut app, "Jn 11:36");
+        app.key(Key::Enter);
+        assert_eq!(app.chapter.to_string(), "Jn 11");
+        assert_eq!(app.verse, Some(36));
+        assert_eq!(app.verses().len(), 2);
+        app.key(Key::Char('g'));
+        keys(&mut app, "Jhn 3");
+        assert_eq!(app.suggestions(), [BibleBook::John]);
+        app.key(Key::Enter);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("No such reference Jhn 3. Did you mean John?")
+        );
+        assert_eq!(app.chapter.to_string(), "Jn 11");
+    }
+
+    #[test]
+    fn test_search() {
+        let mut app = app();
+        app.key(Key::Char('/'));
+        keys(&mut app, "jesse OR jesus");
+        app.key(Key::Enter);
+        assert_eq!(app.mode, Mode::Results);
+        assert_eq!(app.results.len(), 2);
+        app.key(Key::Down);
+        app.key(Key::Enter);
+        assert_eq!(app.chapter.to_string(), "Jn 11");
+        assert_eq!(app.verse, Some(35));
+        app.key(Key::Char('/'));
+        keys(&mut app, "moses");
+        app.key(Key::Enter);
+        assert_eq!(app.mode, Mode::Read);
+        assert_eq!(app.message.as_deref(), Some("No verses match moses"));
+    }
+
+    #[test]
+    fn test_translation() {
+        let mut app = app();
+        app.go_to(BibleChapter::new(BibleBook::John, 11).unwrap(), None);
+        app.key(Key::Char('t'));
+        assert_eq!(app.text().metadata().abbrev, "WEB");
+        assert_eq!(app.verses().len(), 1);
+        app.key(Key::Char('t'));
+        assert_eq!(app.text().metadata().abbrev, "KJV");
+        app.key(Key::Char('q'));
+        assert!(app.quit);
+    }
+}
@@ -0,0 +1,101 @@
+//! Fuzzy suggestions of the book meant by a mistyped reference
+
+use bible_data::{BibleBook, Locale};
+
+/// Return the number of single character edits to turn one string into another
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Return the part of a reference before the chapter, eg. `1 Jhn` from `1 Jhn 3:16`
+fn book_part(input: &str) -> String {
+    let mut words = input.split_whitespace();
+    let mut book: Vec<&str> = words.next().into_iter().collect();
+    book.extend(words.take_while(|word| !word.starts_with(|c: char| c.is_ascii_digit())));
+    book.join(" ").to_lowercase()
+}
+
+/// Suggest the books that the start of a reference may have meant, best first
+///
+/// Books whose name or abbreviation starts with what was typed come first, then those
+/// within a few edits of it. At most `count` books are returned.
+pub fn suggest(input: &str, locale: Locale, count: usize) -> Vec<BibleBook> {
+    let typed = book_part(input);
+    if typed.is_empty() {
+        return Vec::new();
+    }
+    let allowed = (typed.chars().count() / 3).max(1);
+    let mut scored: Vec<(usize, BibleBook)> = BibleBook::iter()
+        .filter_map(|book| {
+            let score = [book.name_in(locale), book.abbrev_in(locale)]
+                .iter()
+                .map(|candidate| {
+                    let candidate = candidate.to_lowercase();
+                    match candidate.starts_with(&typed) {
+                        true => 0,
+                        false => edit_distance(&candidate, &typed),
+                    }
+                })
+                .min()?;
+            (score <= allowed).then_some((score, book))
+        })
+        .collect();
+    scored.sort_by_key(|(score, book)| (*score, book.index()));
+    scored
+        .into_iter()
+        .take(count)
+        .map(|(_, book)| book)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("john", "jhn"), 1);
+        assert_eq!(edit_distance("genesis", "genisis"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_book_part() {
+        assert_eq!(book_part("1 Jhn 3:16"), "1 jhn");
+        assert_eq!(book_part("Song of Songs 2"), "song of songs");
+        assert_eq!(book_part("  "), "");
+    }
+
+    #[test]
+    fn test_suggest() {
+        assert_eq!(suggest("Jhn 3:16", Locale::English, 3), [BibleBook::John]);
+        assert_eq!(
+            suggest("Jo 3", Locale::English, 3),
+            [BibleBook::Joshua, BibleBook::Job, BibleBook::Joel]
+        );
+        assert_eq!(
+            suggest("Genisis 1", Locale::English, 3),
+            [BibleBook::Genesis]
+        );
+        assert_eq!(
+            suggest("Phil", Locale::English, 3),
+            [BibleBook::Philippians, BibleBook::Philemon]
+        );
+        assert_eq!(
+            suggest("Offenbarug", Locale::German, 1),
+            [BibleBook::Revelation]
+        );
+        assert!(suggest("Xyzzy 1", Locale::English, 3).is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+//! Drawing the browser with ratatui
+
+use super::app::{App, Mode};
+use bible_data::{BibleBook, BibleChapter, Locale};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListState, Paragraph, Wrap};
+
+const HELP: &str =
+    "n/p chapter  b book  c chapter  g go to  / search  r results  t translation  q quit";
+
+/// Draw the title, the chapter or list being shown and the status line
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [title, body, status] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let metadata = app.text().metadata();
+    let BibleChapter { book, chapter } = app.chapter;
+    frame.render_widget(
+        Line::from(format!(
+            " {} {}  ({})",
+            book.name(),
+            chapter,
+            metadata.abbrev
+        ))
+        .bold()
+        .reversed(),
+        title,
+    );
+
+    let block = Block::default().borders(Borders::BOTTOM);
+    let highlight = Style::default().add_modifier(Modifier::REVERSED);
+    let list = match app.mode {
+        Mode::Books => Some(
+            BibleBook::iter()
+                .map(|book| format!("{:<5} {}", book.abbrev(), book.name()))
+                .collect::<Vec<_>>(),
+        ),
+        Mode::Chapters(book) => Some(
+            (1..=book.number_of_chapters())
+                .map(|chapter| format!("{} {}", book.name(), chapter))
+                .collect(),
+        ),
+        Mode::Results => Some(
+            app.results
+                .iter()
+                .map(|verse| {
+                    format!(
+                        "{:<12} {}",
+                        verse.to_string(),
+                        app.text().get(verse).unwrap_or_default()
+                    )
+                })
+                .collect(),
+        ),
+        Mode::Read | Mode::Jump | Mode::Search => None,
+    };
+    match list {
+        Some(items) => {
+            let mut state = ListState::default().with_selected(Some(app.selected));
+            let list = List::new(items).block(block).highlight_style(highlight);
+            frame.render_stateful_widget(list, body, &mut state);
+        }
+        None => {
+            let verses = app.verses();
+            let lines: Vec<Line> = match verses.is_empty() {
+                true => {
+                    vec![Line::from(format!("{} is not in this translation", app.chapter)).italic()]
+                }
+                false => verses
+                    .into_iter()
+                    .map(|(verse, text)| {
+                        let line = Line::from(vec![
+                            Span::from(format!("{:>3} ", verse.verse)).bold(),
+                            Span::from(text.to_string()),
+                        ]);
+                        match app.verse == Some(verse.verse) {
+                            true => line.style(highlight),
+                            false => line,
+                        }
+                    })
+                    .collect(),
+            };
+            let paragraph = Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false })
+                .scroll((app.scroll, 0));
+            frame.render_widget(paragraph, body);
+        }
+    }
+
+    let line = match app.mode {
+        Mode::Jump => {
+            let suggestions: Vec<&str> = app
+                .suggestions()
+                .iter()
+                .map(|book| book.name_in(Locale::English))
+                .collect();
+            let mut line = format!("Go to: {}", app.input);
+            if !suggestions.is_empty() && !app.input.trim().is_empty() {
+                line.push_str(&format!("    ({}?)", suggestions.join(", ")));
+            }
+            line
+        }
+        Mode::Search => format!("Search: {}", app.input),
+        _ => app.message.clone().unwrap_or_else(|| HELP.to_string()),
+    };
+    frame.render_widget(Line::from(line), status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bible_data::BibleVerse;
+    use bible_data::text::{BibleText, Metadata, VerseText};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn screen(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_draw() {
+        let text = BibleText::from_verses(
+            Metadata::new("King James Version", "KJV", "en"),
+            [VerseText::new(
+                BibleVerse::new(BibleBook::John, 11, 35),
+                "Jesus wept.",
+            )],
+        );
+        let mut app = App::new(vec![text]);
+        let lines = screen(&app);
+        assert_eq!(lines[0], " John 11  (KJV)");
+        assert_eq!(lines[1], " 35 Jesus wept.");
+        assert!(lines[5].starts_with("n/p chapter"));
+        app.mode = Mode::Jump;
+        app.input = "Jhn".to_string();
+        assert_eq!(screen(&app)[5], "Go to: Jhn    (John?)");
+    }
+}
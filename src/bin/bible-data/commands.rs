@@ -0,0 +1,363 @@
+//! The `parse`, `info` and `list` commands
+
+use crate::CliError;
+use crate::args::Args;
+use bible_data::json::write_string;
+use bible_data::{BibleBook, BibleChapter, BibleReference, CHAPTER_VERSES, Locale, ReferenceStyle};
+use std::io::Write;
+
+/// How results are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn from_args(args: &Args) -> Result<Self, CliError> {
+        match args.option("format") {
+            _ if args.flag("json") => Ok(Format::Json),
+            None | Some("text") => Ok(Format::Text),
+            Some("json") => Ok(Format::Json),
+            Some(other) => Err(CliError::Usage(format!("Unknown format {}", other))),
+        }
+    }
+}
+
+/// Return the style named by an option, with the book names of `--locale` if given
+pub fn style_option(args: &Args, name: &str) -> Result<ReferenceStyle, CliError> {
+    let style = match args.option(name) {
+        None | Some("english") => ReferenceStyle::ENGLISH,
+        Some("continental") => ReferenceStyle::CONTINENTAL,
+        Some("period") => ReferenceStyle::PERIOD,
+        Some(other) => Locale::from_identifier(other)
+            .map(ReferenceStyle::for_locale)
+            .ok_or_else(|| CliError::Usage(format!("Unknown style {}", other)))?,
+    };
+    match locale_option(args)? {
+        Some(locale) => Ok(style.with_locale(locale)),
+        None => Ok(style),
+    }
+}
+
+fn locale_option(args: &Args) -> Result<Option<Locale>, CliError> {
+    match args.option("locale") {
+        None => Ok(None),
+        Some(identifier) => Locale::from_identifier(identifier)
+            .map(Some)
+            .ok_or_else(|| CliError::Usage(format!("Unknown locale {}", identifier))),
+    }
+}
+
+fn references(args: &Args) -> Result<&[String], CliError> {
+    match args.positional.is_empty() {
+        true => Err(CliError::Usage("No reference given".to_string())),
+        false => Ok(&args.positional),
+    }
+}
+
+/// Join the messages for invalid references into a single error, if there were any
+fn invalid(errors: Vec<String>) -> Result<(), CliError> {
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(CliError::Invalid(errors.join("\n"))),
+    }
+}
+
+/// Print each reference in canonical form
+///
+/// Every reference is checked even if an earlier one is invalid. The valid ones are
+/// written out (and in JSON the invalid ones too, with their error) before failing.
+pub fn parse(args: &Args, out: &mut impl Write) -> Result<(), CliError> {
+    let format = Format::from_args(args)?;
+    let style = style_option(args, "style")?;
+    let mut errors = Vec::new();
+    let mut json = String::from("[");
+    for (i, text) in references(args)?.iter().enumerate() {
+        let parsed = BibleReference::from_str_with(text, &style);
+        if format == Format::Json {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str("\n  {\"input\": ");
+            write_string(&mut json, text);
+            match &parsed {
+                Ok(reference) => {
+                    json.push_str(&format!(
+                        ", \"kind\": \"{}\", \"reference\": ",
+                        reference.kind()
+                    ));
+                    write_string(&mut json, &reference.to_string());
+                    json.push_str(", \"book\": ");
+                    write_string(&mut json, reference.book().name());
+                    json.push_str(", \"osis\": ");
+                    write_string(&mut json, &reference.to_osis());
+                }
+                Err(e) => {
+                    json.push_str(", \"error\": ");
+                    write_string(&mut json, &e.to_string());
+                }
+            }
+            json.push('}');
+        }
+        match parsed {
+            Ok(reference) if format == Format::Text => writeln!(out, "{}", reference)?,
+            Ok(_) => {}
+            Err(e) => errors.push(format!("{}: {}", text, e)),
+        }
+    }
+    if format == Format::Json {
+        writeln!(out, "{}\n]", json)?;
+    }
+    invalid(errors)
+}
+
+fn testament(book: BibleBook) -> &'static str {
+    match book.is_old_testament() {
+        true => "Old",
+        false => "New",
+    }
+}
+
+/// Show the names, testament and size of books or chapters
+pub fn info(args: &Args, out: &mut impl Write) -> Result<(), CliError> {
+    let format = Format::from_args(args)?;
+    let style = style_option(args, "style")?;
+    let locale = style.locale;
+    let mut errors = Vec::new();
+    let mut items = Vec::new();
+    for text in references(args)? {
+        let (book, chapter) = match BibleReference::from_str_with(text, &style) {
+            Ok(BibleReference::Book(book)) => (book, None),
+            Ok(BibleReference::Chapter(chapter)) => (chapter.book, Some(chapter)),
+            Ok(_) => {
+                errors.push(format!("{}: Not a book or chapter", text));
+                continue;
+            }
+            Err(e) => {
+                errors.push(format!("{}: {}", text, e));
+                continue;
+            }
+        };
+        let chapter_verses = CHAPTER_VERSES[book.index()];
+        let verses: u32 = chapter_verses.iter().map(|v| *v as u32).sum();
+        match format {
+            Format::Text => {
+                let mut lines = vec![
+                    ("Name", book.name_in(locale).to_string()),
+                    ("Abbreviation", book.abbrev_in(locale).to_string()),
+                    ("OSIS", book.to_osis().to_string()),
+                    ("USFM", book.usfm_code().to_string()),
+                    ("Testament", testament(book).to_string()),
+                    ("Chapters", book.number_of_chapters().to_string()),
+                    ("Verses", verses.to_string()),
+                ];
+                if let Some(BibleChapter { chapter, .. }) = chapter {
+                    let count = chapter_verses[chapter as usize - 1];
+                    lines.push(("Chapter", chapter.to_string()));
+                    lines.push(("Chapter verses", count.to_string()));
+                }
+                let lines: Vec<String> = lines
+                    .into_iter()
+                    .map(|(label, value)| format!("{:<15} {}", format!("{}:", label), value))
+                    .collect();
+                items.push(lines.join("\n"));
+            }
+            Format::Json => {
+                let mut json = String::from("  {\"name\": ");
+                write_string(&mut json, book.name_in(locale));
+                json.push_str(", \"abbrev\": ");
+                write_string(&mut json, book.abbrev_in(locale));
+                json.push_str(&format!(
+                    ", \"osis\": \"{}\", \"usfm\": \"{}\", \"testament\": \"{}\", \
+                     \"chapters\": {}, \"verses\": {}, \"chapter_verses\": {:?}",
+                    book.to_osis(),
+                    book.usfm_code(),
+                    testament(book).to_lowercase(),
+                    book.number_of_chapters(),
+                    verses,
+                    chapter_verses
+                ));
+                if let Some(BibleChapter { chapter, .. }) = chapter {
+                    json.push_str(&format!(", \"chapter\": {}", chapter));
+                }
+                json.push('}');
+                items.push(json);
+            }
+        }
+    }
+    match format {
+        Format::Text if !items.is_empty() => writeln!(out, "{}", items.join("\n\n"))?,
+        Format::Text => {}
+        Format::Json => writeln!(out, "[\n{}\n]", items.join(",\n"))?,
+    }
+    invalid(errors)
+}
+
+/// List the books of the Bible, or of one testament
+pub fn list(args: &Args, out: &mut impl Write) -> Result<(), CliError> {
+    let format = Format::from_args(args)?;
+    let locale = locale_option(args)?.unwrap_or_default();
+    // BibleBook only has the books of the Protestant canon, so no other can be listed
+    if let Some(canon) = args.option("canon")
+        && !canon.eq_ignore_ascii_case("protestant")
+    {
+        return Err(CliError::Usage(format!(
+            "Unknown canon {}. Only protestant is available",
+            canon
+        )));
+    }
+    let include: fn(&BibleBook) -> bool = match args.option("testament") {
+        None => |_| true,
+        Some(testament) => match testament.to_lowercase().as_str() {
+            "ot" | "old" => BibleBook::is_old_testament,
+            "nt" | "new" => BibleBook::is_new_testament,
+            _ => return Err(CliError::Usage(format!("Unknown testament {}", testament))),
+        },
+    };
+    let books = BibleBook::iter().filter(include);
+    match format {
+        Format::Text => {
+            for book in books {
+                writeln!(
+                    out,
+                    "{:>2}  {:<6} {:<20} {:>3}",
+                    book.book_number(),
+                    book.abbrev_in(locale),
+                    book.name_in(locale),
+                    book.number_of_chapters()
+                )?;
+            }
+        }
+        Format::Json => {
+            let items: Vec<String> = books
+                .map(|book| {
+                    let mut json = format!("  {{\"number\": {}, \"name\": ", book.book_number());
+                    write_string(&mut json, book.name_in(locale));
+                    json.push_str(", \"abbrev\": ");
+                    write_string(&mut json, book.abbrev_in(locale));
+                    json.push_str(&format!(
+                        ", \"osis\": \"{}\", \"chapters\": {}}}",
+                        book.to_osis(),
+                        book.number_of_chapters()
+                    ));
+                    json
+                })
+                .collect();
+            writeln!(out, "[\n{}\n]", items.join(",\n"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    fn output(
+        command: fn(&Args, &mut Vec<u8>) -> Result<(), CliError>,
+        arguments: &[&str],
+    ) -> (String, Result<(), CliError>) {
+        let mut out = Vec::new();
+        let result = command(&args(arguments), &mut out);
+        (String::from_utf8(out).unwrap(), result)
+    }
+
+    #[test]
+    fn test_parse() {
+        let (out, result) = output(parse, &["parse", "John 3:16", "Röm 8,28.31-39", "-s", "de"]);
+        assert!(matches!(result, Err(CliError::Invalid(_))));
+        assert_eq!(out, "Ro 8:28, 31-39\n");
+        let (out, result) = output(parse, &["parse", "John 3:16", "Ps 117"]);
+        assert!(result.is_ok());
+        assert_eq!(out, "Jn 3:16\nPs 117\n");
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let (out, result) = output(parse, &["parse", "--json", "Jn 3:16-18", "Jn 99"]);
+        let Err(CliError::Invalid(message)) = result else {
+            panic!("expected an invalid reference");
+        };
+        assert!(message.starts_with("Jn 99: "));
+        assert_eq!(
+            out,
+            "[\n  {\"input\": \"Jn 3:16-18\", \"kind\": \"range\", \"reference\": \"Jn 3:16-18\", \
+             \"book\": \"John\", \"osis\": \"John.3.16-John.3.18\"},\n  {\"input\": \"Jn 99\", \
+             \"error\": \""
+                .to_string()
+                + &message[7..].replace('"', "\\\"")
+                + "\"}\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert!(matches!(
+            output(parse, &["parse"]).1,
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(
+            output(parse, &["parse", "Jn 1", "-f", "xml"]).1,
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(
+            output(list, &["list", "-t", "apocrypha"]).1,
+            Err(CliError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_info() {
+        let (out, result) = output(info, &["info", "Psalms 117"]);
+        assert!(result.is_ok());
+        assert_eq!(
+            out,
+            "Name:           Psalms
+Abbreviation:   Ps
+OSIS:           Ps
+USFM:           PSA
+Testament:      Old
+Chapters:       150
+Verses:         2461
+Chapter:        117
+Chapter verses: 2
+"
+        );
+        let (out, result) = output(info, &["info", "-f", "json", "-l", "de", "Judas"]);
+        assert!(result.is_ok());
+        assert_eq!(
+            out,
+            "[\n  {\"name\": \"Judas\", \"abbrev\": \"Jud\", \"osis\": \"Jude\", \
+             \"usfm\": \"JUD\", \"testament\": \"new\", \"chapters\": 1, \"verses\": 25, \
+             \"chapter_verses\": [25]}\n]\n"
+        );
+        assert!(matches!(
+            output(info, &["info", "Jn 3:16"]).1,
+            Err(CliError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_list() {
+        let (out, result) = output(list, &["list", "--testament", "nt"]);
+        assert!(result.is_ok());
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 27);
+        assert_eq!(lines[0], "40  Mt     Matthew               28");
+        let (out, _) = output(list, &["list", "--json", "-t", "OT", "-l", "es"]);
+        assert!(out.starts_with(
+            "[\n  {\"number\": 1, \"name\": \"Génesis\", \"abbrev\": \"Gn\", \"osis\": \"Gen\", \"chapters\": 50},\n"
+        ));
+        assert_eq!(out.lines().count(), 41);
+        let (out, result) = output(list, &["list", "--canon", "Protestant"]);
+        assert!(result.is_ok());
+        assert_eq!(out.lines().count(), 66);
+        let (_, result) = output(list, &["list", "--canon", "catholic"]);
+        assert!(matches!(result, Err(CliError::Usage(_))));
+    }
+}
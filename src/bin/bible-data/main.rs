@@ -0,0 +1,136 @@
+//! `bible-data` command-line tool for checking, normalising and looking up Bible references
+//! and reading passages from local translation files
+
+mod args;
+#[cfg(feature = "tui")]
+mod browse;
+mod commands;
+mod normalize;
+mod read;
+#[cfg(feature = "server")]
+mod serve;
+
+use args::{Args, UsageError};
+use std::fmt::Display;
+use std::io::{IsTerminal, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: bible-data <COMMAND> [OPTIONS] [ARGS]
+
+Commands:
+  parse <REFERENCE>...   Check references and print them in canonical form
+  normalize              Rewrite the references in standard input in another style
+  info <BOOK|CHAPTER>... Show the names, testament and chapter and verse counts
+  list                   List the books of the Bible
+  browse <FILE>...       Browse translation files in the terminal (with the tui feature)
+  serve [FILE]...        Answer JSON queries over HTTP (with the server feature)
+  read <REFERENCE> <FILE>...
+                         Print a passage from USFM, OSIS, USX, Zefania or binary
+                         files, in parallel if there is more than one
+
+Options:
+  -f, --format <FORMAT>  Output text (the default) or json
+      --json             Same as --format json
+  -s, --style <STYLE>    Style references are written in, for parse, info and read
+      --from <STYLE>     Style of the references to normalize (default english)
+      --to <STYLE>       Style to normalize references to (default english)
+  -l, --locale <LOCALE>  Locale of book names, overriding the style
+  -t, --testament <OT|NT>
+                         Only list the books of one testament
+      --canon <CANON>    Canon to list the books of. Only protestant, the 66 books of
+                         the Protestant canon, is available
+  -w, --width <N>        Wrap read output to N columns, or 0 not to wrap (default 80)
+      --color            Color read output even when it is not to a terminal
+      --listen <ADDRESS> Address to serve on (default 127.0.0.1:8080)
+  -h, --help             Show this help
+
+Styles are english, continental, period or a locale such as de or pt-BR.
+Exits with 1 if any reference is invalid and 2 if the arguments are.";
+
+/// The reasons the tool can fail, each with its own exit code
+#[derive(Debug)]
+pub enum CliError {
+    /// The command line could not be understood
+    Usage(String),
+    /// A reference or other input was not valid
+    Invalid(String),
+    Io(std::io::Error),
+}
+
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => 2,
+            CliError::Invalid(_) | CliError::Io(_) => 1,
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(message) | CliError::Invalid(message) => write!(f, "{}", message),
+            CliError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(value: std::io::Error) -> Self {
+        CliError::Io(value)
+    }
+}
+
+impl From<UsageError> for CliError {
+    fn from(value: UsageError) -> Self {
+        CliError::Usage(value.0)
+    }
+}
+
+fn run(args: &Args) -> Result<(), CliError> {
+    let mut out = std::io::stdout().lock();
+    match args.command.as_deref() {
+        _ if args.flag("help") => writeln!(out, "{}", USAGE).map_err(CliError::from),
+        Some("parse") => commands::parse(args, &mut out),
+        Some("normalize") => normalize::normalize(args, std::io::stdin().lock(), &mut out),
+        Some("info") => commands::info(args, &mut out),
+        Some("list") => commands::list(args, &mut out),
+        Some("read") => {
+            let color = out.is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            read::read(args, &mut out, color)
+        }
+        #[cfg(feature = "tui")]
+        Some("browse") => browse::browse(args),
+        #[cfg(not(feature = "tui"))]
+        Some("browse") => Err(CliError::Usage(
+            "browse needs bible-data to be built with the tui feature".to_string(),
+        )),
+        #[cfg(feature = "server")]
+        Some("serve") => serve::serve(args),
+        #[cfg(not(feature = "server"))]
+        Some("serve") => Err(CliError::Usage(
+            "serve needs bible-data to be built with the server feature".to_string(),
+        )),
+        Some(command) => Err(CliError::Usage(format!("Unknown command {}", command))),
+        None => Err(CliError::Usage("No command given".to_string())),
+    }
+}
+
+fn main() -> ExitCode {
+    let result = Args::parse(std::env::args().skip(1))
+        .map_err(CliError::from)
+        .and_then(|args| run(&args));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader of the output went away, as when piped to `head`
+        Err(CliError::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("bible-data: {}", e);
+            if let CliError::Usage(_) = e {
+                eprintln!("\n{}", USAGE);
+            }
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
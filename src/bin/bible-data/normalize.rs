@@ -0,0 +1,202 @@
+//! The `normalize` command, which finds references in text and rewrites them in another style
+
+use crate::CliError;
+use crate::args::Args;
+use crate::commands::{Format, style_option};
+use bible_data::json::write_string;
+use bible_data::{BibleBook, BibleReference, ReferenceStyle, StyledDisplay};
+use std::io::{BufRead, Write};
+use std::ops::Range;
+
+/// The longest text, in bytes, that is tried as a reference
+const MAX_REFERENCE_LEN: usize = 80;
+
+/// A reference found in a line of text
+#[derive(Debug, PartialEq, Eq)]
+pub struct Found {
+    /// The byte range of the reference in the line
+    pub range: Range<usize>,
+    pub reference: BibleReference,
+}
+
+/// Return if the number at `start` is the number of a book such as the `1` of `1 Cor`
+fn starts_book(line: &str, start: usize, style: &ReferenceStyle) -> bool {
+    let rest = &line[start..];
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let words = rest.split(' ').take(3).collect::<Vec<_>>();
+    (1..words.len()).any(|count| {
+        let candidate = words[..=count].join(" ");
+        let candidate = candidate.trim_end_matches(|c: char| !c.is_alphanumeric());
+        candidate.len() > digits && BibleBook::parse_in(candidate, style.locale).is_some()
+    })
+}
+
+/// Find the references written in a style in a line of text
+///
+/// A reference must start at the beginning of a word and end with a number, so a book
+/// name on its own is never taken for a reference. The longest text that parses wins,
+/// except that a list does not take in a number that starts the next book, as in
+/// `Jn 3:16, 1 Corinthians 13`.
+pub fn find_references(line: &str, style: &ReferenceStyle) -> Vec<Found> {
+    let mut found = Vec::new();
+    let mut position = 0;
+    let boundaries: Vec<(usize, char)> = line.char_indices().collect();
+    for (i, &(start, c)) in boundaries.iter().enumerate() {
+        if start < position || !c.is_alphanumeric() {
+            continue;
+        }
+        if i > 0 && boundaries[i - 1].1.is_alphanumeric() {
+            continue;
+        }
+        let ends = boundaries[i..]
+            .iter()
+            .zip(
+                boundaries[i + 1..]
+                    .iter()
+                    .map(|(index, c)| (*index, Some(*c)))
+                    .chain([(line.len(), None)]),
+            )
+            .filter(|((_, c), (_, next))| {
+                c.is_ascii_digit() && !next.is_some_and(|next| next.is_ascii_digit())
+            })
+            .map(|(_, (end, _))| end)
+            .take_while(|end| end - start <= MAX_REFERENCE_LEN)
+            .collect::<Vec<_>>();
+        for &end in ends.iter().rev() {
+            let text = &line[start..end];
+            let number = text
+                .char_indices()
+                .rev()
+                .find(|(_, c)| !c.is_ascii_digit())
+                .map_or(start, |(index, c)| start + index + c.len_utf8());
+            if number > start && starts_book(line, number, style) {
+                continue;
+            }
+            if let Ok(reference) = BibleReference::from_str_with(text, style) {
+                found.push(Found {
+                    range: start..end,
+                    reference,
+                });
+                position = end;
+                break;
+            }
+        }
+    }
+    found
+}
+
+/// Rewrite the references in a line from one style to another
+pub fn rewrite(line: &str, from: &ReferenceStyle, to: &ReferenceStyle) -> String {
+    let mut out = String::new();
+    let mut position = 0;
+    for found in find_references(line, from) {
+        out.push_str(&line[position..found.range.start]);
+        out.push_str(&found.reference.display_with(to).to_string());
+        position = found.range.end;
+    }
+    out.push_str(&line[position..]);
+    out
+}
+
+/// Copy the input to the output with every reference rewritten
+///
+/// In JSON the references found are listed instead, each with its line number, the
+/// text as written and the rewritten reference.
+pub fn normalize(args: &Args, input: impl BufRead, out: &mut impl Write) -> Result<(), CliError> {
+    let format = Format::from_args(args)?;
+    let from = style_option(args, "from")?;
+    let to = style_option(args, "to")?;
+    let mut items = Vec::new();
+    for (number, line) in input.lines().enumerate() {
+        let line = line?;
+        match format {
+            Format::Text => writeln!(out, "{}", rewrite(&line, &from, &to))?,
+            Format::Json => {
+                for found in find_references(&line, &from) {
+                    let mut json = format!("  {{\"line\": {}, \"text\": ", number + 1);
+                    write_string(&mut json, &line[found.range.clone()]);
+                    json.push_str(&format!(
+                        ", \"kind\": \"{}\", \"reference\": ",
+                        found.reference.kind()
+                    ));
+                    write_string(&mut json, &found.reference.display_with(&to).to_string());
+                    json.push('}');
+                    items.push(json);
+                }
+            }
+        }
+    }
+    if format == Format::Json {
+        match items.is_empty() {
+            true => writeln!(out, "[]")?,
+            false => writeln!(out, "[\n{}\n]", items.join(",\n"))?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_references() {
+        let line = "See John 3:16, 1 Corinthians 13 and Ps 23; but not Job or 1 Samuel.";
+        let found = find_references(line, &ReferenceStyle::ENGLISH);
+        let texts: Vec<&str> = found.iter().map(|f| &line[f.range.clone()]).collect();
+        assert_eq!(texts, ["John 3:16", "1 Corinthians 13", "Ps 23"]);
+        assert_eq!(found[1].reference.kind(), "chapter");
+    }
+
+    #[test]
+    fn test_find_lists() {
+        let line = "Read Ro 8:28, 31-39, 12:1. Then 2 Kings 5:1-14";
+        let found = find_references(line, &ReferenceStyle::ENGLISH);
+        let texts: Vec<&str> = found.iter().map(|f| &line[f.range.clone()]).collect();
+        assert_eq!(texts, ["Ro 8:28, 31-39, 12:1", "2 Kings 5:1-14"]);
+    }
+
+    #[test]
+    fn test_find_multibyte() {
+        let line = "café1 and Ps 23—1, Röm 8";
+        let found = find_references(line, &ReferenceStyle::ENGLISH);
+        let texts: Vec<&str> = found.iter().map(|f| &line[f.range.clone()]).collect();
+        assert_eq!(texts, ["Ps 23"]);
+    }
+
+    #[test]
+    fn test_rewrite() {
+        assert_eq!(
+            rewrite(
+                "Vgl. Röm 8,28.31-39 und 1. Mose 1,1.",
+                &ReferenceStyle::CONTINENTAL,
+                &ReferenceStyle::ENGLISH
+            ),
+            "Vgl. Ro 8:28, 31-39 und Ge 1:1."
+        );
+        assert_eq!(
+            rewrite(
+                "John 3:16 and Jn 3.16",
+                &ReferenceStyle::ENGLISH,
+                &ReferenceStyle::PERIOD
+            ),
+            "Jn 3.16 and Jn 3.16"
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let args = Args::parse(["normalize", "--to", "de"].map(String::from)).unwrap();
+        let mut out = Vec::new();
+        normalize(&args, "Jn 3:16\nnone here\n".as_bytes(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Joh 3,16\nnone here\n");
+        let args = Args::parse(["normalize", "--json"].map(String::from)).unwrap();
+        let mut out = Vec::new();
+        normalize(&args, "x\nGenesis 1:1-3 and John 1\n".as_bytes(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[\n  {\"line\": 2, \"text\": \"Genesis 1:1-3\", \"kind\": \"range\", \"reference\": \"Ge 1:1-3\"},\n  \
+             {\"line\": 2, \"text\": \"John 1\", \"kind\": \"chapter\", \"reference\": \"Jn 1\"}\n]\n"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! The `read` command, which prints a passage from local translation files
+
+use crate::CliError;
+use crate::args::Args;
+use crate::commands::{Format, style_option};
+use bible_data::import::{Format as FileFormat, read_verses};
+use bible_data::json::write_string;
+use bible_data::parallel::ParallelView;
+use bible_data::render::{AnsiRenderer, PassageRenderer, PlainTextRenderer};
+use bible_data::text::binary::{BinaryBible, MAGIC};
+use bible_data::text::{BibleText, Metadata};
+use bible_data::{BibleReference, StyledDisplay};
+use std::io::Write;
+use std::path::Path;
+
+/// The width text is wrapped to when `--width` is not given
+const DEFAULT_WIDTH: usize = 80;
+
+/// Load a translation from a USFM, OSIS, USX, Zefania or binary file
+///
+/// Binary files are recognised by their magic number and the others by their extension
+/// or, failing that, their first element or marker. The file name is used as the name
+/// of a translation that does not have one.
+pub fn load(path: &Path) -> Result<BibleText, CliError> {
+    let invalid = |message: String| CliError::Invalid(format!("{}: {}", path.display(), message));
+    let data = std::fs::read(path).map_err(|e| invalid(e.to_string()))?;
+    if data.starts_with(MAGIC) {
+        let binary = BinaryBible::from_bytes(&data).map_err(|e| invalid(e.to_string()))?;
+        return binary.to_bible_text().map_err(|e| invalid(e.to_string()));
+    }
+    let format = path
+        .extension()
+        .and_then(|extension| FileFormat::from_extension(&extension.to_string_lossy()))
+        .or_else(|| FileFormat::detect(&data[..data.len().min(1024)]))
+        .ok_or_else(|| invalid("Unknown file format".to_string()))?;
+    let verses = read_verses(format, data.as_slice(), true)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| invalid(e.to_string()))?;
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let metadata = Metadata::new(name.clone(), name.to_uppercase(), "");
+    Ok(BibleText::from_verses(metadata, verses))
+}
+
+/// Return the number of characters of a string that are shown, skipping escape codes
+fn visible_len(text: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => len += 1,
+        }
+    }
+    len
+}
+
+/// Wrap each line of text at spaces to fit the width
+///
+/// Continuation lines get the indent of the line they came from, plus `hanging` spaces.
+/// A width of 0 leaves the text as it is.
+pub fn wrap(text: &str, width: usize, hanging: usize) -> String {
+    if width == 0 {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for line in text.split_inclusive('\n') {
+        let (line, newline) = match line.strip_suffix('\n') {
+            Some(line) => (line, "\n"),
+            None => (line, ""),
+        };
+        let words = line.trim_start_matches(' ');
+        let indent = " ".repeat(line.len() - words.len());
+        let continuation = format!("{}{}", indent, " ".repeat(hanging));
+        out.push_str(&indent);
+        let mut column = indent.len();
+        let mut first = true;
+        for word in words.split(' ') {
+            let len = visible_len(word);
+            if !first && column + 1 + len > width {
+                out.push('\n');
+                out.push_str(&continuation);
+                column = continuation.len();
+            } else if !first {
+                out.push(' ');
+                column += 1;
+            }
+            out.push_str(word);
+            column += len;
+            first = false;
+        }
+        out.push_str(newline);
+    }
+    out
+}
+
+/// Print a passage with verse numbers, wrapped to the width of the terminal
+///
+/// With more than one file the translations are shown in parallel, verse by verse.
+/// `color` selects escape codes for the terminal in place of plain text.
+pub fn read(args: &Args, out: &mut impl Write, color: bool) -> Result<(), CliError> {
+    let format = Format::from_args(args)?;
+    let style = style_option(args, "style")?;
+    let width = match args.option("width") {
+        None => DEFAULT_WIDTH,
+        Some(width) => width
+            .parse()
+            .map_err(|_| CliError::Usage(format!("Invalid width {}", width)))?,
+    };
+    let color = (color || args.flag("color")) && format == Format::Text;
+    let (text, files) = match args.positional.split_first() {
+        Some((text, files)) if !files.is_empty() => (text, files),
+        _ => {
+            return Err(CliError::Usage(
+                "A reference and at least one file are needed".to_string(),
+            ));
+        }
+    };
+    let reference = BibleReference::from_str_with(text, &style)
+        .map_err(|e| CliError::Invalid(format!("{}: {}", text, e)))?;
+    let texts = files
+        .iter()
+        .map(|file| load(Path::new(file)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let texts: Vec<&BibleText> = texts.iter().collect();
+    let ranges = reference.ranges();
+    let views: Vec<ParallelView> = ranges
+        .iter()
+        .map(|range| ParallelView::new(&texts, range))
+        .collect();
+    if views
+        .iter()
+        .all(|view| view.present_rows().next().is_none())
+    {
+        return Err(CliError::Invalid(format!(
+            "{}: Not in {}",
+            reference.display_with(&style),
+            files.join(", ")
+        )));
+    }
+    match format {
+        Format::Json => {
+            let mut json = String::from("{\"translations\": [");
+            for (i, text) in texts.iter().enumerate() {
+                if i > 0 {
+                    json.push_str(", ");
+                }
+                write_string(&mut json, &text.metadata().abbrev);
+            }
+            json.push_str("], \"verses\": [");
+            let rows = views.iter().flat_map(|view| view.present_rows());
+            for (i, row) in rows.enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                json.push_str("\n  {\"verse\": ");
+                write_string(&mut json, &row.verse.to_string());
+                json.push_str(", \"texts\": [");
+                for (j, text) in row.texts.iter().enumerate() {
+                    if j > 0 {
+                        json.push_str(", ");
+                    }
+                    match text {
+                        Some(text) => write_string(&mut json, text),
+                        None => json.push_str("null"),
+                    }
+                }
+                json.push_str("]}");
+            }
+            writeln!(out, "{}\n]}}", json)?;
+        }
+        Format::Text if texts.len() == 1 => {
+            let rendered: Vec<String> = ranges
+                .iter()
+                .map(|range| match color {
+                    true => AnsiRenderer::default().render_range(texts[0], range),
+                    false => PlainTextRenderer::default().render_range(texts[0], range),
+                })
+                .collect();
+            write!(out, "{}", wrap(&rendered.join("\n"), width, 0))?;
+        }
+        Format::Text => {
+            let label_width = texts
+                .iter()
+                .map(|text| text.metadata().abbrev.chars().count())
+                .max()
+                .unwrap_or_default();
+            let mut first = true;
+            for row in views.iter().flat_map(|view| view.present_rows()) {
+                if !first {
+                    writeln!(out)?;
+                }
+                first = false;
+                writeln!(out, "{}", row.verse)?;
+                for (text, translation) in row.texts.iter().zip(texts.iter()) {
+                    let line = format!(
+                        "  {:<label_width$}  {}",
+                        translation.metadata().abbrev,
+                        text.unwrap_or("-")
+                    );
+                    write!(out, "{}", wrap(&(line + "\n"), width, label_width + 2))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("bible-data-read-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn read_output(arguments: &[&str]) -> (String, Result<(), CliError>) {
+        let args = Args::parse(arguments.iter().map(|arg| arg.to_string())).unwrap();
+        let mut out = Vec::new();
+        let result = read(&args, &mut out, false);
+        (String::from_utf8(out).unwrap(), result)
+    }
+
+    const KJV: &str = "\\id JHN\n\\c 11\n\\p\n\\v 35 Jesus wept.\n\\v 36 Then said the Jews, \
+                       Behold how he loved him!";
+    const WEB: &str =
+        r#"<usx><book code="JHN"/><chapter number="11"/><verse number="35"/>Jesus wept.</usx>"#;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("12 Jesus wept and said\n  a poem line", 11, 3),
+            "12 Jesus\n   wept and\n   said\n  a poem\n     line"
+        );
+        assert_eq!(
+            wrap("\x1b[1m35\x1b[0m Jesus wept.", 12, 0),
+            "\x1b[1m35\x1b[0m Jesus\nwept."
+        );
+        assert_eq!(wrap("left alone", 0, 0), "left alone");
+    }
+
+    #[test]
+    fn test_read() {
+        let kjv = write_file("read.usfm", KJV);
+        let (out, result) =
+            read_output(&["read", "Jn 11:35-36", kjv.to_str().unwrap(), "-w", "31"]);
+        assert!(result.is_ok());
+        assert_eq!(
+            out,
+            "Jn 11:35-36\n\n35 Jesus wept. 36 Then said the\nJews, Behold how he loved him!\n"
+        );
+    }
+
+    #[test]
+    fn test_read_parallel() {
+        let kjv = write_file("kjv.sfm", KJV);
+        let web = write_file("web.usx", WEB);
+        let files = [kjv.to_str().unwrap(), web.to_str().unwrap()];
+        let (out, result) = read_output(&["read", "Jn 11", files[0], files[1], "--width", "31"]);
+        assert!(result.is_ok());
+        assert_eq!(
+            out,
+            "Jn 11:35\n  KJV  Jesus wept.\n  WEB  Jesus wept.\n\nJn 11:36\n  KJV  Then said the Jews,\n       Behold how he loved him!\n  WEB  -\n"
+        );
+        let (out, result) = read_output(&["read", "Jn 11:36", files[0], files[1], "--json"]);
+        assert!(result.is_ok());
+        assert_eq!(
+            out,
+            "{\"translations\": [\"KJV\", \"WEB\"], \"verses\": [\n  {\"verse\": \"Jn 11:36\", \
+             \"texts\": [\"Then said the Jews, Behold how he loved him!\", null]}\n]}\n"
+        );
+    }
+
+    #[test]
+    fn test_read_binary() {
+        let text = load(&write_file("binary.usfm", KJV)).unwrap();
+        let mut data = Vec::new();
+        bible_data::text::binary::write(&text, &mut data).unwrap();
+        let dir = std::env::temp_dir().join(format!("bible-data-read-{}", std::process::id()));
+        let path = dir.join("kjv.bin");
+        std::fs::write(&path, data).unwrap();
+        let (out, result) = read_output(&["read", "John 11:35", path.to_str().unwrap()]);
+        assert!(result.is_ok());
+        assert_eq!(out, "Jn 11:35\n\n35 Jesus wept.\n");
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let kjv = write_file("errors.usfm", KJV);
+        let kjv = kjv.to_str().unwrap();
+        assert!(matches!(
+            read_output(&["read", "Jn 11:35"]).1,
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(
+            read_output(&["read", "Jn 11:35", kjv, "-w", "wide"]).1,
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(
+            read_output(&["read", "Hezekiah 1:1", kjv]).1,
+            Err(CliError::Invalid(_))
+        ));
+        let Err(CliError::Invalid(message)) = read_output(&["read", "Ge 1:1", kjv]).1 else {
+            panic!("expected a missing passage");
+        };
+        assert!(message.starts_with("Ge 1:1: Not in "));
+        assert!(matches!(
+            read_output(&["read", "Jn 11:35", "no-such-file.usfm"]).1,
+            Err(CliError::Invalid(_))
+        ));
+    }
+}
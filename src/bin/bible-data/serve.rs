@@ -0,0 +1,27 @@
+//! The `serve` command, which answers JSON queries over HTTP
+//!
+//! Only built with the `server` feature.
+
+use crate::CliError;
+use crate::args::Args;
+use crate::read::load;
+use bible_data::server::Server;
+use std::net::TcpListener;
+use std::path::Path;
+
+/// The address listened on when `--listen` is not given
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Serve the translations in the files until the server fails
+pub fn serve(args: &Args) -> Result<(), CliError> {
+    let mut server = Server::new();
+    for file in &args.positional {
+        server = server.with_translation(load(Path::new(file))?);
+    }
+    let address = args.option("listen").unwrap_or(DEFAULT_ADDRESS);
+    let listener =
+        TcpListener::bind(address).map_err(|e| CliError::Invalid(format!("{}: {}", address, e)))?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+    server.serve(listener)?;
+    Ok(())
+}
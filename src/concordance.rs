@@ -0,0 +1,487 @@
+//! Mod for generating a concordance and word frequencies from the text of the Bible
+//!
+//! Words are split and folded the same way as for [search](crate::search), so
+//! `LORD` and `Lord` are the same word.
+use crate::json;
+use crate::search::tokenize::{fold, tokenize};
+use crate::text::BibleText;
+use crate::{BibleBook, BibleVerse};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::io::Write;
+use std::ops::Range;
+
+/// Common English words that are usually left out of a concordance
+pub const ENGLISH_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "be", "but", "by", "for", "from", "he", "her", "him", "his", "i",
+    "in", "is", "it", "me", "my", "not", "of", "on", "or", "shall", "she", "that", "the", "thee",
+    "their", "them", "they", "thou", "thy", "to", "unto", "was", "we", "were", "which", "with",
+    "ye", "you",
+];
+
+/// Reduces a word form to its dictionary form, so that eg. `loved` and `loveth` are
+/// listed under `love`
+///
+/// Any `Fn(&str) -> Option<String>` is a Lemmatizer.
+pub trait Lemmatizer {
+    /// Return the lemma of a folded word form, or None to list it under itself
+    fn lemma(&self, form: &str) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String>> Lemmatizer for F {
+    fn lemma(&self, form: &str) -> Option<String> {
+        self(form)
+    }
+}
+
+/// Where a word occurs
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    pub verse: BibleVerse,
+    /// Where the word is in the text of the verse, in bytes
+    pub range: Range<usize>,
+}
+
+/// All the occurrences of a word
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The folded word, or its lemma
+    pub word: String,
+    /// The folded forms of the word that occur, with how often each does
+    pub forms: BTreeMap<String, usize>,
+    /// Every occurrence in canonical order
+    pub occurrences: Vec<Occurrence>,
+}
+
+#[allow(dead_code)]
+impl Entry {
+    /// Return the number of times the word occurs
+    pub fn count(&self) -> usize {
+        self.occurrences.len()
+    }
+
+    /// Iterate over the verses the word occurs in, once each
+    pub fn verses(&self) -> impl Iterator<Item = BibleVerse> + '_ {
+        let mut last = None;
+        self.occurrences
+            .iter()
+            .filter_map(move |o| match last == Some(o.verse) {
+                true => None,
+                false => {
+                    last = Some(o.verse);
+                    last
+                }
+            })
+    }
+
+    /// Return the number of times the word occurs in each book
+    pub fn books(&self) -> BTreeMap<BibleBook, usize> {
+        let mut books = BTreeMap::new();
+        for occurrence in self.occurrences.iter() {
+            *books.entry(occurrence.verse.book).or_default() += 1;
+        }
+        books
+    }
+
+    /// Return the number of times the word occurs in the Old and New Testaments
+    pub fn testaments(&self) -> (usize, usize) {
+        let old = self
+            .occurrences
+            .iter()
+            .filter(|o| o.verse.book.is_old_testament())
+            .count();
+        (old, self.count() - old)
+    }
+}
+
+/// A line of a keyword-in-context listing
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KwicLine {
+    pub verse: BibleVerse,
+    /// The text before the word, padded on the left to the context width
+    pub left: String,
+    /// The word as it is written in the text
+    pub keyword: String,
+    pub right: String,
+}
+
+impl Display for KwicLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<12} {} {} {}",
+            self.verse.to_string(),
+            self.left,
+            self.keyword,
+            self.right
+        )
+    }
+}
+
+/// Options for building a [Concordance]
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::concordance::{ConcordanceBuilder, ENGLISH_STOP_WORDS};
+/// let concordance = ConcordanceBuilder::new()
+///     .stop_words(ENGLISH_STOP_WORDS.iter().copied())
+///     .lemmatizer(|form: &str| form.strip_suffix("eth").map(|stem| format!("{}e", stem)))
+///     .build([
+///         (BibleVerse::new(BibleBook::John, 3, 35), "The Father loveth the Son,"),
+///         (BibleVerse::new(BibleBook::John, 15, 12), "love one another"),
+///     ]);
+/// assert_eq!(concordance.get("love").unwrap().count(), 2);
+/// assert!(concordance.get("the").is_none());
+/// ```
+#[derive(Default)]
+pub struct ConcordanceBuilder<'a> {
+    stop_words: BTreeSet<String>,
+    lemmatizer: Option<Box<dyn Lemmatizer + 'a>>,
+}
+
+#[allow(dead_code)]
+impl<'a> ConcordanceBuilder<'a> {
+    pub fn new() -> Self {
+        ConcordanceBuilder::default()
+    }
+
+    /// Leave these words out of the concordance
+    pub fn stop_words<'w>(mut self, words: impl IntoIterator<Item = &'w str>) -> Self {
+        self.stop_words.extend(words.into_iter().map(fold));
+        self
+    }
+
+    /// List word forms under the lemma given by `lemmatizer`
+    /// Stop words are checked against both the form and the lemma.
+    pub fn lemmatizer(mut self, lemmatizer: impl Lemmatizer + 'a) -> Self {
+        self.lemmatizer = Some(Box::new(lemmatizer));
+        self
+    }
+
+    /// Build a concordance of the given verses
+    pub fn build<'t>(self, verses: impl IntoIterator<Item = (BibleVerse, &'t str)>) -> Concordance {
+        let mut entries: BTreeMap<String, Entry> = BTreeMap::new();
+        let mut total = 0;
+        for (verse, text) in verses {
+            for token in tokenize(text) {
+                total += 1;
+                if self.stop_words.contains(&token.word) {
+                    continue;
+                }
+                let lemma = self.lemmatizer.as_ref().and_then(|l| l.lemma(&token.word));
+                if let Some(lemma) = &lemma
+                    && self.stop_words.contains(lemma)
+                {
+                    continue;
+                }
+                let word = lemma.unwrap_or_else(|| token.word.clone());
+                let entry = entries.entry(word.clone()).or_insert_with(|| Entry {
+                    word,
+                    forms: BTreeMap::new(),
+                    occurrences: Vec::new(),
+                });
+                *entry.forms.entry(token.word).or_default() += 1;
+                entry.occurrences.push(Occurrence {
+                    verse,
+                    range: token.range,
+                });
+            }
+        }
+        Concordance { entries, total }
+    }
+}
+
+/// Every word in a text with where it occurs
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::concordance::Concordance;
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let text = BibleText::from_verses(
+///     Metadata::default(),
+///     [
+///         VerseText::new(BibleVerse::new(BibleBook::Psalms, 23, 1), "The LORD is my shepherd;"),
+///         VerseText::new(BibleVerse::new(BibleBook::John, 10, 11), "I am the good shepherd:"),
+///     ],
+/// );
+/// let concordance = Concordance::new(&text);
+/// let shepherd = concordance.get("Shepherd").unwrap();
+/// assert_eq!(shepherd.testaments(), (1, 1));
+/// assert_eq!(concordance.frequencies()[0], ("shepherd", 2));
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Concordance {
+    entries: BTreeMap<String, Entry>,
+    /// Number of words in the text, including stop words
+    total: usize,
+}
+
+#[allow(dead_code)]
+impl Concordance {
+    /// Build a concordance of every word in a text
+    /// Use [ConcordanceBuilder] to leave out stop words or to lemmatise.
+    pub fn new(text: &BibleText) -> Self {
+        ConcordanceBuilder::new().build(text.iter())
+    }
+
+    /// Return the entry for a word. The word is folded before it is looked up.
+    pub fn get(&self, word: &str) -> Option<&Entry> {
+        self.entries.get(&fold(word))
+    }
+
+    /// Iterate over the entries in alphabetical order
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> + '_ {
+        self.entries.values()
+    }
+
+    /// Return the number of distinct words
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the number of words in the text, including stop words
+    pub fn total_words(&self) -> usize {
+        self.total
+    }
+
+    /// Return each word with how often it occurs, most frequent first
+    /// Words that occur equally often are in alphabetical order.
+    pub fn frequencies(&self) -> Vec<(&str, usize)> {
+        let mut frequencies: Vec<(&str, usize)> = self
+            .entries
+            .values()
+            .map(|e| (e.word.as_str(), e.count()))
+            .collect();
+        frequencies.sort_by_key(|f| std::cmp::Reverse(f.1));
+        frequencies
+    }
+
+    /// Return a keyword-in-context line for each occurrence of a word
+    ///
+    /// `text` must be the text the concordance was built from. Up to `width`
+    /// characters of context are given either side of the word.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// use bible_data::concordance::Concordance;
+    /// use bible_data::text::{BibleText, Metadata, VerseText};
+    /// let text = BibleText::from_verses(
+    ///     Metadata::default(),
+    ///     [VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.")],
+    /// );
+    /// let lines = Concordance::new(&text).kwic(&text, "wept", 8);
+    /// assert_eq!(lines[0].left, "  Jesus ");
+    /// assert_eq!(lines[0].to_string(), "Jn 11:35       Jesus  wept .");
+    /// ```
+    pub fn kwic(&self, text: &BibleText, word: &str, width: usize) -> Vec<KwicLine> {
+        let Some(entry) = self.get(word) else {
+            return Vec::new();
+        };
+        entry
+            .occurrences
+            .iter()
+            .filter_map(|occurrence| {
+                let verse = text.get(&occurrence.verse)?;
+                let keyword = verse.get(occurrence.range.clone())?;
+                let before: Vec<char> = verse[..occurrence.range.start].chars().collect();
+                let left: String = before[before.len().saturating_sub(width)..]
+                    .iter()
+                    .collect();
+                let right: String = verse[occurrence.range.end..].chars().take(width).collect();
+                Some(KwicLine {
+                    verse: occurrence.verse,
+                    left: format!("{:>width$}", left, width = width),
+                    keyword: keyword.to_string(),
+                    right,
+                })
+            })
+            .collect()
+    }
+
+    /// Write the concordance as CSV with a row for each word
+    /// The columns are the word, its count, the counts in each testament and the
+    /// OSIS identifiers of the verses it is in, separated by spaces.
+    pub fn write_csv(&self, mut out: impl Write) -> std::io::Result<()> {
+        writeln!(out, "word,count,old_testament,new_testament,verses")?;
+        for entry in self.entries.values() {
+            let (old, new) = entry.testaments();
+            let verses: Vec<String> = entry.verses().map(|v| v.to_osis()).collect();
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                csv_field(&entry.word),
+                entry.count(),
+                old,
+                new,
+                verses.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the concordance as a JSON array with an object for each word
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// use bible_data::concordance::ConcordanceBuilder;
+    /// let concordance = ConcordanceBuilder::new()
+    ///     .build([(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.")]);
+    /// assert!(concordance.to_json().starts_with(
+    ///     r#"[{"word":"jesus","count":1,"forms":{"jesus":1},"books":{"John":1},"verses":["John.11.35"]}"#
+    /// ));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, entry) in self.entries.values().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"word\":");
+            json::write_string(&mut out, &entry.word);
+            out.push_str(&format!(",\"count\":{},\"forms\":{{", entry.count()));
+            for (j, (form, count)) in entry.forms.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                json::write_string(&mut out, form);
+                out.push_str(&format!(":{}", count));
+            }
+            out.push_str("},\"books\":{");
+            for (j, (book, count)) in entry.books().iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                json::write_string(&mut out, book.to_osis());
+                out.push_str(&format!(":{}", count));
+            }
+            out.push_str("},\"verses\":[");
+            for (j, verse) in entry.verses().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                json::write_string(&mut out, &verse.to_osis());
+            }
+            out.push_str("]}");
+        }
+        out.push(']');
+        out
+    }
+}
+
+/// Quote a CSV field if it needs it
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<(BibleVerse, &'static str)> {
+        vec![
+            (
+                BibleVerse::new(BibleBook::Genesis, 1, 1),
+                "In the beginning God created the heaven and the earth.",
+            ),
+            (
+                BibleVerse::new(BibleBook::John, 1, 1),
+                "In the beginning was the Word, and the Word was with God, and the Word was God.",
+            ),
+            (
+                BibleVerse::new(BibleBook::John, 3, 16),
+                "For God so loved the world,",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_entries() {
+        let concordance = ConcordanceBuilder::new().build(sample());
+        assert_eq!(concordance.total_words(), 33);
+        let god = concordance.get("GOD").unwrap();
+        assert_eq!(god.count(), 4);
+        assert_eq!(god.verses().count(), 3);
+        assert_eq!(god.testaments(), (1, 3));
+        assert_eq!(
+            god.books(),
+            BTreeMap::from([(BibleBook::Genesis, 1), (BibleBook::John, 3)])
+        );
+        assert_eq!(god.occurrences[0].range, 17..20);
+        assert_eq!(concordance.frequencies()[0], ("the", 8));
+        assert_eq!(concordance.frequencies()[2], ("and", 3));
+    }
+
+    #[test]
+    fn test_stop_words_and_lemmas() {
+        let concordance = ConcordanceBuilder::new()
+            .stop_words(ENGLISH_STOP_WORDS.iter().copied())
+            .lemmatizer(|form: &str| match form {
+                "loved" => Some("love".to_string()),
+                "was" => Some("be".to_string()),
+                _ => None,
+            })
+            .build(sample());
+        assert!(concordance.get("the").is_none());
+        assert!(concordance.get("was").is_none());
+        assert!(concordance.get("loved").is_none());
+        let love = concordance.get("love").unwrap();
+        assert_eq!(love.forms, BTreeMap::from([("loved".to_string(), 1)]));
+        assert_eq!(concordance.frequencies()[0], ("god", 4));
+    }
+
+    #[test]
+    fn test_kwic() {
+        let text = BibleText::from_verses(
+            Default::default(),
+            sample()
+                .into_iter()
+                .map(|(v, t)| crate::text::VerseText::new(v, t)),
+        );
+        let concordance = Concordance::new(&text);
+        let lines = concordance.kwic(&text, "word", 10);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0].left,
+            "ning was the ".chars().skip(3).collect::<String>()
+        );
+        assert_eq!(lines[0].keyword, "Word");
+        assert_eq!(lines[0].right, ", and the ");
+        assert_eq!(lines[2].right, " was God.");
+        assert!(concordance.kwic(&text, "light", 10).is_empty());
+    }
+
+    #[test]
+    fn test_export() {
+        let concordance = ConcordanceBuilder::new().build(sample());
+        let mut csv = Vec::new();
+        concordance.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("word,count,old_testament,new_testament,verses")
+        );
+        assert!(csv.contains("\ngod,4,1,3,Gen.1.1 John.1.1 John.3.16\n"));
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        let json = concordance.to_json();
+        assert!(json.starts_with("[{\"word\":\"and\",\"count\":3,"));
+        assert!(json.contains(r#"{"word":"world","count":1,"forms":{"world":1},"books":{"John":1},"verses":["John.3.16"]}"#));
+        assert!(json.ends_with("]"));
+    }
+}
@@ -0,0 +1,420 @@
+//! Mod for word-level comparison of two texts of the Bible
+//!
+//! Words are compared exactly as written, split at whitespace, so changes to
+//! punctuation and capitalisation are reported as changes to the word.
+use crate::structs::chapterandverse::ChapterAndVerse;
+use crate::text::BibleText;
+use crate::text::{index_range, verse_at};
+use crate::{BibleBook, BibleVerse, BibleVerseRange, html, json};
+
+/// A step in turning the old text into the new one
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// Words in both texts
+    Equal(String),
+    /// Words only in the new text
+    Insert(String),
+    /// Words only in the old text
+    Delete(String),
+    /// Words of the old text replaced by words of the new text
+    Replace { old: String, new: String },
+}
+
+#[allow(dead_code)]
+impl Edit {
+    pub fn is_equal(&self) -> bool {
+        matches!(self, Edit::Equal(_))
+    }
+}
+
+/// Compare two strings word by word
+///
+/// # Example
+/// ```rust
+/// use bible_data::diff::{Edit, diff_words};
+/// let edits = diff_words("And God said, Let there be light", "Then God said, Let there be light!");
+/// assert_eq!(edits, vec![
+///     Edit::Replace { old: "And".to_string(), new: "Then".to_string() },
+///     Edit::Equal("God said, Let there be".to_string()),
+///     Edit::Replace { old: "light".to_string(), new: "light!".to_string() },
+/// ]);
+/// ```
+pub fn diff_words(old: &str, new: &str) -> Vec<Edit> {
+    let old: Vec<&str> = old.split_whitespace().collect();
+    let new: Vec<&str> = new.split_whitespace().collect();
+    // Longest common subsequence, filled from the end so it can be walked forwards
+    let mut lengths = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = match old[i] == new[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+    let mut edits = Vec::new();
+    let (mut deleted, mut inserted, mut equal) = (Vec::new(), Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            flush_changes(&mut edits, &mut deleted, &mut inserted);
+            equal.push(old[i]);
+            i += 1;
+            j += 1;
+            continue;
+        }
+        if !equal.is_empty() {
+            edits.push(Edit::Equal(equal.join(" ")));
+            equal.clear();
+        }
+        if j < new.len() && (i == old.len() || lengths[i][j + 1] >= lengths[i + 1][j]) {
+            inserted.push(new[j]);
+            j += 1;
+        } else {
+            deleted.push(old[i]);
+            i += 1;
+        }
+    }
+    if !equal.is_empty() {
+        edits.push(Edit::Equal(equal.join(" ")));
+    }
+    flush_changes(&mut edits, &mut deleted, &mut inserted);
+    edits
+}
+
+fn flush_changes(edits: &mut Vec<Edit>, deleted: &mut Vec<&str>, inserted: &mut Vec<&str>) {
+    let edit = match (deleted.is_empty(), inserted.is_empty()) {
+        (true, true) => return,
+        (false, true) => Edit::Delete(deleted.join(" ")),
+        (true, false) => Edit::Insert(inserted.join(" ")),
+        (false, false) => Edit::Replace {
+            old: deleted.join(" "),
+            new: inserted.join(" "),
+        },
+    };
+    edits.push(edit);
+    deleted.clear();
+    inserted.clear();
+}
+
+/// The differences between two texts of a verse
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerseDiff {
+    pub verse: BibleVerse,
+    /// Whether each text includes the verse
+    pub in_old: bool,
+    pub in_new: bool,
+    pub edits: Vec<Edit>,
+}
+
+#[allow(dead_code)]
+impl VerseDiff {
+    /// Compare the text of a verse. None for a text that does not include it.
+    pub fn new(verse: BibleVerse, old: Option<&str>, new: Option<&str>) -> Self {
+        VerseDiff {
+            verse,
+            in_old: old.is_some(),
+            in_new: new.is_some(),
+            edits: diff_words(old.unwrap_or_default(), new.unwrap_or_default()),
+        }
+    }
+
+    /// Return if the verse differs between the texts
+    pub fn is_changed(&self) -> bool {
+        self.in_old != self.in_new || !self.edits.iter().all(Edit::is_equal)
+    }
+
+    /// Render the changes as plain text, marking deletions `[-like this-]` and
+    /// insertions `{+like this+}`
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// use bible_data::diff::VerseDiff;
+    /// let verse = BibleVerse::new(BibleBook::John, 11, 35);
+    /// let diff = VerseDiff::new(verse, Some("Jesus wept."), Some("Jesus shed tears."));
+    /// assert_eq!(diff.to_text(), "Jn 11:35 Jesus [-wept.-]{+shed tears.+}");
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut out = self.verse.to_string();
+        for edit in self.edits.iter() {
+            out.push(' ');
+            match edit {
+                Edit::Equal(words) => out.push_str(words),
+                Edit::Insert(words) => out.push_str(&format!("{{+{}+}}", words)),
+                Edit::Delete(words) => out.push_str(&format!("[-{}-]", words)),
+                Edit::Replace { old, new } => out.push_str(&format!("[-{}-]{{+{}+}}", old, new)),
+            }
+        }
+        out
+    }
+
+    /// Render the changes as HTML using `<del>` and `<ins>`
+    pub fn to_html(&self) -> String {
+        let mut out = String::from("<p class=\"verse-diff\"><span class=\"ref\">");
+        html::write_escaped(&mut out, &self.verse.to_string());
+        out.push_str("</span>");
+        for edit in self.edits.iter() {
+            out.push(' ');
+            let (old, new) = match edit {
+                Edit::Equal(words) => {
+                    html::write_escaped(&mut out, words);
+                    continue;
+                }
+                Edit::Insert(words) => (None, Some(words)),
+                Edit::Delete(words) => (Some(words), None),
+                Edit::Replace { old, new } => (Some(old), Some(new)),
+            };
+            if let Some(old) = old {
+                out.push_str("<del>");
+                html::write_escaped(&mut out, old);
+                out.push_str("</del>");
+            }
+            if let Some(new) = new {
+                out.push_str("<ins>");
+                html::write_escaped(&mut out, new);
+                out.push_str("</ins>");
+            }
+        }
+        out.push_str("</p>");
+        out
+    }
+
+    /// Render the changes as a JSON object
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// use bible_data::diff::VerseDiff;
+    /// let verse = BibleVerse::new(BibleBook::John, 11, 35);
+    /// let diff = VerseDiff::new(verse, Some("Jesus wept."), Some("Jesus wept!"));
+    /// assert_eq!(
+    ///     diff.to_json(),
+    ///     r#"{"verse":"John.11.35","in_old":true,"in_new":true,"edits":[{"op":"equal","text":"Jesus"},{"op":"replace","old":"wept.","new":"wept!"}]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"verse\":");
+        json::write_string(&mut out, &self.verse.to_osis());
+        out.push_str(&format!(
+            ",\"in_old\":{},\"in_new\":{},\"edits\":[",
+            self.in_old, self.in_new
+        ));
+        for (i, edit) in self.edits.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let (op, fields) = match edit {
+                Edit::Equal(words) => ("equal", vec![("text", words)]),
+                Edit::Insert(words) => ("insert", vec![("text", words)]),
+                Edit::Delete(words) => ("delete", vec![("text", words)]),
+                Edit::Replace { old, new } => ("replace", vec![("old", old), ("new", new)]),
+            };
+            out.push_str(&format!("{{\"op\":\"{}\"", op));
+            for (name, value) in fields {
+                out.push_str(&format!(",\"{}\":", name));
+                json::write_string(&mut out, value);
+            }
+            out.push('}');
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+fn diff_range(
+    old: &BibleText,
+    new: &BibleText,
+    book: BibleBook,
+    start: ChapterAndVerse,
+    end: ChapterAndVerse,
+) -> Vec<VerseDiff> {
+    index_range(book, start, end)
+        .filter_map(verse_at)
+        .filter_map(|verse| match (old.get(&verse), new.get(&verse)) {
+            (None, None) => None,
+            (old, new) => Some(VerseDiff::new(verse, old, new)),
+        })
+        .collect()
+}
+
+/// Compare a passage in two texts, returning a diff for every verse that either includes
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+/// use bible_data::diff::diff_passage;
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let verse = BibleVerse::new(BibleBook::John, 11, 35);
+/// let old = BibleText::from_verses(Metadata::default(), [VerseText::new(verse, "Jesus wept.")]);
+/// let new = BibleText::from_verses(Metadata::default(), [VerseText::new(verse, "Jesus cried.")]);
+/// let diffs = diff_passage(&old, &new, &BibleVerseRange::parse("Jn 11:1-57").unwrap());
+/// assert_eq!(diffs.len(), 1);
+/// assert!(diffs[0].is_changed());
+/// ```
+pub fn diff_passage(old: &BibleText, new: &BibleText, range: &BibleVerseRange) -> Vec<VerseDiff> {
+    diff_range(
+        old,
+        new,
+        range.book,
+        *range.range.start(),
+        *range.range.end(),
+    )
+}
+
+/// Which verses of a book differ between two texts
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookSummary {
+    pub book: BibleBook,
+    /// Verses in both texts with different words
+    pub changed: Vec<BibleVerse>,
+    /// Verses only in the new text
+    pub added: Vec<BibleVerse>,
+    /// Verses only in the old text
+    pub removed: Vec<BibleVerse>,
+    /// Number of verses the same in both texts
+    pub unchanged: usize,
+}
+
+#[allow(dead_code)]
+impl BookSummary {
+    pub fn is_unchanged(&self) -> bool {
+        self.changed.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Summarise which verses of a book differ between two texts
+pub fn diff_book(old: &BibleText, new: &BibleText, book: BibleBook) -> BookSummary {
+    let mut summary = BookSummary {
+        book,
+        changed: Vec::new(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        unchanged: 0,
+    };
+    let all = diff_range(
+        old,
+        new,
+        book,
+        ChapterAndVerse::new(1, 1),
+        ChapterAndVerse::new(u8::MAX, u8::MAX),
+    );
+    for diff in all {
+        match (diff.in_old, diff.in_new) {
+            (false, _) => summary.added.push(diff.verse),
+            (_, false) => summary.removed.push(diff.verse),
+            _ if diff.is_changed() => summary.changed.push(diff.verse),
+            _ => summary.unchanged += 1,
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::{Metadata, VerseText};
+
+    #[test]
+    fn test_diff_words() {
+        assert_eq!(
+            diff_words("a b c", "a b c"),
+            vec![Edit::Equal("a b c".to_string())]
+        );
+        assert_eq!(
+            diff_words("a b c", "a c"),
+            vec![
+                Edit::Equal("a".to_string()),
+                Edit::Delete("b".to_string()),
+                Edit::Equal("c".to_string())
+            ]
+        );
+        assert_eq!(
+            diff_words("a c", "a b c d"),
+            vec![
+                Edit::Equal("a".to_string()),
+                Edit::Insert("b".to_string()),
+                Edit::Equal("c".to_string()),
+                Edit::Insert("d".to_string())
+            ]
+        );
+        assert_eq!(diff_words("", ""), vec![]);
+        assert_eq!(diff_words("", "new"), vec![Edit::Insert("new".to_string())]);
+        assert_eq!(
+            diff_words("thee thou", "you"),
+            vec![Edit::Replace {
+                old: "thee thou".to_string(),
+                new: "you".to_string()
+            }]
+        );
+    }
+
+    fn texts() -> (BibleText, BibleText) {
+        let verse = |v| BibleVerse::new(BibleBook::Jonah, 1, v);
+        let old = BibleText::from_verses(
+            Metadata::default(),
+            [
+                VerseText::new(verse(1), "Now the word of the LORD came unto Jonah"),
+                VerseText::new(verse(2), "Arise, go to Nineveh, that great city,"),
+                VerseText::new(verse(3), "But Jonah rose up to flee unto Tarshish"),
+            ],
+        );
+        let new = BibleText::from_verses(
+            Metadata::default(),
+            [
+                VerseText::new(verse(1), "Now the word of the LORD came to Jonah"),
+                VerseText::new(verse(2), "Arise, go to Nineveh, that great city,"),
+                VerseText::new(verse(4), "But the LORD sent out a great wind"),
+            ],
+        );
+        (old, new)
+    }
+
+    #[test]
+    fn test_diff_book() {
+        let (old, new) = texts();
+        let summary = diff_book(&old, &new, BibleBook::Jonah);
+        assert_eq!(
+            summary.changed,
+            vec![BibleVerse::new(BibleBook::Jonah, 1, 1)]
+        );
+        assert_eq!(
+            summary.removed,
+            vec![BibleVerse::new(BibleBook::Jonah, 1, 3)]
+        );
+        assert_eq!(summary.added, vec![BibleVerse::new(BibleBook::Jonah, 1, 4)]);
+        assert_eq!(summary.unchanged, 1);
+        assert!(!summary.is_unchanged());
+        assert!(diff_book(&old, &old, BibleBook::Jonah).is_unchanged());
+    }
+
+    #[test]
+    fn test_render() {
+        let (old, new) = texts();
+        let diffs = diff_passage(
+            &old,
+            &new,
+            &BibleVerseRange::new(BibleBook::Jonah, 1, 1, 1, 4),
+        );
+        assert_eq!(diffs.len(), 4);
+        assert_eq!(
+            diffs[0].to_text(),
+            "Jnh 1:1 Now the word of the LORD came [-unto-]{+to+} Jonah"
+        );
+        assert_eq!(
+            diffs[0].to_html(),
+            "<p class=\"verse-diff\"><span class=\"ref\">Jnh 1:1</span> Now the word of the LORD came <del>unto</del><ins>to</ins> Jonah</p>"
+        );
+        assert!(!diffs[1].is_changed());
+        assert!(diffs[2].is_changed());
+        assert!(diffs[3].to_json().contains("\"in_old\":false"));
+        assert!(
+            diffs[3]
+                .to_json()
+                .contains("{\"op\":\"insert\",\"text\":\"But the LORD sent out a great wind\"}")
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Minimal HTML output helpers
+
+/// Append text to `out` with the characters that are special in HTML escaped
+pub(crate) fn write_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_escaped() {
+        let mut out = String::new();
+        write_escaped(&mut out, "<b>\"Tom\" & 'Jerry'</b>");
+        assert_eq!(
+            out,
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+    }
+}
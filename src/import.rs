@@ -0,0 +1,145 @@
+//! Mod for reading Bible texts from the file formats they are commonly distributed in
+//!
+//! Each reader yields a [VerseText](crate::text::versetext::VerseText) for every verse
+//! it finds, keyed by [BibleVerse](crate::BibleVerse), so the results can be collected
+//! into whatever structure suits the application.
+use crate::BibleVerse;
+use crate::text::versetext::VerseText;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::BufRead;
+
+mod builder;
+pub mod errors;
+pub mod osis;
+pub mod usfm;
+pub mod usx;
+mod xml;
+pub mod zefania;
+
+pub use errors::ImportError;
+
+/// The file formats that Bible texts can be read from
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    Usfm,
+    Osis,
+    Zefania,
+    Usx,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Format::Usfm => "USFM",
+            Format::Osis => "OSIS",
+            Format::Zefania => "Zefania",
+            Format::Usx => "USX",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[allow(dead_code)]
+impl Format {
+    /// Guess the format from a file extension
+    /// Returns None for `xml` as that could be OSIS or Zefania. Use [detect](Self::detect).
+    ///
+    /// ```rust
+    /// use bible_data::import::Format;
+    /// assert_eq!(Format::from_extension("SFM"), Some(Format::Usfm));
+    /// assert_eq!(Format::from_extension("xml"), None);
+    /// ```
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "usfm" | "sfm" => Some(Format::Usfm),
+            "usx" => Some(Format::Usx),
+            "osis" => Some(Format::Osis),
+            _ => None,
+        }
+    }
+
+    /// Detect the format from the start of a file, by looking for the root element
+    /// of the XML formats or the `\id` marker of USFM
+    ///
+    /// ```rust
+    /// use bible_data::import::Format;
+    /// assert_eq!(Format::detect(b"<?xml version=\"1.0\"?>\n<XMLBIBLE>"), Some(Format::Zefania));
+    /// assert_eq!(Format::detect(b"\\id GEN"), Some(Format::Usfm));
+    /// ```
+    pub fn detect(start: &[u8]) -> Option<Self> {
+        let start = String::from_utf8_lossy(start);
+        let start = start.trim_start_matches('\u{feff}').trim_start();
+        if start.starts_with("\\id") {
+            return Some(Format::Usfm);
+        }
+        let mut rest = start;
+        // Find the first element that is not a declaration, comment or DOCTYPE
+        while let Some(pos) = rest.find('<') {
+            rest = &rest[pos + 1..];
+            if rest.starts_with(['?', '!']) {
+                continue;
+            }
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .unwrap_or(rest.len());
+            let name = &rest[..end];
+            let name = name.rsplit(':').next().unwrap_or(name);
+            return match name.to_ascii_lowercase().as_str() {
+                "osis" => Some(Format::Osis),
+                "xmlbible" => Some(Format::Zefania),
+                "usx" => Some(Format::Usx),
+                _ => None,
+            };
+        }
+        None
+    }
+}
+
+/// Read the verses from a text in any of the supported formats
+/// All the readers yield the same [VerseText]s so they can be handled in the same way.
+///
+/// # Example
+/// ```rust
+/// use bible_data::import::{read_verses, Format};
+/// let usx = r#"<usx><book code="JHN"/><chapter number="11"/><verse number="35"/>Jesus wept.</usx>"#;
+/// let usfm = "\\id JHN\n\\c 11\n\\v 35 Jesus wept.";
+/// let from_usx: Vec<_> = read_verses(Format::Usx, usx.as_bytes(), true).collect();
+/// let from_usfm: Vec<_> = read_verses(Format::Usfm, usfm.as_bytes(), true).collect();
+/// assert_eq!(from_usx[0].as_ref().unwrap(), from_usfm[0].as_ref().unwrap());
+/// ```
+pub fn read_verses<'a, R: BufRead + 'a>(
+    format: Format,
+    reader: R,
+    keep_markup: bool,
+) -> Box<dyn Iterator<Item = Result<VerseText, ImportError>> + 'a> {
+    match format {
+        Format::Usfm => Box::new(usfm::UsfmReader::new(reader).keep_markup(keep_markup)),
+        Format::Osis => Box::new(osis::OsisReader::new(reader).keep_markup(keep_markup)),
+        Format::Zefania => Box::new(zefania::ZefaniaReader::new(reader).keep_markup(keep_markup)),
+        Format::Usx => Box::new(usx::UsxReader::new(reader).keep_markup(keep_markup)),
+    }
+}
+
+/// Collect the verses yielded by a reader into a map keyed by [BibleVerse]
+/// Stops at the first error. If a verse occurs more than once the last one wins.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::import::{collect_verses, usfm::UsfmReader};
+/// let usfm = "\\id JHN\n\\c 11\n\\p\n\\v 35 Jesus wept.";
+/// let verses = collect_verses(UsfmReader::new(usfm.as_bytes())).unwrap();
+/// assert_eq!(verses[&BibleVerse::new(BibleBook::John, 11, 35)].text, "Jesus wept.");
+/// ```
+pub fn collect_verses(
+    reader: impl Iterator<Item = Result<VerseText, ImportError>>,
+) -> Result<BTreeMap<BibleVerse, VerseText>, ImportError> {
+    let mut verses = BTreeMap::new();
+    for verse in reader {
+        let verse = verse?;
+        verses.insert(verse.verse, verse);
+    }
+    Ok(verses)
+}
@@ -0,0 +1,201 @@
+use crate::text::versetext::{
+    Break, BreakKind, Markup, Note, NoteKind, Span, SpanStyle, VerseText, WordTag,
+};
+use crate::{BibleVerse, StrongsNumber};
+
+/// Accumulates the text and markup of a verse as a reader walks through it
+///
+/// Whitespace is collapsed as text is added so the readers can pass through
+/// whatever whitespace the source contains.
+pub(crate) struct VerseBuilder {
+    verse: BibleVerse,
+    text: String,
+    markup: Markup,
+    open_spans: Vec<(String, SpanStyle, usize)>,
+    /// Start of each tagged word that has not been closed
+    open_words: Vec<usize>,
+}
+
+impl VerseBuilder {
+    pub(crate) fn new(verse: BibleVerse) -> Self {
+        VerseBuilder {
+            verse,
+            text: String::new(),
+            markup: Markup::default(),
+            open_spans: Vec::new(),
+            open_words: Vec::new(),
+        }
+    }
+
+    /// Length of the text ignoring any trailing space
+    /// Notes and the ends of spans attach to the last word, not the space after it.
+    fn content_len(&self) -> usize {
+        self.text.trim_end().len()
+    }
+
+    pub(crate) fn push_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.text.is_empty() && !self.text.ends_with(' ') {
+                    self.text.push(' ');
+                }
+            } else {
+                self.text.push(c);
+            }
+        }
+    }
+
+    /// Start a span. `name` is used to match it with [close_span](Self::close_span)
+    pub(crate) fn open_span(&mut self, name: &str, style: SpanStyle) {
+        self.open_spans
+            .push((name.to_string(), style, self.text.len()));
+    }
+
+    pub(crate) fn has_open_span(&self) -> bool {
+        !self.open_spans.is_empty()
+    }
+
+    /// Close the most recently opened span with the given name
+    pub(crate) fn close_span(&mut self, name: &str) {
+        if let Some(pos) = self.open_spans.iter().rposition(|(n, _, _)| n == name) {
+            let (_, style, start) = self.open_spans.remove(pos);
+            let end = self.content_len();
+            if end > start {
+                self.markup.spans.push(Span {
+                    style,
+                    range: start..end,
+                });
+            }
+        }
+    }
+
+    /// Start a word whose tags will be given when it is closed
+    pub(crate) fn open_word(&mut self) {
+        self.open_words.push(self.text.len());
+    }
+
+    /// Close the most recently opened word, tagging it if there are any tags
+    pub(crate) fn close_word(&mut self, strongs: Vec<StrongsNumber>, morph: Option<String>) {
+        if let Some(mut start) = self.open_words.pop() {
+            if self.text[start..].starts_with(' ') {
+                start += 1;
+            }
+            let end = self.content_len();
+            if end > start && (!strongs.is_empty() || morph.is_some()) {
+                self.markup.words.push(WordTag {
+                    range: start..end,
+                    strongs,
+                    morph,
+                });
+            }
+        }
+    }
+
+    pub(crate) fn add_note(&mut self, kind: NoteKind, caller: String, text: &str) {
+        let mut note = String::new();
+        for word in text.split_whitespace() {
+            if !note.is_empty() {
+                note.push(' ');
+            }
+            note.push_str(word);
+        }
+        self.markup.notes.push(Note {
+            kind,
+            offset: self.content_len(),
+            caller,
+            text: note,
+        });
+    }
+
+    pub(crate) fn add_break(&mut self, kind: BreakKind) {
+        let offset = self.content_len();
+        // A break at the very start of the verse replaces any other
+        // so the verse does not start with an empty paragraph.
+        if let Some(last) = self.markup.breaks.last_mut()
+            && last.offset == offset
+        {
+            last.kind = kind;
+            return;
+        }
+        self.markup.breaks.push(Break { kind, offset });
+    }
+
+    pub(crate) fn finish(mut self, keep_markup: bool) -> VerseText {
+        while let Some((name, _, _)) = self.open_spans.last() {
+            let name = name.clone();
+            self.close_span(&name);
+        }
+        let len = self.content_len();
+        self.text.truncate(len);
+        self.markup.spans.sort_by_key(|span| span.range.start);
+        self.markup.words.sort_by_key(|word| word.range.start);
+        let markup = match keep_markup && !self.markup.is_empty() {
+            true => Some(self.markup),
+            false => None,
+        };
+        VerseText {
+            verse: self.verse,
+            text: self.text,
+            markup,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BibleBook;
+
+    #[test]
+    fn test_builder() {
+        let mut builder = VerseBuilder::new(BibleVerse::new(BibleBook::John, 3, 16));
+        builder.add_break(BreakKind::Paragraph);
+        builder.push_text("  For God\n so ");
+        builder.open_span("wj", SpanStyle::WordsOfJesus);
+        builder.push_text("loved ");
+        builder.add_note(NoteKind::Footnote, "+".to_string(), " Or\ncherished ");
+        builder.close_span("wj");
+        builder.push_text(" the world ");
+        let verse = builder.finish(true);
+        assert_eq!(verse.text, "For God so loved the world");
+        let markup = verse.markup.unwrap();
+        assert_eq!(
+            markup.breaks,
+            vec![Break {
+                kind: BreakKind::Paragraph,
+                offset: 0
+            }]
+        );
+        assert_eq!(markup.spans[0].range, 11..16);
+        assert_eq!(markup.notes[0].offset, 16);
+        assert_eq!(markup.notes[0].text, "Or cherished");
+    }
+
+    #[test]
+    fn test_no_markup() {
+        let mut builder = VerseBuilder::new(BibleVerse::new(BibleBook::John, 11, 35));
+        builder.push_text("Jesus wept.");
+        assert_eq!(builder.finish(true).markup, None);
+        let mut builder = VerseBuilder::new(BibleVerse::new(BibleBook::John, 11, 35));
+        builder.open_span("wj", SpanStyle::WordsOfJesus);
+        builder.push_text("Jesus wept.");
+        assert_eq!(builder.finish(false).markup, None);
+    }
+
+    #[test]
+    fn test_words() {
+        let mut builder = VerseBuilder::new(BibleVerse::new(BibleBook::Genesis, 1, 1));
+        builder.push_text("In");
+        builder.open_word();
+        builder.push_text(" the beginning ");
+        builder.close_word(vec![StrongsNumber::hebrew(7225)], None);
+        builder.open_word();
+        builder.push_text("God");
+        builder.close_word(Vec::new(), None);
+        let verse = builder.finish(true);
+        let markup = verse.markup.unwrap();
+        assert_eq!(markup.words.len(), 1);
+        assert_eq!(&verse.text[markup.words[0].range.clone()], "the beginning");
+        assert!(markup.has_strongs(&StrongsNumber::hebrew(7225)));
+    }
+}
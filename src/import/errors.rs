@@ -0,0 +1,117 @@
+use crate::{BibleBook, BibleChapter, BibleVerse};
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while importing a Bible text
+///
+/// Apart from [Io](ImportError::Io), these do not stop a reader: it reports the
+/// problem, skips the affected text and carries on with the rest of the file.
+/// `line` is the 1-based line in the source where the problem was found.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum ImportError {
+    Io(std::io::Error),
+    /// The book code or identifier is not one this crate knows
+    UnknownBook {
+        line: usize,
+        code: String,
+    },
+    /// Chapter or verse text was found before the book was identified
+    MissingBook {
+        line: usize,
+    },
+    /// A verse was found before any chapter
+    MissingChapter {
+        line: usize,
+    },
+    /// The chapter is not in the book
+    ChapterOutOfRange {
+        line: usize,
+        book: BibleBook,
+        chapter: u32,
+    },
+    /// The verse is not in the chapter
+    VerseOutOfRange {
+        line: usize,
+        verse: BibleVerse,
+    },
+    /// A chapter or verse number could not be read
+    InvalidNumber {
+        line: usize,
+        text: String,
+    },
+    /// A reference to a verse could not be read
+    InvalidReference {
+        line: usize,
+        text: String,
+    },
+    /// The XML is not well formed
+    Xml {
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Io(e) => write!(f, "\"ImportError\": {}", e),
+            ImportError::UnknownBook { line, code } => {
+                write!(f, "\"UnknownBook\": line {}: {}", line, code)
+            }
+            ImportError::MissingBook { line } => {
+                write!(f, "\"MissingBook\": line {}: text before book id", line)
+            }
+            ImportError::MissingChapter { line } => {
+                write!(f, "\"MissingChapter\": line {}: verse before chapter", line)
+            }
+            ImportError::ChapterOutOfRange {
+                line,
+                book,
+                chapter,
+            } => write!(
+                f,
+                "\"ChapterOutOfRange\": line {}: {} has {} chapters. {} is out of range.",
+                line,
+                book.name(),
+                book.number_of_chapters(),
+                chapter
+            ),
+            ImportError::VerseOutOfRange { line, verse } => write!(
+                f,
+                "\"VerseOutOfRange\": line {}: {} {} has {} verses. {} is out of range.",
+                line,
+                verse.book.name(),
+                verse.chapter,
+                BibleChapter::new(verse.book, verse.chapter)
+                    .map(|chapter| chapter.number_of_verses())
+                    .unwrap_or_default(),
+                verse.verse
+            ),
+            ImportError::InvalidNumber { line, text } => {
+                write!(f, "\"InvalidNumber\": line {}: {}", line, text)
+            }
+            ImportError::InvalidReference { line, text } => {
+                write!(f, "\"InvalidReference\": line {}: {}", line, text)
+            }
+            ImportError::Xml { line, message } => {
+                write!(f, "\"Xml\": line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(value: std::io::Error) -> Self {
+        ImportError::Io(value)
+    }
+}
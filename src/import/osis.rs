@@ -0,0 +1,576 @@
+use super::builder::VerseBuilder;
+use super::errors::ImportError;
+use super::xml::{Element, XmlEvent, XmlReader};
+use crate::text::versetext::{BreakKind, NoteKind, SpanStyle, VerseText};
+use crate::{BibleBook, BibleVerse, OsisId, StrongsNumber};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// Streaming reader for OSIS XML files
+///
+/// Yields a [VerseText] for each `<verse>` in the file, in the order they appear.
+/// Both container verses (`<verse osisID="Gen.1.1">...</verse>`) and milestone
+/// verses (`<verse sID="Gen.1.1" osisID="Gen.1.1"/>...<verse eID="Gen.1.1"/>`)
+/// are supported.
+///
+/// * `<p>`, paragraph milestones and `<l>` become [Break](crate::text::versetext::Break)s.
+/// * `<note>`s become [Note](crate::text::versetext::Note)s and are removed from the text.
+/// * `<q who="Jesus">` becomes a [SpanStyle::WordsOfJesus] span, and `<transChange>`,
+///   `<divineName>` and `<hi>` become character spans named as their USFM equivalents.
+/// * `<w lemma="strong:H7225">` becomes a [WordTag](crate::text::versetext::WordTag)
+///   with its Strong's numbers and any `morph` attribute.
+/// * Titles are skipped.
+/// * A verse with several ids (`osisID="Gen.1.1 Gen.1.2"`) is keyed by the first.
+///
+/// Verses are checked against the versification of this crate. A verse that does not
+/// exist is reported as an error and skipped. A book that is not known, such as one
+/// of the deuterocanonical books, is reported once and all of its verses skipped.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::import::osis::OsisReader;
+/// let osis = r#"<osis><osisText><div type="book" osisID="John">
+///   <chapter osisID="John.11"><verse osisID="John.11.35">Jesus wept.</verse></chapter>
+/// </div></osisText></osis>"#;
+/// let mut reader = OsisReader::new(osis.as_bytes());
+/// let verse = reader.next().unwrap().unwrap();
+/// assert_eq!(verse.verse, BibleVerse::new(BibleBook::John, 11, 35));
+/// assert_eq!(verse.text, "Jesus wept.");
+/// ```
+pub struct OsisReader<R: BufRead> {
+    xml: XmlReader<R>,
+    finished: bool,
+    keep_markup: bool,
+    ready: VecDeque<Result<VerseText, ImportError>>,
+    current: Option<VerseBuilder>,
+    pending_breaks: Vec<BreakKind>,
+    frames: Vec<Frame>,
+    skip_depth: usize,
+    note: Option<(NoteKind, String, String)>,
+    /// sIDs of milestone quotes of the words of Jesus that are still open
+    jesus_quotes: Vec<String>,
+    unknown_book: Option<String>,
+}
+
+/// What to do when an element ends
+enum Frame {
+    Verse,
+    /// A book or chapter. Any verse still open ends with it.
+    Division,
+    Span(&'static str),
+    /// A `<w>` with its Strong's numbers and morphology
+    Word(Vec<StrongsNumber>, Option<String>),
+    Skip,
+    Note,
+    Other,
+}
+
+/// The USFM name of the character style for an element, if it has one
+fn span_name(element: &Element) -> Option<&'static str> {
+    match element.name.as_str() {
+        "q" if element.attr("who") == Some("Jesus") => Some("wj"),
+        "transChange" => Some("add"),
+        "divineName" => Some("nd"),
+        "hi" => match element.attr("type")? {
+            "italic" => Some("it"),
+            "bold" => Some("bd"),
+            "small-caps" => Some("sc"),
+            "super" => Some("sup"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn span_style(name: &str) -> SpanStyle {
+    match name {
+        "wj" => SpanStyle::WordsOfJesus,
+        _ => SpanStyle::Character(name.to_string()),
+    }
+}
+
+fn is_paragraph(element: &Element) -> bool {
+    match element.name.as_str() {
+        "p" => true,
+        "div" => element.attr("type") == Some("paragraph"),
+        "milestone" => matches!(
+            element.attr("type"),
+            Some("x-p" | "pilcrow" | "x-paragraph")
+        ),
+        _ => false,
+    }
+}
+
+#[allow(dead_code)]
+impl<R: BufRead> OsisReader<R> {
+    pub fn new(reader: R) -> Self {
+        OsisReader {
+            xml: XmlReader::new(reader),
+            finished: false,
+            keep_markup: true,
+            ready: VecDeque::new(),
+            current: None,
+            pending_breaks: Vec::new(),
+            frames: Vec::new(),
+            skip_depth: 0,
+            note: None,
+            jesus_quotes: Vec::new(),
+            unknown_book: None,
+        }
+    }
+
+    /// Set whether to keep markup. If not, every verse is yielded with `markup` set to None.
+    /// Markup is kept by default.
+    pub fn keep_markup(mut self, keep: bool) -> Self {
+        self.keep_markup = keep;
+        self
+    }
+
+    fn error(&mut self, error: ImportError) {
+        self.ready.push_back(Err(error));
+    }
+
+    fn flush(&mut self) {
+        if let Some(builder) = self.current.take() {
+            self.ready.push_back(Ok(builder.finish(self.keep_markup)));
+        }
+    }
+
+    fn apply_breaks(&mut self) {
+        if let Some(builder) = self.current.as_mut() {
+            for kind in self.pending_breaks.drain(..) {
+                builder.add_break(kind);
+            }
+        }
+    }
+
+    fn process(&mut self, event: XmlEvent) {
+        match event {
+            XmlEvent::Text(text) => self.text(&text),
+            XmlEvent::Start(element) => {
+                let frame = self.start(&element);
+                self.frames.push(frame);
+            }
+            XmlEvent::Empty(element) => self.empty(&element),
+            XmlEvent::End(_) => self.end(),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        if let Some((_, _, note)) = self.note.as_mut() {
+            note.push_str(text);
+            return;
+        }
+        if self.current.is_none() {
+            return;
+        }
+        if !text.trim().is_empty() {
+            self.apply_breaks();
+        }
+        if let Some(builder) = self.current.as_mut() {
+            builder.push_text(text);
+        }
+    }
+
+    fn start(&mut self, element: &Element) -> Frame {
+        if self.skip_depth > 0 {
+            self.skip_depth += 1;
+            return Frame::Skip;
+        }
+        match element.name.as_str() {
+            "verse" => {
+                if let Some(id) = element.attr("osisID").or(element.attr("sID")) {
+                    self.begin_verse(id);
+                }
+                return Frame::Verse;
+            }
+            "div" if element.attr("type") == Some("book") => {
+                self.flush();
+                self.check_book(element);
+                return Frame::Division;
+            }
+            "chapter" => return Frame::Division,
+            "note" => {
+                let kind = match element.attr("type") {
+                    Some("crossReference") => NoteKind::CrossReference,
+                    _ => NoteKind::Footnote,
+                };
+                let caller = element.attr("n").unwrap_or("+").to_string();
+                self.note = Some((kind, caller, String::new()));
+                return Frame::Note;
+            }
+            "title" | "header" | "figure" | "speaker" => {
+                self.skip_depth += 1;
+                return Frame::Skip;
+            }
+            // The reference a note is attached to, such as `3:16`, is not part of the note
+            "reference" if element.attr("type") == Some("annotateRef") => {
+                self.skip_depth += 1;
+                return Frame::Skip;
+            }
+            "l" => {
+                let level = element.attr("level").and_then(|l| l.parse().ok());
+                self.pending_breaks
+                    .push(BreakKind::Poetry(level.unwrap_or(1)));
+            }
+            "w" if self.note.is_none() => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.open_word();
+                }
+                let strongs = StrongsNumber::parse_lemma(element.attr("lemma").unwrap_or_default());
+                return Frame::Word(strongs, element.attr("morph").map(str::to_string));
+            }
+            _ if is_paragraph(element) => self.pending_breaks.push(BreakKind::Paragraph),
+            _ => {}
+        }
+        match span_name(element) {
+            Some(name) if self.note.is_none() => {
+                self.apply_breaks();
+                if let Some(builder) = self.current.as_mut() {
+                    builder.open_span(name, span_style(name));
+                }
+                Frame::Span(name)
+            }
+            _ => Frame::Other,
+        }
+    }
+
+    fn empty(&mut self, element: &Element) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        match element.name.as_str() {
+            "verse" => match (element.attr("sID"), element.attr("eID")) {
+                (Some(id), _) => self.begin_verse(element.attr("osisID").unwrap_or(id)),
+                (None, Some(_)) => self.flush(),
+                (None, None) => {}
+            },
+            "chapter" => self.flush(),
+            "div" if element.attr("type") == Some("book") => {
+                self.flush();
+                if element.attr("sID").is_some() {
+                    self.check_book(element);
+                }
+            }
+            "q" if element.attr("who") == Some("Jesus") => {
+                if let Some(id) = element.attr("sID") {
+                    self.jesus_quotes.push(id.to_string());
+                    self.apply_breaks();
+                    if let Some(builder) = self.current.as_mut() {
+                        builder.open_span("wj", SpanStyle::WordsOfJesus);
+                    }
+                }
+            }
+            "q" => {
+                if let Some(id) = element.attr("eID")
+                    && let Some(pos) = self.jesus_quotes.iter().position(|q| q == id)
+                {
+                    self.jesus_quotes.remove(pos);
+                    if let Some(builder) = self.current.as_mut() {
+                        builder.close_span("wj");
+                    }
+                }
+            }
+            "l" if element.attr("sID").is_some() => {
+                let level = element.attr("level").and_then(|l| l.parse().ok());
+                self.pending_breaks
+                    .push(BreakKind::Poetry(level.unwrap_or(1)));
+            }
+            "lb" if element.attr("type") == Some("x-blank") => {
+                self.pending_breaks.push(BreakKind::Blank)
+            }
+            _ if is_paragraph(element) && element.attr("eID").is_none() => {
+                self.pending_breaks.push(BreakKind::Paragraph)
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self) {
+        match self.frames.pop() {
+            Some(Frame::Verse | Frame::Division) => self.flush(),
+            Some(Frame::Span(span)) => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.close_span(span);
+                }
+            }
+            Some(Frame::Word(strongs, morph)) => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.close_word(strongs, morph);
+                }
+            }
+            Some(Frame::Skip) => self.skip_depth -= 1,
+            Some(Frame::Note) => {
+                if let Some((kind, caller, text)) = self.note.take()
+                    && let Some(builder) = self.current.as_mut()
+                {
+                    builder.add_note(kind, caller, &text);
+                }
+            }
+            Some(Frame::Other) | None => {}
+        }
+    }
+
+    fn check_book(&mut self, element: &Element) {
+        if let Some(id) = element.attr("osisID")
+            && BibleBook::from_osis(id).is_err()
+        {
+            self.unknown_book = Some(id.to_string());
+            let line = self.xml.event_line();
+            self.error(ImportError::UnknownBook {
+                line,
+                code: id.to_string(),
+            });
+        }
+    }
+
+    fn begin_verse(&mut self, osis_id: &str) {
+        self.flush();
+        let line = self.xml.event_line();
+        let first = osis_id.split_whitespace().next().unwrap_or_default();
+        let id = OsisId::split(first).id;
+        let parts: Vec<&str> = id.split('.').collect();
+        let [code, chapter, verse] = parts[..] else {
+            return self.error(ImportError::InvalidReference {
+                line,
+                text: osis_id.to_string(),
+            });
+        };
+        let Ok(book) = BibleBook::from_osis(code) else {
+            // Only report an unknown book once
+            if self.unknown_book.as_deref() != Some(code) {
+                self.unknown_book = Some(code.to_string());
+                self.error(ImportError::UnknownBook {
+                    line,
+                    code: code.to_string(),
+                });
+            }
+            return;
+        };
+        let (Ok(chapter), Ok(verse)) = (chapter.parse::<u32>(), verse.parse::<u8>()) else {
+            return self.error(ImportError::InvalidNumber {
+                line,
+                text: id.to_string(),
+            });
+        };
+        if !(1..=book.number_of_chapters()).contains(&chapter) {
+            return self.error(ImportError::ChapterOutOfRange {
+                line,
+                book,
+                chapter,
+            });
+        }
+        let verse = BibleVerse::new(book, chapter as u8, verse);
+        if !verse.is_valid() {
+            return self.error(ImportError::VerseOutOfRange { line, verse });
+        }
+        let mut builder = VerseBuilder::new(verse);
+        if !self.jesus_quotes.is_empty() {
+            builder.open_span("wj", SpanStyle::WordsOfJesus);
+        }
+        self.current = Some(builder);
+        // Breaks before the first words of the verse come before the verse number
+        self.apply_breaks();
+    }
+}
+
+impl<R: BufRead> Iterator for OsisReader<R> {
+    type Item = Result<VerseText, ImportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.xml.next_event() {
+                Ok(Some(event)) => self.process(event),
+                Ok(None) => {
+                    self.finished = true;
+                    self.flush();
+                }
+                Err(e) => {
+                    self.finished = true;
+                    self.flush();
+                    self.error(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::versetext::{Break, Note};
+
+    const CONTAINER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osis xmlns="http://www.bibletechnologies.net/2003/OSIS/namespace">
+<osisText osisIDWork="KJV" xml:lang="en">
+<header><work osisWork="KJV"><title>King James Version</title></work></header>
+<div type="book" osisID="John">
+<title type="main">The Gospel According to John</title>
+<chapter osisID="John.3">
+<title>Jesus and Nicodemus</title>
+<p>
+<verse osisID="John.3.16">For God so loved the world,<note type="study" n="a"><reference type="annotateRef">3:16</reference> Or <catchWord>only begotten</catchWord></note> that he gave his <transChange type="added">only</transChange> begotten Son.</verse>
+<verse osisID="John.3.17"><q who="Jesus" marker="">For God sent not his Son into the world</q><note type="crossReference">Luke 19:10</note>.</verse>
+</p>
+</chapter>
+</div>
+</osisText>
+</osis>"#;
+
+    const MILESTONE: &str = r#"<osis><osisText>
+<div type="book" osisID="Ps">
+<chapter sID="Ps.23" osisID="Ps.23"/>
+<title type="psalm" canonical="true">A Psalm of David.</title>
+<verse sID="Ps.23.1" osisID="Ps.23.1"/><l level="1">The <divineName>Lord</divineName> is my shepherd;</l>
+<l level="2">I shall not want.</l><verse eID="Ps.23.1"/>
+<chapter eID="Ps.23"/>
+</div>
+<div type="book" osisID="Matt">
+<chapter sID="Matt.5" osisID="Matt.5"/>
+<milestone type="x-p"/><verse sID="Matt.5.3" osisID="Matt.5.3"/><q who="Jesus" sID="q1" marker=""/>Blessed are the poor in spirit:<verse eID="Matt.5.3"/>
+<verse sID="Matt.5.4" osisID="Matt.5.4"/>Blessed are they that mourn<q eID="q1" marker=""/>.<verse eID="Matt.5.4"/>
+<chapter eID="Matt.5"/>
+</div>
+</osisText></osis>"#;
+
+    fn read(osis: &str) -> Vec<Result<VerseText, ImportError>> {
+        OsisReader::new(osis.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_container() {
+        let verses: Vec<VerseText> = read(CONTAINER).into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(verses.len(), 2);
+        assert_eq!(verses[0].verse, BibleVerse::new(BibleBook::John, 3, 16));
+        assert_eq!(
+            verses[0].text,
+            "For God so loved the world, that he gave his only begotten Son."
+        );
+        let markup = verses[0].markup.as_ref().unwrap();
+        assert_eq!(
+            markup.breaks,
+            vec![Break {
+                kind: BreakKind::Paragraph,
+                offset: 0
+            }]
+        );
+        assert_eq!(
+            markup.notes,
+            vec![Note {
+                kind: NoteKind::Footnote,
+                offset: 27,
+                caller: "a".to_string(),
+                text: "Or only begotten".to_string()
+            }]
+        );
+        assert_eq!(&verses[0].text[markup.spans[0].range.clone()], "only");
+        assert_eq!(
+            markup.spans[0].style,
+            SpanStyle::Character("add".to_string())
+        );
+
+        assert_eq!(verses[1].text, "For God sent not his Son into the world.");
+        let markup = verses[1].markup.as_ref().unwrap();
+        assert!(markup.has_words_of_jesus());
+        assert_eq!(markup.spans[0].range, 0..39);
+        assert_eq!(markup.notes[0].kind, NoteKind::CrossReference);
+        assert_eq!(markup.notes[0].text, "Luke 19:10");
+        assert!(markup.breaks.is_empty());
+    }
+
+    #[test]
+    fn test_milestones() {
+        let verses: Vec<VerseText> = read(MILESTONE).into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(verses.len(), 3);
+        assert_eq!(verses[0].text, "The Lord is my shepherd; I shall not want.");
+        let markup = verses[0].markup.as_ref().unwrap();
+        assert_eq!(markup.breaks[0].kind, BreakKind::Poetry(1));
+        assert_eq!(
+            markup.breaks[1],
+            Break {
+                kind: BreakKind::Poetry(2),
+                offset: "The Lord is my shepherd;".len()
+            }
+        );
+        assert_eq!(&verses[0].text[markup.spans[0].range.clone()], "Lord");
+
+        assert_eq!(verses[1].verse, BibleVerse::new(BibleBook::Matthew, 5, 3));
+        let markup = verses[1].markup.as_ref().unwrap();
+        assert_eq!(markup.breaks[0].kind, BreakKind::Paragraph);
+        assert_eq!(markup.spans[0].range, 0..verses[1].text.len());
+        // The quote continues into the next verse
+        let markup = verses[2].markup.as_ref().unwrap();
+        assert_eq!(verses[2].text, "Blessed are they that mourn.");
+        assert_eq!(markup.spans[0].range, 0..27);
+        assert!(markup.breaks.is_empty());
+    }
+
+    #[test]
+    fn test_keep_markup() {
+        let verses: Vec<VerseText> = OsisReader::new(MILESTONE.as_bytes())
+            .keep_markup(false)
+            .map(|v| v.unwrap())
+            .collect();
+        assert!(verses.iter().all(|v| v.markup.is_none()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let osis = r#"<osis>
+<div type="book" osisID="Tob">
+<verse osisID="Tob.1.1">Not in this versification</verse>
+<verse osisID="Tob.1.2">Nor this</verse>
+</div>
+<div type="book" osisID="Gen">
+<verse osisID="Gen.1.32">Too far</verse>
+<verse osisID="Gen.51.1">Much too far</verse>
+<verse osisID="Gen.1">Not a verse</verse>
+<verse osisID="Gen.1.31">Very good</verse>
+</div>
+"#;
+        let results = read(osis);
+        assert!(matches!(
+            &results[0],
+            Err(ImportError::UnknownBook { line: 2, code }) if code == "Tob"
+        ));
+        assert!(matches!(
+            results[1],
+            Err(ImportError::VerseOutOfRange {
+                line: 7,
+                verse: BibleVerse {
+                    book: BibleBook::Genesis,
+                    chapter: 1,
+                    verse: 32
+                }
+            })
+        ));
+        assert!(matches!(
+            results[2],
+            Err(ImportError::ChapterOutOfRange {
+                line: 8,
+                chapter: 51,
+                ..
+            })
+        ));
+        assert!(matches!(
+            results[3],
+            Err(ImportError::InvalidReference { line: 9, .. })
+        ));
+        assert_eq!(results[4].as_ref().unwrap().text, "Very good");
+        assert_eq!(results.len(), 5);
+
+        let results = read("<osis><verse osisID=\"Gen.1.1\">In the beginning</osis");
+        assert_eq!(results[0].as_ref().unwrap().text, "In the beginning");
+        assert!(matches!(results[1], Err(ImportError::Xml { .. })));
+    }
+}
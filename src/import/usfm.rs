@@ -0,0 +1,703 @@
+use super::builder::VerseBuilder;
+use super::errors::ImportError;
+use crate::text::versetext::{BreakKind, NoteKind, SpanStyle, VerseText};
+use crate::{BibleBook, BibleVerse, StrongsNumber};
+use std::collections::VecDeque;
+use std::io::{BufRead, Lines};
+
+/// Streaming reader for USFM (Unified Standard Format Markers) files
+///
+/// Yields a [VerseText] for each `\v` in the file, in the order they appear.
+/// Several books may be read from the same stream, each starting with `\id`.
+///
+/// * Paragraph (`\p`, `\m`, `\pi` ...) and poetry (`\q1`, `\q2` ...) markers become
+///   [Break](crate::text::versetext::Break)s.
+/// * Footnotes (`\f ... \f*`) and cross-references (`\x ... \x*`) become
+///   [Note](crate::text::versetext::Note)s and are removed from the text.
+/// * Character styles become [Span](crate::text::versetext::Span)s, with `\wj` as
+///   [SpanStyle::WordsOfJesus]. A `\w` with a `strong` attribute
+///   (`\w word|strong="H1234"\w*`) is also tagged with its Strong's numbers and any
+///   `x-morph`. Other attributes are dropped.
+/// * Headings, titles and introductions are skipped. This includes `\d`, so the
+///   superscriptions of the Psalms are not part of any verse.
+/// * Verse bridges (`\v 1-2`) are keyed by their first verse.
+///
+/// Chapters and verses are checked against the versification of this crate. A chapter
+/// that does not exist is reported as an error and its verses are skipped, as is a
+/// verse that does not exist.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::import::usfm::UsfmReader;
+/// let usfm = "\\id MAT\n\\c 5\n\\p\n\\v 3 \\wj Blessed are the poor in spirit\\wj*";
+/// let mut reader = UsfmReader::new(usfm.as_bytes());
+/// let verse = reader.next().unwrap().unwrap();
+/// assert_eq!(verse.verse, BibleVerse::new(BibleBook::Matthew, 5, 3));
+/// assert_eq!(verse.text, "Blessed are the poor in spirit");
+/// assert!(verse.markup.unwrap().has_words_of_jesus());
+/// ```
+pub struct UsfmReader<R: BufRead> {
+    lines: Lines<R>,
+    line_number: usize,
+    finished: bool,
+    keep_markup: bool,
+    ready: VecDeque<Result<VerseText, ImportError>>,
+    book: Option<BibleBook>,
+    chapter: Option<u8>,
+    skip_chapter: bool,
+    current: Option<VerseBuilder>,
+    pending_breaks: Vec<BreakKind>,
+    context: Context,
+    expect: Option<Argument>,
+    /// The attributes of the current character style, after its `|`
+    attributes: Option<String>,
+}
+
+/// What the reader is in the middle of
+enum Context {
+    /// Verse text
+    Text,
+    /// A heading, title or other block that is not verse text
+    SkipBlock,
+    /// Character content that is not verse text, until the closing marker
+    SkipUntil(String),
+    /// A footnote or cross-reference
+    Note {
+        name: String,
+        kind: NoteKind,
+        caller: Option<String>,
+        text: String,
+        skip: bool,
+    },
+}
+
+/// Markers that are followed by an argument rather than text
+#[derive(Clone, Copy)]
+enum Argument {
+    Id,
+    Chapter,
+    Verse,
+}
+
+enum Token<'a> {
+    Marker { name: &'a str, closing: bool },
+    Text(&'a str),
+}
+
+const PARAGRAPH_MARKERS: &[&str] = &[
+    "p", "m", "po", "pr", "cls", "pmo", "pm", "pmc", "pmr", "pi", "mi", "pc", "ph", "li", "lim",
+    "lh", "lf",
+];
+const POETRY_MARKERS: &[&str] = &["q", "qr", "qc", "qm", "qd"];
+const SKIP_BLOCK_MARKERS: &[&str] = &[
+    "h", "toc", "toca", "mt", "mte", "ms", "mr", "s", "sr", "r", "sp", "sd", "d", "cl", "cd",
+    "rem", "sts", "usfm", "ide", "ip", "ipi", "im", "imi", "ipq", "imq", "ipr", "iq", "ib", "ili",
+    "io", "iot", "is", "imt", "imte", "iex", "ie", "lit", "restore", "cp", "periph",
+];
+pub(crate) const SKIP_CONTENT_MARKERS: &[&str] = &["fig", "rq", "va", "vp", "ca", "cat"];
+pub(crate) const NOTE_SKIP_MARKERS: &[&str] = &["fr", "fv", "xo", "xop"];
+
+/// The marker without any trailing level number eg. `q2` -> `q`
+fn stem(name: &str) -> &str {
+    name.trim_end_matches(|c: char| c.is_ascii_digit())
+}
+
+/// The value of a named attribute in the attribute list of a character style
+/// eg. `strong="H1877" x-morph="He,Aafbp"`
+fn word_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(pos) = rest.find(name) {
+        let before = &rest[..pos];
+        let after = rest[pos + name.len()..].trim_start();
+        rest = &rest[pos + name.len()..];
+        if !before.is_empty() && !before.ends_with(char::is_whitespace) {
+            continue;
+        }
+        if let Some(value) = after.strip_prefix('=') {
+            let value = value.trim_start().strip_prefix('"')?;
+            return Some(&value[..value.find('"')?]);
+        }
+    }
+    None
+}
+
+fn level(name: &str) -> u8 {
+    name[stem(name).len()..].parse().unwrap_or(1)
+}
+
+/// The break that a paragraph marker starts, if it is one that contains verse text
+pub(crate) fn paragraph_break(name: &str) -> Option<BreakKind> {
+    let stem = stem(name);
+    match stem {
+        "b" => Some(BreakKind::Blank),
+        _ if PARAGRAPH_MARKERS.contains(&stem) => Some(BreakKind::Paragraph),
+        _ if POETRY_MARKERS.contains(&stem) => Some(BreakKind::Poetry(level(name))),
+        _ => None,
+    }
+}
+
+/// Whether a paragraph marker starts a heading, title or other block that is not verse text
+pub(crate) fn is_skipped_block(name: &str) -> bool {
+    SKIP_BLOCK_MARKERS.contains(&stem(name))
+}
+
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = line;
+    while let Some(pos) = rest.find('\\') {
+        if pos > 0 {
+            tokens.push(Token::Text(&rest[..pos]));
+        }
+        let after = &rest[pos + 1..];
+        let len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '+' || c == '-'))
+            .unwrap_or(after.len());
+        let name = &after[..len];
+        let mut remain = &after[len..];
+        let closing = remain.starts_with('*');
+        if closing {
+            remain = &remain[1..];
+        } else if let Some(c) = remain.chars().next()
+            && c.is_whitespace()
+        {
+            // A single space after an opening marker is part of the marker
+            remain = &remain[c.len_utf8()..];
+        }
+        tokens.push(Token::Marker {
+            name: name.trim_start_matches('+'),
+            closing,
+        });
+        rest = remain;
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    // The end of the line is whitespace like any other
+    tokens.push(Token::Text(" "));
+    tokens
+}
+
+#[allow(dead_code)]
+impl<R: BufRead> UsfmReader<R> {
+    pub fn new(reader: R) -> Self {
+        UsfmReader {
+            lines: reader.lines(),
+            line_number: 0,
+            finished: false,
+            keep_markup: true,
+            ready: VecDeque::new(),
+            book: None,
+            chapter: None,
+            skip_chapter: false,
+            current: None,
+            pending_breaks: Vec::new(),
+            context: Context::Text,
+            expect: None,
+            attributes: None,
+        }
+    }
+
+    /// Set whether to keep markup. If not, every verse is yielded with `markup` set to None.
+    /// Markup is kept by default.
+    pub fn keep_markup(mut self, keep: bool) -> Self {
+        self.keep_markup = keep;
+        self
+    }
+
+    fn error(&mut self, error: ImportError) {
+        self.ready.push_back(Err(error));
+    }
+
+    fn flush(&mut self) {
+        if let Some(builder) = self.current.take() {
+            self.ready.push_back(Ok(builder.finish(self.keep_markup)));
+        }
+    }
+
+    fn process_line(&mut self, line: &str) {
+        for token in tokenize(line) {
+            match token {
+                Token::Text(text) => self.text(text),
+                Token::Marker { name, closing } => match closing {
+                    true => self.closing_marker(name),
+                    false => self.marker(name),
+                },
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = match self.expect {
+            Some(argument) => {
+                let trimmed = text.trim_start();
+                if trimmed.is_empty() {
+                    return;
+                }
+                let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+                self.expect = None;
+                self.argument(argument, &trimmed[..end]);
+                &trimmed[end..]
+            }
+            None => text,
+        };
+        match &mut self.context {
+            Context::Text => {}
+            Context::Note {
+                caller,
+                text: note,
+                skip,
+                ..
+            } => {
+                if caller.is_none() {
+                    let trimmed = text.trim_start();
+                    if let Some(word) = trimmed.split_whitespace().next() {
+                        *caller = Some(word.to_string());
+                        note.push_str(&trimmed[word.len()..]);
+                    }
+                } else if !*skip {
+                    note.push_str(text);
+                }
+                return;
+            }
+            _ => return,
+        }
+        if let Some(attributes) = self.attributes.as_mut() {
+            attributes.push_str(text);
+            return;
+        }
+        let Some(builder) = self.current.as_mut() else {
+            return;
+        };
+        if text.trim().is_empty() {
+            builder.push_text(text);
+            return;
+        }
+        for kind in self.pending_breaks.drain(..) {
+            builder.add_break(kind);
+        }
+        match text.split_once('|') {
+            Some((before, after)) if builder.has_open_span() => {
+                builder.push_text(before);
+                self.attributes = Some(after.to_string());
+            }
+            _ => builder.push_text(text),
+        }
+    }
+
+    fn argument(&mut self, argument: Argument, value: &str) {
+        let line = self.line_number;
+        match argument {
+            Argument::Id => match BibleBook::from_usfm_code(value) {
+                Ok(book) => self.book = Some(book),
+                Err(_) => {
+                    self.book = None;
+                    self.error(ImportError::UnknownBook {
+                        line,
+                        code: value.to_string(),
+                    });
+                }
+            },
+            Argument::Chapter => {
+                let Some(book) = self.book else {
+                    self.skip_chapter = true;
+                    return self.error(ImportError::MissingBook { line });
+                };
+                match value.parse::<u32>() {
+                    Ok(chapter) if (1..=book.number_of_chapters()).contains(&chapter) => {
+                        self.chapter = Some(chapter as u8);
+                        self.skip_chapter = false;
+                    }
+                    Ok(chapter) => {
+                        self.skip_chapter = true;
+                        self.error(ImportError::ChapterOutOfRange {
+                            line,
+                            book,
+                            chapter,
+                        });
+                    }
+                    Err(_) => {
+                        self.skip_chapter = true;
+                        self.error(ImportError::InvalidNumber {
+                            line,
+                            text: value.to_string(),
+                        });
+                    }
+                }
+            }
+            Argument::Verse => {
+                if self.skip_chapter {
+                    return;
+                }
+                let Some(book) = self.book else {
+                    return self.error(ImportError::MissingBook { line });
+                };
+                let Some(chapter) = self.chapter else {
+                    return self.error(ImportError::MissingChapter { line });
+                };
+                // Verse bridges and segments such as 1-2 or 3a are keyed by the first verse
+                let digits = value
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(value.len());
+                match value[..digits].parse::<u8>() {
+                    Ok(verse) if verse > 0 => {
+                        let verse = BibleVerse::new(book, chapter, verse);
+                        if !verse.is_valid() {
+                            return self.error(ImportError::VerseOutOfRange { line, verse });
+                        }
+                        let mut builder = VerseBuilder::new(verse);
+                        for kind in self.pending_breaks.drain(..) {
+                            builder.add_break(kind);
+                        }
+                        self.current = Some(builder);
+                    }
+                    _ => self.error(ImportError::InvalidNumber {
+                        line,
+                        text: value.to_string(),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Start of a new block. Ends any note, character style or verse text
+    fn block(&mut self, context: Context) {
+        if let Context::Note { .. } = self.context {
+            self.end_note();
+        }
+        self.attributes = None;
+        self.context = context;
+    }
+
+    fn marker(&mut self, name: &str) {
+        let stem = stem(name);
+        match stem {
+            "id" => {
+                self.flush();
+                self.block(Context::SkipBlock);
+                self.chapter = None;
+                self.skip_chapter = false;
+                self.pending_breaks.clear();
+                self.expect = Some(Argument::Id);
+            }
+            "c" => {
+                self.flush();
+                self.block(Context::Text);
+                self.expect = Some(Argument::Chapter);
+            }
+            "v" => {
+                self.flush();
+                self.block(Context::Text);
+                self.expect = Some(Argument::Verse);
+            }
+            "nb" | "pb" => self.block(Context::Text),
+            _ if is_skipped_block(name) => self.block(Context::SkipBlock),
+            _ => match paragraph_break(name) {
+                Some(kind) => {
+                    self.block(Context::Text);
+                    self.pending_breaks.push(kind);
+                }
+                None => self.character_marker(name),
+            },
+        }
+    }
+
+    fn character_marker(&mut self, name: &str) {
+        match &mut self.context {
+            Context::Text => {}
+            Context::Note { skip, .. } => {
+                *skip = NOTE_SKIP_MARKERS.contains(&name);
+                return;
+            }
+            _ => return,
+        }
+        match name {
+            "f" | "fe" | "ef" | "x" | "ex" => {
+                let kind = match name.ends_with('x') {
+                    true => NoteKind::CrossReference,
+                    false => NoteKind::Footnote,
+                };
+                self.context = Context::Note {
+                    name: name.to_string(),
+                    kind,
+                    caller: None,
+                    text: String::new(),
+                    skip: false,
+                };
+            }
+            _ if SKIP_CONTENT_MARKERS.contains(&name) => {
+                self.context = Context::SkipUntil(name.to_string());
+            }
+            _ => {
+                let Some(builder) = self.current.as_mut() else {
+                    return;
+                };
+                for kind in self.pending_breaks.drain(..) {
+                    builder.add_break(kind);
+                }
+                let style = match name {
+                    "wj" => SpanStyle::WordsOfJesus,
+                    _ => SpanStyle::Character(name.to_string()),
+                };
+                builder.open_span(name, style);
+                if name == "w" {
+                    builder.open_word();
+                }
+            }
+        }
+    }
+
+    fn closing_marker(&mut self, name: &str) {
+        match &self.context {
+            Context::Note { name: note, .. } if note == name => {
+                self.end_note();
+                self.context = Context::Text;
+            }
+            Context::SkipUntil(skip) if skip == name => self.context = Context::Text,
+            Context::Text => {
+                let attributes = self.attributes.take().unwrap_or_default();
+                if let Some(builder) = self.current.as_mut() {
+                    if name == "w" {
+                        let strongs = word_attribute(&attributes, "strong").unwrap_or_default();
+                        builder.close_word(
+                            strongs
+                                .split(',')
+                                .filter_map(StrongsNumber::parse)
+                                .collect(),
+                            word_attribute(&attributes, "x-morph").map(str::to_string),
+                        );
+                    }
+                    builder.close_span(name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end_note(&mut self) {
+        let context = std::mem::replace(&mut self.context, Context::Text);
+        if let Context::Note {
+            kind, caller, text, ..
+        } = context
+            && let Some(builder) = self.current.as_mut()
+        {
+            builder.add_note(kind, caller.unwrap_or_default(), &text);
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for UsfmReader<R> {
+    type Item = Result<VerseText, ImportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.lines.next() {
+                None => {
+                    self.finished = true;
+                    self.flush();
+                }
+                Some(Err(e)) => {
+                    self.finished = true;
+                    return Some(Err(e.into()));
+                }
+                Some(Ok(line)) => {
+                    self.line_number += 1;
+                    self.process_line(&line);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::versetext::{Break, Note};
+
+    const JOHN: &str = r#"\id JHN 43-JHN-kjv.sfm The King James Version
+\h John
+\toc1 The Gospel According to John
+\mt1 The Gospel According to John
+\c 3
+\s1 Jesus and Nicodemus
+\p
+\v 16 For God so loved the world,\f + \fr 3:16 \ft Or \fq only begotten\f* that he gave his only begotten Son,
+that whosoever believeth in him should not perish, but have everlasting life.
+\v 17 \wj For God sent not his Son into the world to condemn the world;\wj*
+\p \v 18 He that believeth\x - \xo 3:18 \xt Jn 5:24\x* on him is not condemned.
+\c 11
+\p
+\v 35 Jesus wept.
+"#;
+
+    fn read(usfm: &str) -> Vec<Result<VerseText, ImportError>> {
+        UsfmReader::new(usfm.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_verses() {
+        let verses: Vec<VerseText> = read(JOHN).into_iter().map(|v| v.unwrap()).collect();
+        let refs: Vec<String> = verses.iter().map(|v| v.verse.to_string()).collect();
+        assert_eq!(refs, vec!["Jn 3:16", "Jn 3:17", "Jn 3:18", "Jn 11:35"]);
+        assert_eq!(
+            verses[0].text,
+            "For God so loved the world, that he gave his only begotten Son, that whosoever believeth in him should not perish, but have everlasting life."
+        );
+        assert_eq!(
+            verses[1].text,
+            "For God sent not his Son into the world to condemn the world;"
+        );
+        assert_eq!(verses[2].text, "He that believeth on him is not condemned.");
+        assert_eq!(verses[3].text, "Jesus wept.");
+    }
+
+    #[test]
+    fn test_markup() {
+        let verses: Vec<VerseText> = read(JOHN).into_iter().map(|v| v.unwrap()).collect();
+        let markup = verses[0].markup.as_ref().unwrap();
+        assert_eq!(
+            markup.breaks,
+            vec![Break {
+                kind: BreakKind::Paragraph,
+                offset: 0
+            }]
+        );
+        assert_eq!(
+            markup.notes,
+            vec![Note {
+                kind: NoteKind::Footnote,
+                offset: 27,
+                caller: "+".to_string(),
+                text: "Or only begotten".to_string()
+            }]
+        );
+        assert_eq!(&verses[0].text[..27], "For God so loved the world,");
+        let markup = verses[1].markup.as_ref().unwrap();
+        assert!(markup.has_words_of_jesus());
+        assert_eq!(markup.spans[0].range, 0..verses[1].text.len());
+        assert!(markup.breaks.is_empty());
+        let markup = verses[2].markup.as_ref().unwrap();
+        assert_eq!(markup.breaks[0].offset, 0);
+        assert_eq!(markup.notes[0].kind, NoteKind::CrossReference);
+        assert_eq!(markup.notes[0].text, "Jn 5:24");
+        assert_eq!(markup.notes[0].offset, "He that believeth".len());
+    }
+
+    #[test]
+    fn test_keep_markup() {
+        let verses: Vec<VerseText> = UsfmReader::new(JOHN.as_bytes())
+            .keep_markup(false)
+            .map(|v| v.unwrap())
+            .collect();
+        assert!(verses.iter().all(|v| v.markup.is_none()));
+    }
+
+    #[test]
+    fn test_poetry_and_styles() {
+        let usfm = r#"\id PSA
+\c 23
+\d A Psalm of David.
+\q1
+\v 1 The \nd Lord\nd* is my shepherd;
+\q2 I shall not want.
+\v 2 He maketh me to lie down in \w green|strong="H1877"\w* pastures:
+"#;
+        let verses: Vec<VerseText> = read(usfm).into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(verses[0].text, "The Lord is my shepherd; I shall not want.");
+        let markup = verses[0].markup.as_ref().unwrap();
+        assert_eq!(
+            markup.breaks,
+            vec![
+                Break {
+                    kind: BreakKind::Poetry(1),
+                    offset: 0
+                },
+                Break {
+                    kind: BreakKind::Poetry(2),
+                    offset: "The Lord is my shepherd;".len()
+                }
+            ]
+        );
+        assert_eq!(
+            markup.spans[0].style,
+            SpanStyle::Character("nd".to_string())
+        );
+        assert_eq!(&verses[0].text[markup.spans[0].range.clone()], "Lord");
+        assert_eq!(
+            verses[1].text,
+            "He maketh me to lie down in green pastures:"
+        );
+        let words = &verses[1].markup.as_ref().unwrap().words;
+        assert_eq!(&verses[1].text[words[0].range.clone()], "green");
+        assert_eq!(words[0].strongs, vec![StrongsNumber::hebrew(1877)]);
+    }
+
+    #[test]
+    fn test_word_attribute() {
+        let attributes = r#"lemma="grace" strong="G5485,G5486" x-morph="Gr,N""#;
+        assert_eq!(word_attribute(attributes, "strong"), Some("G5485,G5486"));
+        assert_eq!(word_attribute(attributes, "x-morph"), Some("Gr,N"));
+        assert_eq!(word_attribute(attributes, "morph"), None);
+        assert_eq!(word_attribute("grace", "lemma"), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let results = read("\\id GEN\n\\c 51\n\\v 1 Not here\n\\c 50\n\\v 26 So Joseph died");
+        assert!(matches!(
+            results[0],
+            Err(ImportError::ChapterOutOfRange {
+                line: 2,
+                book: BibleBook::Genesis,
+                chapter: 51
+            })
+        ));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].as_ref().unwrap().text, "So Joseph died");
+
+        let results = read("\\id XYZ\n\\c 1\n\\v 1 Text");
+        assert!(matches!(
+            results[0],
+            Err(ImportError::UnknownBook { line: 1, .. })
+        ));
+        assert!(matches!(
+            results[1],
+            Err(ImportError::MissingBook { line: 2 })
+        ));
+
+        let results = read("\\id GEN\n\\v 1 Text");
+        assert!(matches!(
+            results[0],
+            Err(ImportError::MissingChapter { line: 2 })
+        ));
+
+        let results = read("\\id GEN\n\\c 1\n\\v 32 Text\n\\v 31 Very good");
+        assert!(matches!(
+            results[0],
+            Err(ImportError::VerseOutOfRange { line: 3, .. })
+        ));
+        assert_eq!(results[1].as_ref().unwrap().text, "Very good");
+
+        let results = read("\\id GEN\n\\c 1\n\\v x Text");
+        assert!(matches!(
+            results[0],
+            Err(ImportError::InvalidNumber { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_bridges_and_books() {
+        let usfm =
+            "\\id OBA\n\\c 1\n\\p\n\\v 1-2 Bridge \\va 2\\va* text\n\\id JUD\n\\c 1\n\\v 25 Amen.";
+        let verses: Vec<VerseText> = read(usfm).into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(verses[0].verse, BibleVerse::new(BibleBook::Obadiah, 1, 1));
+        assert_eq!(verses[0].text, "Bridge text");
+        assert_eq!(verses[1].verse, BibleVerse::new(BibleBook::Jude, 1, 25));
+    }
+}
@@ -0,0 +1,472 @@
+use super::builder::VerseBuilder;
+use super::errors::ImportError;
+use super::usfm::{NOTE_SKIP_MARKERS, SKIP_CONTENT_MARKERS, is_skipped_block, paragraph_break};
+use super::xml::{Element, XmlEvent, XmlReader};
+use crate::text::versetext::{BreakKind, NoteKind, SpanStyle, VerseText};
+use crate::{BibleBook, BibleVerse, StrongsNumber};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// Streaming reader for USX (Paratext XML) files
+///
+/// USX is the XML form of USFM, so the text and markup is handled in the same way as
+/// by [UsfmReader](super::usfm::UsfmReader): `<para>` styles become breaks or are
+/// skipped, `<note>`s become notes and `<char>` styles become spans.
+/// A `<char style="w">` with a `strong` attribute is also tagged with its Strong's numbers
+/// and any `x-morph`. Other attributes, such as `lemma`, are dropped.
+/// Verses end at `<verse eid>` if present (USX 3) or at the next verse or chapter (USX 2).
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::import::usx::UsxReader;
+/// let usx = r#"<usx version="3.0"><book code="JHN" style="id"/>
+/// <chapter number="11" style="c" sid="JHN 11"/>
+/// <para style="p"><verse number="35" style="v" sid="JHN 11:35"/>Jesus wept.<verse eid="JHN 11:35"/></para>
+/// </usx>"#;
+/// let mut reader = UsxReader::new(usx.as_bytes());
+/// let verse = reader.next().unwrap().unwrap();
+/// assert_eq!(verse.verse, BibleVerse::new(BibleBook::John, 11, 35));
+/// assert_eq!(verse.text, "Jesus wept.");
+/// ```
+pub struct UsxReader<R: BufRead> {
+    xml: XmlReader<R>,
+    finished: bool,
+    keep_markup: bool,
+    ready: VecDeque<Result<VerseText, ImportError>>,
+    book: Option<BibleBook>,
+    chapter: Option<u8>,
+    skip_chapter: bool,
+    current: Option<VerseBuilder>,
+    pending_breaks: Vec<BreakKind>,
+    frames: Vec<Frame>,
+    skip_depth: usize,
+    note: Option<(NoteKind, String, String)>,
+}
+
+/// What to do when an element ends
+enum Frame {
+    Span(String),
+    /// A `\w` character style with its Strong's numbers and morphology
+    Word(Vec<StrongsNumber>, Option<String>),
+    Skip,
+    Note,
+    Other,
+}
+
+#[allow(dead_code)]
+impl<R: BufRead> UsxReader<R> {
+    pub fn new(reader: R) -> Self {
+        UsxReader {
+            xml: XmlReader::new(reader),
+            finished: false,
+            keep_markup: true,
+            ready: VecDeque::new(),
+            book: None,
+            chapter: None,
+            skip_chapter: false,
+            current: None,
+            pending_breaks: Vec::new(),
+            frames: Vec::new(),
+            skip_depth: 0,
+            note: None,
+        }
+    }
+
+    /// Set whether to keep markup. If not, every verse is yielded with `markup` set to None.
+    /// Markup is kept by default.
+    pub fn keep_markup(mut self, keep: bool) -> Self {
+        self.keep_markup = keep;
+        self
+    }
+
+    fn error(&mut self, error: ImportError) {
+        self.ready.push_back(Err(error));
+    }
+
+    fn flush(&mut self) {
+        if let Some(builder) = self.current.take() {
+            self.ready.push_back(Ok(builder.finish(self.keep_markup)));
+        }
+    }
+
+    fn apply_breaks(&mut self) {
+        if let Some(builder) = self.current.as_mut() {
+            for kind in self.pending_breaks.drain(..) {
+                builder.add_break(kind);
+            }
+        }
+    }
+
+    fn process(&mut self, event: XmlEvent) {
+        match event {
+            XmlEvent::Text(text) => self.text(&text),
+            XmlEvent::Start(element) => {
+                let frame = self.start(&element);
+                self.frames.push(frame);
+            }
+            XmlEvent::Empty(element) => {
+                // An empty element is a start and end together
+                let frame = self.start(&element);
+                self.frames.push(frame);
+                self.end();
+            }
+            XmlEvent::End(_) => self.end(),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        if let Some((_, _, note)) = self.note.as_mut() {
+            note.push_str(text);
+            return;
+        }
+        if self.current.is_none() {
+            return;
+        }
+        if !text.trim().is_empty() {
+            self.apply_breaks();
+        }
+        if let Some(builder) = self.current.as_mut() {
+            builder.push_text(text);
+        }
+    }
+
+    fn start(&mut self, element: &Element) -> Frame {
+        if self.skip_depth > 0 {
+            self.skip_depth += 1;
+            return Frame::Skip;
+        }
+        let style = element.attr("style").unwrap_or_default();
+        match element.name.as_str() {
+            "book" => {
+                self.flush();
+                self.begin_book(element.attr("code").unwrap_or_default());
+                self.skip_depth += 1;
+                Frame::Skip
+            }
+            "chapter" => {
+                self.flush();
+                if let Some(number) = element.attr("number") {
+                    self.begin_chapter(number);
+                }
+                Frame::Other
+            }
+            "verse" => {
+                self.flush();
+                if let Some(number) = element.attr("number") {
+                    self.begin_verse(number);
+                }
+                Frame::Other
+            }
+            "para" if is_skipped_block(style) => {
+                self.skip_depth += 1;
+                Frame::Skip
+            }
+            "para" => {
+                if let Some(kind) = paragraph_break(style) {
+                    self.pending_breaks.push(kind);
+                }
+                Frame::Other
+            }
+            "note" if self.note.is_none() => {
+                let kind = match style {
+                    "x" | "ex" => NoteKind::CrossReference,
+                    _ => NoteKind::Footnote,
+                };
+                let caller = element.attr("caller").unwrap_or("+").to_string();
+                self.note = Some((kind, caller, String::new()));
+                Frame::Note
+            }
+            "char" if self.note.is_some() => match NOTE_SKIP_MARKERS.contains(&style) {
+                true => {
+                    self.skip_depth += 1;
+                    Frame::Skip
+                }
+                false => Frame::Other,
+            },
+            "char" if SKIP_CONTENT_MARKERS.contains(&style) => {
+                self.skip_depth += 1;
+                Frame::Skip
+            }
+            "char" if style == "w" => {
+                self.apply_breaks();
+                if let Some(builder) = self.current.as_mut() {
+                    builder.open_span(style, SpanStyle::Character(style.to_string()));
+                    builder.open_word();
+                }
+                let strongs = element.attr("strong").unwrap_or_default().split(',');
+                Frame::Word(
+                    strongs.filter_map(StrongsNumber::parse).collect(),
+                    element.attr("x-morph").map(str::to_string),
+                )
+            }
+            "char" => {
+                self.apply_breaks();
+                if let Some(builder) = self.current.as_mut() {
+                    let span = match style {
+                        "wj" => SpanStyle::WordsOfJesus,
+                        _ => SpanStyle::Character(style.to_string()),
+                    };
+                    builder.open_span(style, span);
+                }
+                Frame::Span(style.to_string())
+            }
+            "figure" | "sidebar" => {
+                self.skip_depth += 1;
+                Frame::Skip
+            }
+            _ => Frame::Other,
+        }
+    }
+
+    fn end(&mut self) {
+        match self.frames.pop() {
+            Some(Frame::Span(style)) => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.close_span(&style);
+                }
+            }
+            Some(Frame::Word(strongs, morph)) => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.close_word(strongs, morph);
+                    builder.close_span("w");
+                }
+            }
+            Some(Frame::Skip) => self.skip_depth -= 1,
+            Some(Frame::Note) => {
+                if let Some((kind, caller, text)) = self.note.take()
+                    && let Some(builder) = self.current.as_mut()
+                {
+                    builder.add_note(kind, caller, &text);
+                }
+            }
+            Some(Frame::Other) | None => {}
+        }
+    }
+
+    fn begin_book(&mut self, code: &str) {
+        self.chapter = None;
+        self.skip_chapter = false;
+        self.pending_breaks.clear();
+        match BibleBook::from_usfm_code(code) {
+            Ok(book) => self.book = Some(book),
+            Err(_) => {
+                self.book = None;
+                let line = self.xml.event_line();
+                self.error(ImportError::UnknownBook {
+                    line,
+                    code: code.to_string(),
+                });
+            }
+        }
+    }
+
+    fn begin_chapter(&mut self, number: &str) {
+        let line = self.xml.event_line();
+        self.skip_chapter = true;
+        let Some(book) = self.book else {
+            return self.error(ImportError::MissingBook { line });
+        };
+        match number.parse::<u32>() {
+            Ok(chapter) if (1..=book.number_of_chapters()).contains(&chapter) => {
+                self.chapter = Some(chapter as u8);
+                self.skip_chapter = false;
+            }
+            Ok(chapter) => self.error(ImportError::ChapterOutOfRange {
+                line,
+                book,
+                chapter,
+            }),
+            Err(_) => self.error(ImportError::InvalidNumber {
+                line,
+                text: number.to_string(),
+            }),
+        }
+    }
+
+    fn begin_verse(&mut self, number: &str) {
+        if self.skip_chapter {
+            return;
+        }
+        let line = self.xml.event_line();
+        let Some(book) = self.book else {
+            return self.error(ImportError::MissingBook { line });
+        };
+        let Some(chapter) = self.chapter else {
+            return self.error(ImportError::MissingChapter { line });
+        };
+        // Verse bridges and segments such as 1-2 or 3a are keyed by the first verse
+        let digits = number
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(number.len());
+        let verse = match number[..digits].parse::<u8>() {
+            Ok(verse) if verse > 0 => BibleVerse::new(book, chapter, verse),
+            _ => {
+                return self.error(ImportError::InvalidNumber {
+                    line,
+                    text: number.to_string(),
+                });
+            }
+        };
+        if !verse.is_valid() {
+            return self.error(ImportError::VerseOutOfRange { line, verse });
+        }
+        self.current = Some(VerseBuilder::new(verse));
+    }
+}
+
+impl<R: BufRead> Iterator for UsxReader<R> {
+    type Item = Result<VerseText, ImportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.xml.next_event() {
+                Ok(Some(event)) => self.process(event),
+                Ok(None) => {
+                    self.finished = true;
+                    self.flush();
+                }
+                Err(e) => {
+                    self.finished = true;
+                    self.flush();
+                    self.error(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::versetext::{Break, Note};
+
+    const JOHN: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<usx version="3.0">
+  <book code="JHN" style="id">The King James Version</book>
+  <para style="h">John</para>
+  <para style="mt1">The Gospel According to John</para>
+  <chapter number="3" style="c" sid="JHN 3" />
+  <para style="s1">Jesus and Nicodemus</para>
+  <para style="p">
+    <verse number="16" style="v" sid="JHN 3:16" />For God so loved the world,<note caller="+" style="f"><char style="fr" closed="false">3:16 </char><char style="ft" closed="false">Or </char><char style="fq">only begotten</char></note> that he gave his only begotten Son.<verse eid="JHN 3:16" />
+    <verse number="17" style="v" sid="JHN 3:17" /><char style="wj">For God sent not his Son into the world</char>.<verse eid="JHN 3:17" /></para>
+  <chapter eid="JHN 3" />
+</usx>"#;
+
+    const PSALM: &str = r#"<usx version="2.0">
+<book code="PSA" style="id" />
+<chapter number="23" style="c" />
+<para style="d">A Psalm of David.</para>
+<para style="q1"><verse number="1" style="v" />The <char style="nd">Lord</char> is my shepherd;</para>
+<para style="q2">I shall not want.</para>
+<para style="q1"><verse number="2" style="v" />He maketh me to lie down in <char style="w" strong="H1877" lemma="דֶּשֶׁא" x-morph="He,Ncmpc">green</char> pastures:</para>
+</usx>"#;
+
+    fn read(usx: &str) -> Vec<Result<VerseText, ImportError>> {
+        UsxReader::new(usx.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_verses() {
+        let verses: Vec<VerseText> = read(JOHN).into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(verses.len(), 2);
+        assert_eq!(verses[0].verse, BibleVerse::new(BibleBook::John, 3, 16));
+        assert_eq!(
+            verses[0].text,
+            "For God so loved the world, that he gave his only begotten Son."
+        );
+        let markup = verses[0].markup.as_ref().unwrap();
+        assert_eq!(
+            markup.breaks,
+            vec![Break {
+                kind: BreakKind::Paragraph,
+                offset: 0
+            }]
+        );
+        assert_eq!(
+            markup.notes,
+            vec![Note {
+                kind: NoteKind::Footnote,
+                offset: 27,
+                caller: "+".to_string(),
+                text: "Or only begotten".to_string()
+            }]
+        );
+        assert_eq!(verses[1].text, "For God sent not his Son into the world.");
+        let markup = verses[1].markup.as_ref().unwrap();
+        assert!(markup.has_words_of_jesus());
+        assert_eq!(markup.spans[0].range, 0..39);
+    }
+
+    #[test]
+    fn test_usx2() {
+        let verses: Vec<VerseText> = read(PSALM).into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(verses.len(), 2);
+        assert_eq!(verses[0].text, "The Lord is my shepherd; I shall not want.");
+        let markup = verses[0].markup.as_ref().unwrap();
+        assert_eq!(markup.breaks[0].kind, BreakKind::Poetry(1));
+        assert_eq!(markup.breaks[1].kind, BreakKind::Poetry(2));
+        assert_eq!(&verses[0].text[markup.spans[0].range.clone()], "Lord");
+        assert_eq!(
+            verses[1].text,
+            "He maketh me to lie down in green pastures:"
+        );
+        let words = &verses[1].markup.as_ref().unwrap().words;
+        assert_eq!(&verses[1].text[words[0].range.clone()], "green");
+        assert_eq!(words[0].strongs, vec![StrongsNumber::hebrew(1877)]);
+        assert_eq!(words[0].morph.as_deref(), Some("He,Ncmpc"));
+        assert_eq!(
+            verses[1].markup.as_ref().unwrap().breaks[0],
+            Break {
+                kind: BreakKind::Poetry(1),
+                offset: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let usx = r#"<usx>
+<book code="XYZ" style="id"/>
+<chapter number="1" style="c"/>
+<book code="GEN" style="id"/>
+<chapter number="51" style="c"/>
+<para style="p"><verse number="1" style="v"/>Skipped</para>
+<chapter number="1" style="c"/>
+<para style="p"><verse number="32" style="v"/>Too far<verse number="31" style="v"/>Very good</para>
+</usx>"#;
+        let results = read(usx);
+        assert!(matches!(
+            results[0],
+            Err(ImportError::UnknownBook { line: 2, .. })
+        ));
+        assert!(matches!(
+            results[1],
+            Err(ImportError::MissingBook { line: 3 })
+        ));
+        assert!(matches!(
+            results[2],
+            Err(ImportError::ChapterOutOfRange {
+                line: 5,
+                chapter: 51,
+                ..
+            })
+        ));
+        assert!(matches!(
+            results[3],
+            Err(ImportError::VerseOutOfRange { line: 8, .. })
+        ));
+        assert_eq!(results[4].as_ref().unwrap().text, "Very good");
+        assert_eq!(results.len(), 5);
+    }
+}
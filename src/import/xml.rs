@@ -0,0 +1,322 @@
+use super::errors::ImportError;
+use std::io::BufRead;
+
+/// A minimal streaming XML reader, enough for the XML Bible formats
+///
+/// Comments, processing instructions and the DOCTYPE are skipped. Namespace prefixes
+/// are removed from element names. Entities are decoded in text and attribute values.
+pub(crate) struct XmlReader<R: BufRead> {
+    reader: R,
+    line: usize,
+    event_line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum XmlEvent {
+    Start(Element),
+    /// An element with no content eg. `<verse eID="Gen.1.1"/>`
+    Empty(Element),
+    End(String),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+}
+
+impl Element {
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Remove any namespace prefix eg. `osis:verse` -> `verse`
+fn local_name(name: &str) -> &str {
+    match name.split_once(':') {
+        Some((_, local)) => local,
+        None => name,
+    }
+}
+
+/// Replace the predefined and numeric entities with the characters they represent
+/// Unknown entities are left as they are.
+pub(crate) fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('&') {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                entity => {
+                    let number = entity.strip_prefix('#')?;
+                    let code = match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => number.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+impl<R: BufRead> XmlReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        XmlReader {
+            reader,
+            line: 1,
+            event_line: 1,
+        }
+    }
+
+    /// The line on which the last event started
+    pub(crate) fn event_line(&self) -> usize {
+        self.event_line
+    }
+
+    fn error(&self, message: impl Into<String>) -> ImportError {
+        ImportError::Xml {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, ImportError> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    fn bump(&mut self) -> Result<Option<u8>, ImportError> {
+        let byte = self.peek()?;
+        if let Some(b) = byte {
+            self.reader.consume(1);
+            if b == b'\n' {
+                self.line += 1;
+            }
+        }
+        Ok(byte)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), ImportError> {
+        for _ in 0..count {
+            self.bump()?;
+        }
+        Ok(())
+    }
+
+    /// Read up to and including `end`, returning what came before it
+    fn read_until(&mut self, end: &[u8]) -> Result<Vec<u8>, ImportError> {
+        let mut bytes = Vec::new();
+        loop {
+            match self.bump()? {
+                None => return Err(self.error("Unexpected end of file")),
+                Some(b) => {
+                    bytes.push(b);
+                    if bytes.ends_with(end) {
+                        bytes.truncate(bytes.len() - end.len());
+                        return Ok(bytes);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Read the rest of a tag up to the closing `>`, allowing for `>` in quoted values
+    fn read_tag(&mut self) -> Result<Vec<u8>, ImportError> {
+        let mut bytes = Vec::new();
+        let mut quote = None;
+        loop {
+            let b = self
+                .bump()?
+                .ok_or_else(|| self.error("Unexpected end of file in tag"))?;
+            match quote {
+                Some(q) if b == q => quote = None,
+                Some(_) => {}
+                None if b == b'"' || b == b'\'' => quote = Some(b),
+                None if b == b'>' => return Ok(bytes),
+                None => {}
+            }
+            bytes.push(b);
+        }
+    }
+
+    fn to_string(&self, bytes: Vec<u8>) -> Result<String, ImportError> {
+        String::from_utf8(bytes).map_err(|_| self.error("Invalid UTF-8"))
+    }
+
+    fn parse_element(&self, tag: &str) -> Result<Element, ImportError> {
+        let tag = tag.trim();
+        let end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = local_name(&tag[..end]).to_string();
+        if name.is_empty() {
+            return Err(self.error("Missing element name"));
+        }
+        let mut attributes = Vec::new();
+        let mut rest = tag[end..].trim_start();
+        while !rest.is_empty() {
+            let (attr, value) = rest
+                .split_once('=')
+                .ok_or_else(|| self.error(format!("Invalid attribute in <{}>", name)))?;
+            let value = value.trim_start();
+            let quote = value
+                .chars()
+                .next()
+                .filter(|&c| c == '"' || c == '\'')
+                .ok_or_else(|| self.error(format!("Unquoted attribute in <{}>", name)))?;
+            let close = value[1..]
+                .find(quote)
+                .ok_or_else(|| self.error(format!("Unterminated attribute in <{}>", name)))?;
+            attributes.push((attr.trim().to_string(), unescape(&value[1..close + 1])));
+            rest = value[close + 2..].trim_start();
+        }
+        Ok(Element { name, attributes })
+    }
+
+    /// Read the next event, or None at the end of the document
+    pub(crate) fn next_event(&mut self) -> Result<Option<XmlEvent>, ImportError> {
+        loop {
+            self.event_line = self.line;
+            match self.peek()? {
+                None => return Ok(None),
+                Some(b'<') => {}
+                Some(_) => {
+                    let mut bytes = Vec::new();
+                    while let Some(b) = self.peek()? {
+                        if b == b'<' {
+                            break;
+                        }
+                        bytes.push(b);
+                        self.bump()?;
+                    }
+                    return Ok(Some(XmlEvent::Text(unescape(&self.to_string(bytes)?))));
+                }
+            }
+            self.bump()?;
+            if self.peek()? == Some(b'?') {
+                self.read_until(b"?>")?;
+            } else if self.peek()? == Some(b'!') {
+                self.bump()?;
+                match self.peek()? {
+                    Some(b'-') => {
+                        self.read_until(b"-->")?;
+                    }
+                    Some(b'[') => {
+                        // <![CDATA[
+                        self.skip(7)?;
+                        let bytes = self.read_until(b"]]>")?;
+                        return Ok(Some(XmlEvent::Text(self.to_string(bytes)?)));
+                    }
+                    _ => {
+                        // DOCTYPE. May contain an internal subset in [brackets]
+                        let mut depth = 0;
+                        loop {
+                            match self.bump()? {
+                                None => {
+                                    return Err(self.error("Unexpected end of file in DOCTYPE"));
+                                }
+                                Some(b'[') => depth += 1,
+                                Some(b']') => depth -= 1,
+                                Some(b'>') if depth == 0 => break,
+                                Some(_) => {}
+                            }
+                        }
+                    }
+                }
+            } else {
+                let tag = self.read_tag()?;
+                let tag = self.to_string(tag)?;
+                if let Some(name) = tag.strip_prefix('/') {
+                    return Ok(Some(XmlEvent::End(local_name(name.trim()).to_string())));
+                }
+                return Ok(Some(match tag.strip_suffix('/') {
+                    Some(tag) => XmlEvent::Empty(self.parse_element(tag)?),
+                    None => XmlEvent::Start(self.parse_element(&tag)?),
+                }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(xml: &str) -> Vec<XmlEvent> {
+        let mut reader = XmlReader::new(xml.as_bytes());
+        let mut events = Vec::new();
+        while let Some(event) = reader.next_event().unwrap() {
+            events.push(event);
+        }
+        events
+    }
+
+    #[test]
+    fn test_events() {
+        let xml = r#"<?xml version="1.0"?>
+<!DOCTYPE osis [ <!ENTITY x "y"> ]><!-- comment -->
+<osis:verse osisID="Gen.1.1" note='a > b'>In &amp; <![CDATA[<out>]]></osis:verse><br/>"#;
+        assert_eq!(
+            events(xml),
+            vec![
+                XmlEvent::Text("\n".to_string()),
+                XmlEvent::Text("\n".to_string()),
+                XmlEvent::Start(Element {
+                    name: "verse".to_string(),
+                    attributes: vec![
+                        ("osisID".to_string(), "Gen.1.1".to_string()),
+                        ("note".to_string(), "a > b".to_string())
+                    ]
+                }),
+                XmlEvent::Text("In & ".to_string()),
+                XmlEvent::Text("<out>".to_string()),
+                XmlEvent::End("verse".to_string()),
+                XmlEvent::Empty(Element {
+                    name: "br".to_string(),
+                    attributes: vec![]
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("&lt;a&gt; &quot;&apos;&#65;&#x42;"), "<a> \"'AB");
+        assert_eq!(unescape("AT&T &unknown; &"), "AT&T &unknown; &");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut reader = XmlReader::new("<a>\n<b x=1>".as_bytes());
+        assert!(reader.next_event().is_ok());
+        assert!(reader.next_event().is_ok());
+        assert!(matches!(
+            reader.next_event(),
+            Err(ImportError::Xml { line: 2, .. })
+        ));
+        let mut reader = XmlReader::new("<a".as_bytes());
+        assert!(reader.next_event().is_err());
+    }
+}
@@ -0,0 +1,447 @@
+use super::builder::VerseBuilder;
+use super::errors::ImportError;
+use super::xml::{Element, XmlEvent, XmlReader};
+use crate::text::versetext::{BreakKind, NoteKind, SpanStyle, VerseText};
+use crate::{BibleBook, BibleVerse, StrongsLanguage, StrongsNumber};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// Streaming reader for Zefania XML files
+///
+/// Yields a [VerseText] for each `<VERS>` in the file, in the order they appear.
+/// Books are identified by their `bnumber`, which follows the same order as [BibleBook].
+///
+/// * `<NOTE>`s become footnotes and `<XREF>`s cross-references.
+/// * `<STYLE fs="...">` becomes a character span named as its USFM equivalent
+///   eg. `italic` -> `it`, `divineName` -> `nd`.
+/// * `<BR>` becomes a paragraph [Break](crate::text::versetext::Break).
+/// * `<gr str="...">` becomes a [WordTag](crate::text::versetext::WordTag) with its
+///   Strong's numbers and any `rmac` morphology. Bare numbers are Hebrew in the Old
+///   Testament and Greek in the New.
+/// * Captions, prologs and remarks are skipped.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::import::zefania::ZefaniaReader;
+/// let xml = r#"<XMLBIBLE biblename="KJV"><BIBLEBOOK bnumber="43" bname="John">
+/// <CHAPTER cnumber="11"><VERS vnumber="35">Jesus wept.</VERS></CHAPTER>
+/// </BIBLEBOOK></XMLBIBLE>"#;
+/// let mut reader = ZefaniaReader::new(xml.as_bytes());
+/// let verse = reader.next().unwrap().unwrap();
+/// assert_eq!(verse.verse, BibleVerse::new(BibleBook::John, 11, 35));
+/// assert_eq!(verse.text, "Jesus wept.");
+/// ```
+pub struct ZefaniaReader<R: BufRead> {
+    xml: XmlReader<R>,
+    finished: bool,
+    keep_markup: bool,
+    ready: VecDeque<Result<VerseText, ImportError>>,
+    book: Option<BibleBook>,
+    chapter: Option<u8>,
+    current: Option<VerseBuilder>,
+    pending_breaks: Vec<BreakKind>,
+    frames: Vec<Frame>,
+    skip_depth: usize,
+    note: Option<(NoteKind, String)>,
+}
+
+/// What to do when an element ends
+enum Frame {
+    Verse,
+    Span(&'static str),
+    /// A `<gr>` with its Strong's numbers and morphology
+    Word(Vec<StrongsNumber>, Option<String>),
+    Skip,
+    Note,
+    Other,
+}
+
+/// The Strong's numbers in a `str` attribute such as `2637` or `H7225 H430`
+fn parse_strongs(text: &str, book: Option<BibleBook>) -> Vec<StrongsNumber> {
+    let language = match book.is_some_and(|book| book.is_new_testament()) {
+        true => StrongsLanguage::Greek,
+        false => StrongsLanguage::Hebrew,
+    };
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|part| match part.parse() {
+            Ok(number) => Some(StrongsNumber::new(language, number)),
+            Err(_) => StrongsNumber::parse(part),
+        })
+        .collect()
+}
+
+/// The USFM name of the character style for a `<STYLE>` element, if it has one
+fn span_name(element: &Element) -> Option<&'static str> {
+    match element.attr("fs")? {
+        "italic" => Some("it"),
+        "bold" => Some("bd"),
+        "emphasis" => Some("em"),
+        "super" => Some("sup"),
+        "sub" => Some("sub"),
+        "divineName" => Some("nd"),
+        "acrostic" => Some("qac"),
+        _ => None,
+    }
+}
+
+#[allow(dead_code)]
+impl<R: BufRead> ZefaniaReader<R> {
+    pub fn new(reader: R) -> Self {
+        ZefaniaReader {
+            xml: XmlReader::new(reader),
+            finished: false,
+            keep_markup: true,
+            ready: VecDeque::new(),
+            book: None,
+            chapter: None,
+            current: None,
+            pending_breaks: Vec::new(),
+            frames: Vec::new(),
+            skip_depth: 0,
+            note: None,
+        }
+    }
+
+    /// Set whether to keep markup. If not, every verse is yielded with `markup` set to None.
+    /// Markup is kept by default.
+    pub fn keep_markup(mut self, keep: bool) -> Self {
+        self.keep_markup = keep;
+        self
+    }
+
+    fn error(&mut self, error: ImportError) {
+        self.ready.push_back(Err(error));
+    }
+
+    fn flush(&mut self) {
+        if let Some(builder) = self.current.take() {
+            self.ready.push_back(Ok(builder.finish(self.keep_markup)));
+        }
+    }
+
+    fn process(&mut self, event: XmlEvent) {
+        match event {
+            XmlEvent::Text(text) => self.text(&text),
+            XmlEvent::Start(element) => {
+                let frame = self.start(&element);
+                self.frames.push(frame);
+            }
+            XmlEvent::Empty(element) => {
+                // An empty element is a start and end together
+                let frame = self.start(&element);
+                self.frames.push(frame);
+                self.end();
+            }
+            XmlEvent::End(_) => self.end(),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        if let Some((_, note)) = self.note.as_mut() {
+            note.push_str(text);
+            return;
+        }
+        let Some(builder) = self.current.as_mut() else {
+            return;
+        };
+        if !text.trim().is_empty() {
+            for kind in self.pending_breaks.drain(..) {
+                builder.add_break(kind);
+            }
+        }
+        builder.push_text(text);
+    }
+
+    fn start(&mut self, element: &Element) -> Frame {
+        if self.skip_depth > 0 {
+            self.skip_depth += 1;
+            return Frame::Skip;
+        }
+        // Zefania element names are upper case, but not every file follows that
+        match element.name.to_ascii_uppercase().as_str() {
+            "BIBLEBOOK" => {
+                self.flush();
+                self.begin_book(element.attr("bnumber").unwrap_or_default());
+                Frame::Other
+            }
+            "CHAPTER" => {
+                self.flush();
+                self.begin_chapter(element.attr("cnumber").unwrap_or_default());
+                Frame::Other
+            }
+            "VERS" => {
+                self.flush();
+                self.begin_verse(element.attr("vnumber").unwrap_or_default());
+                Frame::Verse
+            }
+            "NOTE" if self.note.is_none() => {
+                self.note = Some((NoteKind::Footnote, String::new()));
+                Frame::Note
+            }
+            "XREF" if self.note.is_none() => {
+                let scope = element.attr("fscope").unwrap_or_default();
+                self.note = Some((NoteKind::CrossReference, scope.to_string()));
+                Frame::Note
+            }
+            "BR" => {
+                self.pending_breaks.push(BreakKind::Paragraph);
+                Frame::Other
+            }
+            "STYLE" => match span_name(element) {
+                Some(name) if self.note.is_none() => {
+                    if let Some(builder) = self.current.as_mut() {
+                        for kind in self.pending_breaks.drain(..) {
+                            builder.add_break(kind);
+                        }
+                        builder.open_span(name, SpanStyle::Character(name.to_string()));
+                    }
+                    Frame::Span(name)
+                }
+                _ => Frame::Other,
+            },
+            "GR" if self.note.is_none() => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.open_word();
+                }
+                let strongs = parse_strongs(element.attr("str").unwrap_or_default(), self.book);
+                Frame::Word(strongs, element.attr("rmac").map(str::to_string))
+            }
+            "CAPTION" | "INFORMATION" | "PROLOG" | "REMARK" | "MEDIA" => {
+                self.skip_depth += 1;
+                Frame::Skip
+            }
+            _ => Frame::Other,
+        }
+    }
+
+    fn end(&mut self) {
+        match self.frames.pop() {
+            Some(Frame::Verse) => self.flush(),
+            Some(Frame::Span(name)) => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.close_span(name);
+                }
+            }
+            Some(Frame::Word(strongs, morph)) => {
+                if let Some(builder) = self.current.as_mut() {
+                    builder.close_word(strongs, morph);
+                }
+            }
+            Some(Frame::Skip) => self.skip_depth -= 1,
+            Some(Frame::Note) => {
+                if let Some((kind, text)) = self.note.take()
+                    && let Some(builder) = self.current.as_mut()
+                {
+                    builder.add_note(kind, "+".to_string(), &text);
+                }
+            }
+            Some(Frame::Other) | None => {}
+        }
+    }
+
+    fn begin_book(&mut self, number: &str) {
+        self.chapter = None;
+        self.pending_breaks.clear();
+        self.book = number
+            .parse::<u32>()
+            .ok()
+            .and_then(|number| BibleBook::from_book_number(number).ok());
+        if self.book.is_none() {
+            let line = self.xml.event_line();
+            self.error(ImportError::UnknownBook {
+                line,
+                code: number.to_string(),
+            });
+        }
+    }
+
+    fn begin_chapter(&mut self, number: &str) {
+        // Chapters in a book that was not recognised have already been reported
+        let Some(book) = self.book else {
+            return;
+        };
+        let line = self.xml.event_line();
+        self.chapter = None;
+        match number.parse::<u32>() {
+            Ok(chapter) if (1..=book.number_of_chapters()).contains(&chapter) => {
+                self.chapter = Some(chapter as u8);
+            }
+            Ok(chapter) => self.error(ImportError::ChapterOutOfRange {
+                line,
+                book,
+                chapter,
+            }),
+            Err(_) => self.error(ImportError::InvalidNumber {
+                line,
+                text: number.to_string(),
+            }),
+        }
+    }
+
+    fn begin_verse(&mut self, number: &str) {
+        let (Some(book), Some(chapter)) = (self.book, self.chapter) else {
+            return;
+        };
+        let line = self.xml.event_line();
+        let verse = match number.parse::<u8>() {
+            Ok(verse) if verse > 0 => BibleVerse::new(book, chapter, verse),
+            _ => {
+                return self.error(ImportError::InvalidNumber {
+                    line,
+                    text: number.to_string(),
+                });
+            }
+        };
+        if !verse.is_valid() {
+            return self.error(ImportError::VerseOutOfRange { line, verse });
+        }
+        let mut builder = VerseBuilder::new(verse);
+        for kind in self.pending_breaks.drain(..) {
+            builder.add_break(kind);
+        }
+        self.current = Some(builder);
+    }
+}
+
+impl<R: BufRead> Iterator for ZefaniaReader<R> {
+    type Item = Result<VerseText, ImportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.xml.next_event() {
+                Ok(Some(event)) => self.process(event),
+                Ok(None) => {
+                    self.finished = true;
+                    self.flush();
+                }
+                Err(e) => {
+                    self.finished = true;
+                    self.flush();
+                    self.error(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::versetext::Note;
+
+    const ZEFANIA: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<XMLBIBLE xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" biblename="King James Version">
+  <INFORMATION><title>King James Version</title><language>ENG</language></INFORMATION>
+  <BIBLEBOOK bnumber="19" bname="Psalms" bsname="Ps">
+    <CHAPTER cnumber="23">
+      <CAPTION vref="1">A Psalm of David.</CAPTION>
+      <VERS vnumber="1">The <STYLE fs="divineName">LORD</STYLE> is my shepherd; I shall not <gr str="2637">want</gr>.</VERS>
+      <VERS vnumber="2">He maketh me to lie down<NOTE type="x-studynote">Heb. pastures of tender grass</NOTE> in green pastures:<XREF fscope="Ezek 34:14"/></VERS>
+    </CHAPTER>
+  </BIBLEBOOK>
+  <BIBLEBOOK bnumber="70" bname="Tobit">
+    <CHAPTER cnumber="1"><VERS vnumber="1">Not in this versification</VERS></CHAPTER>
+  </BIBLEBOOK>
+  <BIBLEBOOK bnumber="1" bname="Genesis">
+    <CHAPTER cnumber="1">
+      <VERS vnumber="32">Too far</VERS>
+      <VERS vnumber="31">And God saw every thing that he had made, and, behold, it was very good.</VERS>
+    </CHAPTER>
+    <CHAPTER cnumber="51"><VERS vnumber="1">Skipped</VERS></CHAPTER>
+  </BIBLEBOOK>
+</XMLBIBLE>"#;
+
+    #[test]
+    fn test_verses() {
+        let results: Vec<_> = ZefaniaReader::new(ZEFANIA.as_bytes()).collect();
+        let verse = results[0].as_ref().unwrap();
+        assert_eq!(verse.verse, BibleVerse::new(BibleBook::Psalms, 23, 1));
+        assert_eq!(verse.text, "The LORD is my shepherd; I shall not want.");
+        let markup = verse.markup.as_ref().unwrap();
+        assert_eq!(&verse.text[markup.spans[0].range.clone()], "LORD");
+        assert_eq!(
+            markup.spans[0].style,
+            SpanStyle::Character("nd".to_string())
+        );
+        assert_eq!(&verse.text[markup.words[0].range.clone()], "want");
+        assert_eq!(markup.words[0].strongs, vec![StrongsNumber::hebrew(2637)]);
+        let verse = results[1].as_ref().unwrap();
+        assert_eq!(verse.text, "He maketh me to lie down in green pastures:");
+        let markup = verse.markup.as_ref().unwrap();
+        assert_eq!(
+            markup.notes,
+            vec![
+                Note {
+                    kind: NoteKind::Footnote,
+                    offset: 24,
+                    caller: "+".to_string(),
+                    text: "Heb. pastures of tender grass".to_string()
+                },
+                Note {
+                    kind: NoteKind::CrossReference,
+                    offset: verse.text.len(),
+                    caller: "+".to_string(),
+                    text: "Ezek 34:14".to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_strongs() {
+        assert_eq!(
+            parse_strongs("25", Some(BibleBook::John)),
+            vec![StrongsNumber::greek(25)]
+        );
+        assert_eq!(
+            parse_strongs("H7225 430 x", Some(BibleBook::Genesis)),
+            vec![StrongsNumber::hebrew(7225), StrongsNumber::hebrew(430)]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let results: Vec<_> = ZefaniaReader::new(ZEFANIA.as_bytes()).collect();
+        assert!(matches!(
+            &results[2],
+            Err(ImportError::UnknownBook { line: 11, code }) if code == "70"
+        ));
+        assert!(matches!(
+            results[3],
+            Err(ImportError::VerseOutOfRange { line: 16, .. })
+        ));
+        assert_eq!(
+            results[4].as_ref().unwrap().verse,
+            BibleVerse::new(BibleBook::Genesis, 1, 31)
+        );
+        assert!(matches!(
+            results[5],
+            Err(ImportError::ChapterOutOfRange {
+                line: 19,
+                chapter: 51,
+                ..
+            })
+        ));
+        assert_eq!(results.len(), 6);
+    }
+
+    #[test]
+    fn test_keep_markup() {
+        let verses: Vec<VerseText> = ZefaniaReader::new(ZEFANIA.as_bytes())
+            .keep_markup(false)
+            .filter_map(|v| v.ok())
+            .collect();
+        assert_eq!(verses.len(), 3);
+        assert!(verses.iter().all(|v| v.markup.is_none()));
+    }
+}
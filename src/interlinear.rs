@@ -0,0 +1,353 @@
+//! Mod for a word-level model of the text, for interlinear display and word studies
+//!
+//! Each verse is a sequence of [Word]s addressed by [BibleWord]. An [Interlinear]
+//! holds the words of a translation alongside the words of the original language,
+//! with each translation word aligned to the original words it translates.
+use crate::search::tokenize::tokenize;
+use crate::text::BibleText;
+use crate::text::versetext::Markup;
+use crate::{BibleVerse, BibleWord, StrongsNumber};
+use std::collections::BTreeMap;
+
+/// A word of a verse with what is known about it
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Word {
+    /// The word as it is written in the text
+    pub surface: String,
+    /// The dictionary form of the word
+    pub lemma: Option<String>,
+    /// Morphology code eg. `robinson:N-NSM`
+    pub morph: Option<String>,
+    pub strongs: Vec<StrongsNumber>,
+    /// Numbers of the words of the original verse this word translates, starting from 1
+    pub alignment: Vec<u16>,
+}
+
+#[allow(dead_code)]
+impl Word {
+    /// Construct a word with only its surface text
+    pub fn new(surface: impl Into<String>) -> Self {
+        Word {
+            surface: surface.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_lemma(mut self, lemma: impl Into<String>) -> Self {
+        self.lemma = Some(lemma.into());
+        self
+    }
+
+    pub fn with_morph(mut self, morph: impl Into<String>) -> Self {
+        self.morph = Some(morph.into());
+        self
+    }
+
+    pub fn with_strongs(mut self, number: StrongsNumber) -> Self {
+        self.strongs.push(number);
+        self
+    }
+}
+
+/// The words of a single verse
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordVerse {
+    pub verse: BibleVerse,
+    pub words: Vec<Word>,
+}
+
+#[allow(dead_code)]
+impl WordVerse {
+    pub fn new(verse: BibleVerse, words: Vec<Word>) -> Self {
+        WordVerse { verse, words }
+    }
+
+    /// Split the text of a verse into words, tagging them with any Strong's numbers
+    /// and morphology in its markup
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// use bible_data::interlinear::WordVerse;
+    /// let verse = WordVerse::from_text(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.", None);
+    /// assert_eq!(verse.word(2).unwrap().surface, "wept");
+    /// assert_eq!(verse.text(), "Jesus wept");
+    /// ```
+    pub fn from_text(verse: BibleVerse, text: &str, markup: Option<&Markup>) -> Self {
+        let words = tokenize(text)
+            .map(|token| {
+                let mut word = Word::new(&text[token.range.clone()]);
+                let tag = markup.and_then(|markup| {
+                    markup.words.iter().find(|tag| {
+                        tag.range.start <= token.range.start && token.range.end <= tag.range.end
+                    })
+                });
+                if let Some(tag) = tag {
+                    word.strongs = tag.strongs.clone();
+                    word.morph = tag.morph.clone();
+                }
+                word
+            })
+            .collect();
+        WordVerse { verse, words }
+    }
+
+    /// Return a word by its number, starting from 1
+    pub fn word(&self, number: u16) -> Option<&Word> {
+        self.words.get((number as usize).checked_sub(1)?)
+    }
+
+    pub fn word_mut(&mut self, number: u16) -> Option<&mut Word> {
+        self.words.get_mut((number as usize).checked_sub(1)?)
+    }
+
+    /// Iterate over the words with their addresses
+    pub fn iter(&self) -> impl Iterator<Item = (BibleWord, &Word)> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (BibleWord::new(self.verse, i as u16 + 1), word))
+    }
+
+    /// Return the surface text of the words separated by spaces
+    pub fn text(&self) -> String {
+        let words: Vec<&str> = self.words.iter().map(|w| w.surface.as_str()).collect();
+        words.join(" ")
+    }
+}
+
+/// A translation word with the original words it is aligned to
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignedWord<'a> {
+    pub word: BibleWord,
+    pub translation: &'a Word,
+    pub original: Vec<&'a Word>,
+}
+
+/// The words of a translation and of the original language, verse by verse
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleWord, StrongsNumber};
+/// use bible_data::interlinear::{Interlinear, Word, WordVerse};
+/// let verse = BibleVerse::new(BibleBook::John, 11, 35);
+/// let mut interlinear = Interlinear::new();
+/// interlinear.insert_original(WordVerse::new(verse, vec![
+///     Word::new("ἐδάκρυσεν").with_lemma("δακρύω").with_strongs(StrongsNumber::greek(1145)),
+///     Word::new("ὁ").with_strongs(StrongsNumber::greek(3588)),
+///     Word::new("Ἰησοῦς").with_lemma("Ἰησοῦς").with_strongs(StrongsNumber::greek(2424)),
+/// ]));
+/// interlinear.insert_translation(WordVerse::new(verse, vec![
+///     Word::new("Jesus").with_strongs(StrongsNumber::greek(2424)),
+///     Word::new("wept").with_strongs(StrongsNumber::greek(1145)),
+/// ]));
+/// interlinear.align_by_strongs();
+/// let wept = BibleWord::parse("Jn 11:35 word 2").unwrap();
+/// assert_eq!(interlinear.aligned(&wept)[0].lemma.as_deref(), Some("δακρύω"));
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Interlinear {
+    translation: BTreeMap<BibleVerse, WordVerse>,
+    original: BTreeMap<BibleVerse, WordVerse>,
+}
+
+#[allow(dead_code)]
+impl Interlinear {
+    pub fn new() -> Self {
+        Interlinear::default()
+    }
+
+    /// Construct an Interlinear with the words of a translation
+    /// Words tagged in its markup keep their Strong's numbers and morphology.
+    pub fn from_translation(text: &BibleText) -> Self {
+        let mut interlinear = Interlinear::new();
+        for (verse, verse_text) in text.iter() {
+            interlinear.insert_translation(WordVerse::from_text(
+                verse,
+                verse_text,
+                text.markup(&verse),
+            ));
+        }
+        interlinear
+    }
+
+    /// Add or replace the translation words of a verse
+    pub fn insert_translation(&mut self, verse: WordVerse) {
+        self.translation.insert(verse.verse, verse);
+    }
+
+    /// Add or replace the original language words of a verse
+    pub fn insert_original(&mut self, verse: WordVerse) {
+        self.original.insert(verse.verse, verse);
+    }
+
+    pub fn translation(&self, verse: &BibleVerse) -> Option<&WordVerse> {
+        self.translation.get(verse)
+    }
+
+    pub fn original(&self, verse: &BibleVerse) -> Option<&WordVerse> {
+        self.original.get(verse)
+    }
+
+    /// Return a word of the translation
+    pub fn word(&self, word: &BibleWord) -> Option<&Word> {
+        self.translation.get(&word.verse)?.word(word.word)
+    }
+
+    pub fn word_mut(&mut self, word: &BibleWord) -> Option<&mut Word> {
+        self.translation.get_mut(&word.verse)?.word_mut(word.word)
+    }
+
+    /// Return a word of the original language
+    pub fn original_word(&self, word: &BibleWord) -> Option<&Word> {
+        self.original.get(&word.verse)?.word(word.word)
+    }
+
+    /// Return the original words a translation word is aligned to
+    pub fn aligned(&self, word: &BibleWord) -> Vec<&Word> {
+        let Some(translation) = self.word(word) else {
+            return Vec::new();
+        };
+        translation
+            .alignment
+            .iter()
+            .filter_map(|&number| self.original_word(&BibleWord::new(word.verse, number)))
+            .collect()
+    }
+
+    /// Return every translation word of a verse with the original words it is aligned to
+    pub fn verse(&self, verse: &BibleVerse) -> Vec<AlignedWord<'_>> {
+        let Some(translation) = self.translation.get(verse) else {
+            return Vec::new();
+        };
+        translation
+            .iter()
+            .map(|(address, word)| AlignedWord {
+                word: address,
+                translation: word,
+                original: self.aligned(&address),
+            })
+            .collect()
+    }
+
+    /// Align each translation word that has no alignment to the original words of
+    /// its verse that share a Strong's number with it
+    pub fn align_by_strongs(&mut self) {
+        for (verse, translation) in self.translation.iter_mut() {
+            let Some(original) = self.original.get(verse) else {
+                continue;
+            };
+            for word in translation.words.iter_mut() {
+                if !word.alignment.is_empty() || word.strongs.is_empty() {
+                    continue;
+                }
+                word.alignment = original
+                    .words
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, o)| o.strongs.iter().any(|n| word.strongs.contains(n)))
+                    .map(|(i, _)| i as u16 + 1)
+                    .collect();
+            }
+        }
+    }
+
+    /// Return every translation word tagged with a Strong's number
+    pub fn occurrences(&self, number: &StrongsNumber) -> Vec<BibleWord> {
+        self.translation
+            .values()
+            .flat_map(|verse| verse.iter())
+            .filter(|(_, word)| word.strongs.contains(number))
+            .map(|(address, _)| address)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BibleBook;
+    use crate::import::osis::OsisReader;
+    use crate::text::Metadata;
+
+    fn john_1_1() -> BibleVerse {
+        BibleVerse::new(BibleBook::John, 1, 1)
+    }
+
+    fn original() -> WordVerse {
+        WordVerse::new(
+            john_1_1(),
+            vec![
+                Word::new("Ἐν").with_strongs(StrongsNumber::greek(1722)),
+                Word::new("ἀρχῇ").with_strongs(StrongsNumber::greek(746)),
+                Word::new("ἦν").with_strongs(StrongsNumber::greek(2258)),
+                Word::new("ὁ").with_strongs(StrongsNumber::greek(3588)),
+                Word::new("λόγος")
+                    .with_lemma("λόγος")
+                    .with_morph("robinson:N-NSM")
+                    .with_strongs(StrongsNumber::greek(3056)),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_from_translation() {
+        let osis = r#"<osis><verse osisID="John.1.1"><w lemma="strong:G1722">In</w> <w lemma="strong:G746">the beginning</w> <w lemma="strong:G2258">was</w> <w lemma="strong:G3588 strong:G3056" morph="robinson:N-NSM">the Word</w>,</verse></osis>"#;
+        let text = BibleText::from_verses(
+            Metadata::default(),
+            OsisReader::new(osis.as_bytes()).map(|v| v.unwrap()),
+        );
+        let mut interlinear = Interlinear::from_translation(&text);
+        let verse = interlinear.translation(&john_1_1()).unwrap();
+        assert_eq!(verse.text(), "In the beginning was the Word");
+        assert_eq!(verse.words.len(), 6);
+        assert_eq!(
+            verse.word(3).unwrap().strongs,
+            vec![StrongsNumber::greek(746)]
+        );
+        assert_eq!(verse.word(0), None);
+
+        interlinear.insert_original(original());
+        interlinear.align_by_strongs();
+        let word = BibleWord::parse("Jn 1:1 word 6").unwrap();
+        assert_eq!(interlinear.word(&word).unwrap().surface, "Word");
+        assert_eq!(interlinear.word(&word).unwrap().alignment, vec![4, 5]);
+        let aligned = interlinear.aligned(&word);
+        assert_eq!(aligned[1].surface, "λόγος");
+        assert_eq!(aligned[1].morph.as_deref(), Some("robinson:N-NSM"));
+
+        let rows = interlinear.verse(&john_1_1());
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[1].word, BibleWord::new(john_1_1(), 2));
+        assert_eq!(rows[1].original[0].surface, "ἀρχῇ");
+        assert_eq!(
+            interlinear.occurrences(&StrongsNumber::greek(746)),
+            vec![BibleWord::new(john_1_1(), 2), BibleWord::new(john_1_1(), 3)]
+        );
+    }
+
+    #[test]
+    fn test_manual_alignment() {
+        let mut interlinear = Interlinear::new();
+        interlinear.insert_original(original());
+        interlinear.insert_translation(WordVerse::from_text(john_1_1(), "In the beginning", None));
+        let beginning = BibleWord::new(john_1_1(), 3);
+        interlinear.word_mut(&beginning).unwrap().alignment = vec![2];
+        interlinear.align_by_strongs();
+        assert_eq!(interlinear.aligned(&beginning)[0].surface, "ἀρχῇ");
+        assert!(
+            interlinear
+                .aligned(&BibleWord::new(john_1_1(), 1))
+                .is_empty()
+        );
+        assert!(
+            interlinear
+                .aligned(&BibleWord::new(john_1_1(), 9))
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Minimal JSON output, so the crate does not need a serialisation dependency
+//!
+//! Used for the JSON written by the server, the concordance and the command-line tool.
+
+use std::fmt::Write;
+
+/// Append a string to `out` as a quoted JSON string
+///
+/// # Example
+/// ```rust
+/// use bible_data::json::write_string;
+/// let mut out = String::from("{\"text\": ");
+/// write_string(&mut out, "He said, \"Follow me.\"");
+/// out.push('}');
+/// assert_eq!(out, r#"{"text": "He said, \"Follow me.\""}"#);
+/// ```
+pub fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> String {
+        let mut out = String::new();
+        write_string(&mut out, value);
+        out
+    }
+
+    #[test]
+    fn test_string() {
+        assert_eq!(string("Jesus wept."), "\"Jesus wept.\"");
+        assert_eq!(string("say \"hi\"\\\n"), "\"say \\\"hi\\\"\\\\\\n\"");
+        assert_eq!(string("\u{1}"), "\"\\u0001\"");
+        assert_eq!(string("λόγος"), "\"λόγος\"");
+    }
+}
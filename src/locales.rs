@@ -0,0 +1,49 @@
+//! Book names and abbreviations in languages other than English
+//!
+//! Each submodule mirrors the [BOOK_NAMES](crate::BOOK_NAMES) and
+//! [BOOK_ABBREVS](crate::BOOK_ABBREVS) arrays in the root of the crate, in the
+//! same canonical order, so the zero-based index of a book is the same in every table.
+//!
+//! Abbreviations never contain spaces so that, as with the English tables, a reference
+//! can be split into book and chapter/verse at the first space after the book.
+pub mod de;
+pub mod es;
+pub mod fr;
+pub mod ko;
+pub mod pt;
+pub mod zh;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn check_unique(names: &[&str; 66], abbrevs: &[&str; 66]) {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for (i, text) in names.iter().chain(abbrevs.iter()).enumerate() {
+            let book = i % 66;
+            if let Some(other) = seen.insert(text, book) {
+                assert_eq!(other, book, "{} is used for more than one book", text);
+            }
+            assert!(!text.is_empty());
+        }
+        for abbrev in abbrevs {
+            assert!(
+                !abbrev.contains(' '),
+                "Abbreviation [{}] contains a space",
+                abbrev
+            );
+        }
+    }
+
+    #[test]
+    fn test_unique() {
+        check_unique(&crate::BOOK_NAMES, &crate::BOOK_ABBREVS);
+        check_unique(&de::BOOK_NAMES, &de::BOOK_ABBREVS);
+        check_unique(&es::BOOK_NAMES, &es::BOOK_ABBREVS);
+        check_unique(&fr::BOOK_NAMES, &fr::BOOK_ABBREVS);
+        check_unique(&ko::BOOK_NAMES, &ko::BOOK_ABBREVS);
+        check_unique(&pt::BOOK_NAMES, &pt::BOOK_ABBREVS);
+        check_unique(&zh::BOOK_NAMES, &zh::BOOK_ABBREVS);
+    }
+}
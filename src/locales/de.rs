@@ -0,0 +1,78 @@
+//! German book names and abbreviations
+//! Names follow the Luther / Loccumer Richtlinien tradition.
+pub static BOOK_NAMES: [&str; 66] = [
+    "1. Mose",
+    "2. Mose",
+    "3. Mose",
+    "4. Mose",
+    "5. Mose",
+    "Josua",
+    "Richter",
+    "Rut",
+    "1. Samuel",
+    "2. Samuel",
+    "1. Könige",
+    "2. Könige",
+    "1. Chronik",
+    "2. Chronik",
+    "Esra",
+    "Nehemia",
+    "Ester",
+    "Hiob",
+    "Psalmen",
+    "Sprüche",
+    "Prediger",
+    "Hoheslied",
+    "Jesaja",
+    "Jeremia",
+    "Klagelieder",
+    "Hesekiel",
+    "Daniel",
+    "Hosea",
+    "Joel",
+    "Amos",
+    "Obadja",
+    "Jona",
+    "Micha",
+    "Nahum",
+    "Habakuk",
+    "Zefanja",
+    "Haggai",
+    "Sacharja",
+    "Maleachi",
+    "Matthäus",
+    "Markus",
+    "Lukas",
+    "Johannes",
+    "Apostelgeschichte",
+    "Römer",
+    "1. Korinther",
+    "2. Korinther",
+    "Galater",
+    "Epheser",
+    "Philipper",
+    "Kolosser",
+    "1. Thessalonicher",
+    "2. Thessalonicher",
+    "1. Timotheus",
+    "2. Timotheus",
+    "Titus",
+    "Philemon",
+    "Hebräer",
+    "Jakobus",
+    "1. Petrus",
+    "2. Petrus",
+    "1. Johannes",
+    "2. Johannes",
+    "3. Johannes",
+    "Judas",
+    "Offenbarung",
+];
+pub static BOOK_ABBREVS: [&str; 66] = [
+    "1Mo", "2Mo", "3Mo", "4Mo", "5Mo", "Jos", "Ri", "Rut", "1Sam", "2Sam", "1Kön", "2Kön", "1Chr",
+    "2Chr", "Esr", "Neh", "Est", "Hi", "Ps", "Spr", "Pred", "Hld", "Jes", "Jer", "Klgl", "Hes",
+    "Dan", "Hos", "Joel", "Am", "Obd", "Jona", "Mi", "Nah", "Hab", "Zef", "Hag", "Sach", "Mal",
+    "Mt", "Mk", "Lk", "Joh", "Apg", "Röm", "1Kor", "2Kor", "Gal", "Eph", "Phil", "Kol", "1Thess",
+    "2Thess", "1Tim", "2Tim", "Tit", "Phlm", "Hebr", "Jak", "1Petr", "2Petr", "1Joh", "2Joh",
+    "3Joh", "Jud", "Offb",
+];
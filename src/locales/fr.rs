@@ -0,0 +1,77 @@
+//! French book names and abbreviations
+//! Names follow the Louis Segond tradition.
+pub static BOOK_NAMES: [&str; 66] = [
+    "Genèse",
+    "Exode",
+    "Lévitique",
+    "Nombres",
+    "Deutéronome",
+    "Josué",
+    "Juges",
+    "Ruth",
+    "1 Samuel",
+    "2 Samuel",
+    "1 Rois",
+    "2 Rois",
+    "1 Chroniques",
+    "2 Chroniques",
+    "Esdras",
+    "Néhémie",
+    "Esther",
+    "Job",
+    "Psaumes",
+    "Proverbes",
+    "Ecclésiaste",
+    "Cantique des Cantiques",
+    "Ésaïe",
+    "Jérémie",
+    "Lamentations",
+    "Ézéchiel",
+    "Daniel",
+    "Osée",
+    "Joël",
+    "Amos",
+    "Abdias",
+    "Jonas",
+    "Michée",
+    "Nahum",
+    "Habacuc",
+    "Sophonie",
+    "Aggée",
+    "Zacharie",
+    "Malachie",
+    "Matthieu",
+    "Marc",
+    "Luc",
+    "Jean",
+    "Actes",
+    "Romains",
+    "1 Corinthiens",
+    "2 Corinthiens",
+    "Galates",
+    "Éphésiens",
+    "Philippiens",
+    "Colossiens",
+    "1 Thessaloniciens",
+    "2 Thessaloniciens",
+    "1 Timothée",
+    "2 Timothée",
+    "Tite",
+    "Philémon",
+    "Hébreux",
+    "Jacques",
+    "1 Pierre",
+    "2 Pierre",
+    "1 Jean",
+    "2 Jean",
+    "3 Jean",
+    "Jude",
+    "Apocalypse",
+];
+pub static BOOK_ABBREVS: [&str; 66] = [
+    "Gn", "Ex", "Lv", "Nb", "Dt", "Jos", "Jg", "Rt", "1S", "2S", "1R", "2R", "1Ch", "2Ch", "Esd",
+    "Ne", "Est", "Jb", "Ps", "Pr", "Ec", "Ct", "Es", "Jr", "Lm", "Ez", "Dn", "Os", "Jl", "Am",
+    "Ab", "Jon", "Mi", "Na", "Ha", "So", "Ag", "Za", "Ml", "Mt", "Mc", "Lc", "Jn", "Ac", "Rm",
+    "1Co", "2Co", "Ga", "Ep", "Ph", "Col", "1Th", "2Th", "1Tm", "2Tm", "Tt", "Phm", "He", "Jc",
+    "1P", "2P", "1Jn", "2Jn", "3Jn", "Jude", "Ap",
+];
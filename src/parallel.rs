@@ -0,0 +1,193 @@
+//! Mod for comparing a passage across several translations side by side
+use crate::text::{BibleText, Metadata, index_range, verse_at};
+use crate::{BibleVerse, BibleVerseRange};
+
+/// A verse of a passage in each translation
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParallelRow<'a> {
+    pub verse: BibleVerse,
+    /// The text in each translation, in the order the translations were given
+    /// None if the translation does not include the verse.
+    pub texts: Vec<Option<&'a str>>,
+}
+
+#[allow(dead_code)]
+impl ParallelRow<'_> {
+    /// Return if every translation includes this verse
+    pub fn is_complete(&self) -> bool {
+        self.texts.iter().all(Option::is_some)
+    }
+
+    /// Return if no translation includes this verse
+    pub fn is_empty(&self) -> bool {
+        self.texts.iter().all(Option::is_none)
+    }
+
+    /// Return the positions of the translations that do not include this verse
+    pub fn gaps(&self) -> Vec<usize> {
+        self.texts
+            .iter()
+            .enumerate()
+            .filter(|(_, text)| text.is_none())
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// A verse that one of the translations does not include
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    pub verse: BibleVerse,
+    /// Position of the translation in the view
+    pub translation: usize,
+}
+
+/// A passage aligned verse by verse across several translations
+///
+/// There is a row for every verse of the passage in the versification of this
+/// crate, so a verse that some translations omit, such as Mt 17:21, has a row
+/// with a gap for each of them.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+/// use bible_data::parallel::ParallelView;
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let kjv = BibleText::from_verses(
+///     Metadata::new("King James Version", "KJV", "en"),
+///     [
+///         VerseText::new(BibleVerse::new(BibleBook::Matthew, 17, 20), "And Jesus said unto them,"),
+///         VerseText::new(BibleVerse::new(BibleBook::Matthew, 17, 21), "Howbeit this kind goeth not out"),
+///     ],
+/// );
+/// let web = BibleText::from_verses(
+///     Metadata::new("World English Bible", "WEB", "en"),
+///     [VerseText::new(BibleVerse::new(BibleBook::Matthew, 17, 20), "He said to them,")],
+/// );
+/// let passage = BibleVerseRange::parse("Mt 17:20-21").unwrap();
+/// let view = ParallelView::new(&[&kjv, &web], &passage);
+/// assert_eq!(view.rows.len(), 2);
+/// assert_eq!(view.rows[1].texts, vec![Some("Howbeit this kind goeth not out"), None]);
+/// assert_eq!(view.gaps()[0].verse, BibleVerse::new(BibleBook::Matthew, 17, 21));
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParallelView<'a> {
+    /// The metadata of each translation, in the order they were given
+    pub translations: Vec<&'a Metadata>,
+    pub rows: Vec<ParallelRow<'a>>,
+}
+
+#[allow(dead_code)]
+impl<'a> ParallelView<'a> {
+    /// Align a passage across the given translations
+    /// A range past the end of a chapter or book stops at the end of it.
+    pub fn new(texts: &[&'a BibleText], range: &BibleVerseRange) -> Self {
+        let rows = index_range(range.book, *range.range.start(), *range.range.end())
+            .filter_map(verse_at)
+            .map(|verse| ParallelRow {
+                verse,
+                texts: texts.iter().map(|text| text.get(&verse)).collect(),
+            })
+            .collect();
+        ParallelView {
+            translations: texts.iter().map(|text| text.metadata()).collect(),
+            rows,
+        }
+    }
+
+    /// Return every verse that a translation does not include, in canonical order
+    pub fn gaps(&self) -> Vec<Gap> {
+        self.rows
+            .iter()
+            .flat_map(|row| {
+                row.gaps().into_iter().map(|translation| Gap {
+                    verse: row.verse,
+                    translation,
+                })
+            })
+            .collect()
+    }
+
+    /// Return if every translation includes every verse of the passage
+    pub fn is_complete(&self) -> bool {
+        self.rows.iter().all(ParallelRow::is_complete)
+    }
+
+    /// Return the rows of verses that at least one translation includes
+    pub fn present_rows(&self) -> impl Iterator<Item = &ParallelRow<'a>> + '_ {
+        self.rows.iter().filter(|row| !row.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BibleBook;
+    use crate::text::VerseText;
+
+    fn acts(abbrev: &str, verses: &[(u8, &str)]) -> BibleText {
+        BibleText::from_verses(
+            Metadata::new(abbrev, abbrev, "en"),
+            verses.iter().map(|(verse, text)| {
+                VerseText::new(BibleVerse::new(BibleBook::Acts, 8, *verse), *text)
+            }),
+        )
+    }
+
+    #[test]
+    fn test_view() {
+        let kjv = acts(
+            "KJV",
+            &[
+                (36, "See, here is water;"),
+                (37, "And Philip said, If thou believest"),
+                (38, "And he commanded the chariot to stand still:"),
+            ],
+        );
+        let esv = acts(
+            "ESV",
+            &[
+                (36, "See, here is water!"),
+                (38, "And he commanded the chariot to stop,"),
+            ],
+        );
+        let passage = BibleVerseRange::parse("Ac 8:36-39").unwrap();
+        let view = ParallelView::new(&[&kjv, &esv], &passage);
+        assert_eq!(view.translations[1].abbrev, "ESV");
+        assert_eq!(view.rows.len(), 4);
+        assert!(view.rows[0].is_complete());
+        assert_eq!(view.rows[1].gaps(), vec![1]);
+        assert!(view.rows[3].is_empty());
+        assert_eq!(view.present_rows().count(), 3);
+        assert!(!view.is_complete());
+        assert_eq!(
+            view.gaps(),
+            vec![
+                Gap {
+                    verse: BibleVerse::new(BibleBook::Acts, 8, 37),
+                    translation: 1
+                },
+                Gap {
+                    verse: BibleVerse::new(BibleBook::Acts, 8, 39),
+                    translation: 0
+                },
+                Gap {
+                    verse: BibleVerse::new(BibleBook::Acts, 8, 39),
+                    translation: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clamped() {
+        let kjv = acts("KJV", &[(40, "But Philip was found at Azotus:")]);
+        let passage = BibleVerseRange::parse("Ac 8:40-99").unwrap();
+        let view = ParallelView::new(&[&kjv], &passage);
+        assert_eq!(view.rows.len(), 1);
+        assert!(view.is_complete());
+    }
+}
@@ -0,0 +1,299 @@
+//! Mod for rendering passages of a [BibleText] as formatted text or LaTeX
+//!
+//! A [Passage] turns the text and markup of the verses of a range into a sequence of
+//! [Event]s. Each renderer implements [PassageRenderer] by writing those events out
+//! in its own format.
+//!
+//! # Example
+//! ```rust
+//! use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+//! use bible_data::render::{PassageRenderer, PlainTextRenderer};
+//! use bible_data::text::{BibleText, Metadata, VerseText};
+//! let text = BibleText::from_verses(
+//!     Metadata::default(),
+//!     [
+//!         VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept."),
+//!         VerseText::new(BibleVerse::new(BibleBook::John, 11, 36), "Then said the Jews, Behold how he loved him!"),
+//!     ],
+//! );
+//! let range = BibleVerseRange::parse("Jn 11:35-36").unwrap();
+//! assert_eq!(
+//!     PlainTextRenderer::default().render_range(&text, &range),
+//!     "Jn 11:35-36\n\n35 Jesus wept. 36 Then said the Jews, Behold how he loved him!\n"
+//! );
+//! ```
+use crate::text::BibleText;
+use crate::text::versetext::{BreakKind, Markup, Note, SpanStyle};
+use crate::{BibleVerse, BibleVerseRange};
+
+pub mod ansi;
+pub mod html;
+pub mod latex;
+pub mod markdown;
+pub mod plain;
+
+pub use ansi::AnsiRenderer;
+pub use html::HtmlRenderer;
+pub use latex::{LatexRenderer, LatexTemplate};
+pub use markdown::MarkdownRenderer;
+pub use plain::PlainTextRenderer;
+
+/// What to include when rendering a passage
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Start with the reference of the passage
+    pub heading: bool,
+    pub verse_numbers: bool,
+    /// Show the chapter number at the start of each chapter
+    pub chapter_numbers: bool,
+    /// Mark the words of Jesus
+    pub red_letter: bool,
+    /// Mark footnotes and cross-references and list them after the passage
+    pub notes: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            heading: true,
+            verse_numbers: true,
+            chapter_numbers: true,
+            red_letter: true,
+            notes: true,
+        }
+    }
+}
+
+/// A step in rendering a passage
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// The start of a new paragraph, line of poetry or blank line
+    Break(BreakKind),
+    /// The start of a chapter, before the first verse of it
+    Chapter(u8),
+    /// The start of a verse
+    Verse(BibleVerse),
+    /// Text of a verse with the styles that apply to it
+    Text {
+        text: &'a str,
+        words_of_jesus: bool,
+        /// Names of any other character styles eg. `nd`, `add`
+        styles: Vec<&'a str>,
+    },
+    /// The point a note is attached to, numbered from 1 in the order of the passage
+    Note { number: usize, note: &'a Note },
+}
+
+static NO_MARKUP: Markup = Markup {
+    spans: Vec::new(),
+    notes: Vec::new(),
+    breaks: Vec::new(),
+    words: Vec::new(),
+};
+
+/// The verses of a range of a text, ready to be rendered
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passage<'a> {
+    pub range: BibleVerseRange,
+    pub verses: Vec<(BibleVerse, &'a str, Option<&'a Markup>)>,
+}
+
+#[allow(dead_code)]
+impl<'a> Passage<'a> {
+    /// Collect the verses of a range that are in a text
+    pub fn new(text: &'a BibleText, range: &BibleVerseRange) -> Self {
+        Passage {
+            range: range.clone(),
+            verses: text
+                .passage(range)
+                .map(|(verse, verse_text)| (verse, verse_text, text.markup(&verse)))
+                .collect(),
+        }
+    }
+
+    /// Return the heading for the passage, from the `Display` of its range
+    pub fn heading(&self) -> String {
+        self.range.to_string()
+    }
+
+    /// Return the notes of the passage in order
+    pub fn notes(&self) -> Vec<(BibleVerse, &'a Note)> {
+        self.verses
+            .iter()
+            .flat_map(|(verse, _, markup)| {
+                let notes = markup.map(|m| m.notes.as_slice()).unwrap_or_default();
+                notes.iter().map(|note| (*verse, note))
+            })
+            .collect()
+    }
+
+    /// Return the events that make up the passage
+    ///
+    /// Breaks at the start of a verse come before it, followed by the chapter
+    /// if the verse is the first of one.
+    pub fn events(&self) -> Vec<Event<'a>> {
+        let mut events = Vec::new();
+        let mut note_number = 0;
+        for (verse, text, markup) in self.verses.iter() {
+            let markup = markup.unwrap_or(&NO_MARKUP);
+            let mut points = vec![0, text.len()];
+            for span in markup.spans.iter() {
+                points.extend([span.range.start, span.range.end]);
+            }
+            points.extend(markup.notes.iter().map(|n| n.offset));
+            points.extend(markup.breaks.iter().map(|b| b.offset));
+            points.retain(|&p| p <= text.len() && text.is_char_boundary(p));
+            points.sort_unstable();
+            points.dedup();
+            for (i, &point) in points.iter().enumerate() {
+                // Notes attach to the text before them
+                if point > 0 {
+                    for note in markup.notes.iter().filter(|n| n.offset == point) {
+                        note_number += 1;
+                        events.push(Event::Note {
+                            number: note_number,
+                            note,
+                        });
+                    }
+                }
+                for item in markup.breaks.iter().filter(|b| b.offset == point) {
+                    events.push(Event::Break(item.kind));
+                }
+                if point == 0 {
+                    if verse.verse == 1 {
+                        events.push(Event::Chapter(verse.chapter));
+                    }
+                    events.push(Event::Verse(*verse));
+                    for note in markup.notes.iter().filter(|n| n.offset == 0) {
+                        note_number += 1;
+                        events.push(Event::Note {
+                            number: note_number,
+                            note,
+                        });
+                    }
+                }
+                let Some(&next) = points.get(i + 1) else {
+                    break;
+                };
+                let covering = markup
+                    .spans
+                    .iter()
+                    .filter(|s| s.range.start <= point && next <= s.range.end);
+                let mut words_of_jesus = false;
+                let mut styles = Vec::new();
+                for span in covering {
+                    match &span.style {
+                        SpanStyle::WordsOfJesus => words_of_jesus = true,
+                        SpanStyle::Character(name) => styles.push(name.as_str()),
+                    }
+                }
+                events.push(Event::Text {
+                    text: &text[point..next],
+                    words_of_jesus,
+                    styles,
+                });
+            }
+        }
+        events
+    }
+}
+
+/// Renders a passage in some format
+pub trait PassageRenderer {
+    fn render(&self, passage: &Passage) -> String;
+
+    /// Render the verses of a range of a text
+    fn render_range(&self, text: &BibleText, range: &BibleVerseRange) -> String {
+        self.render(&Passage::new(text, range))
+    }
+}
+
+/// The letter used to mark a note that has no caller of its own
+/// Notes are lettered a to z, then aa, ab and so on.
+pub(crate) fn note_letter(number: usize) -> String {
+    let mut number = number;
+    let mut letters = Vec::new();
+    while number > 0 {
+        number -= 1;
+        letters.push((b'a' + (number % 26) as u8) as char);
+        number /= 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// The text used to mark a note in the passage
+pub(crate) fn note_caller(number: usize, note: &Note) -> String {
+    match note.caller.as_str() {
+        "+" | "" => note_letter(number),
+        "-" => String::new(),
+        caller => caller.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::BibleBook;
+    use crate::import::usfm::UsfmReader;
+    use crate::text::Metadata;
+
+    /// A passage exercising each kind of markup, used by the tests of the renderers
+    pub(crate) fn sample() -> BibleText {
+        let usfm = r#"\id JHN
+\c 14
+\p
+\v 31 But that the world may know that I love the Father; \wj Arise, let us go hence.\wj*
+\c 15
+\p
+\v 1 \wj I am the true vine, and my Father is the husbandman.\wj*\f + \ft Or vinedresser\f*
+\q1
+\v 2 Every branch in me that beareth not fruit
+\q2 he taketh away: & <more>
+"#;
+        BibleText::from_verses(
+            Metadata::default(),
+            UsfmReader::new(usfm.as_bytes()).map(|v| v.unwrap()),
+        )
+    }
+
+    pub(crate) fn range() -> BibleVerseRange {
+        BibleVerseRange::new(BibleBook::John, 14, 31, 15, 2)
+    }
+
+    #[test]
+    fn test_events() {
+        let text = sample();
+        let passage = Passage::new(&text, &range());
+        assert_eq!(passage.heading(), "Jn 14:31-15:2");
+        let events = passage.events();
+        assert_eq!(events[0], Event::Break(BreakKind::Paragraph));
+        assert_eq!(
+            events[1],
+            Event::Verse(BibleVerse::new(BibleBook::John, 14, 31))
+        );
+        assert_eq!(
+            events[3],
+            Event::Text {
+                text: "Arise, let us go hence.",
+                words_of_jesus: true,
+                styles: vec![]
+            }
+        );
+        assert_eq!(events[4], Event::Break(BreakKind::Paragraph));
+        assert_eq!(events[5], Event::Chapter(15));
+        assert!(matches!(events[8], Event::Note { number: 1, .. }));
+        assert_eq!(events[9], Event::Break(BreakKind::Poetry(1)));
+        assert_eq!(passage.notes().len(), 1);
+    }
+
+    #[test]
+    fn test_note_letter() {
+        assert_eq!(note_letter(1), "a");
+        assert_eq!(note_letter(26), "z");
+        assert_eq!(note_letter(27), "aa");
+        assert_eq!(note_letter(28), "ab");
+    }
+}
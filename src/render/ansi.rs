@@ -0,0 +1,74 @@
+use super::plain::{AnsiCodes, render_text};
+use super::{Passage, PassageRenderer, RenderOptions};
+
+const CODES: AnsiCodes = AnsiCodes {
+    heading: "\x1b[1m",
+    number: "\x1b[2m",
+    chapter: "\x1b[1m",
+    words_of_jesus: "\x1b[31m",
+    italic: "\x1b[3m",
+    reset: "\x1b[0m",
+};
+
+/// Renders a passage for a terminal, laid out as by the
+/// [PlainTextRenderer](super::PlainTextRenderer) with ANSI escape codes for style
+///
+/// The heading and chapter numbers are bold, verse numbers and note markers dim,
+/// the words of Jesus red and added words italic.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+/// use bible_data::render::{AnsiRenderer, PassageRenderer, RenderOptions};
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let text = BibleText::from_verses(
+///     Metadata::default(),
+///     [VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.")],
+/// );
+/// let options = RenderOptions { heading: false, ..Default::default() };
+/// let range = BibleVerseRange::parse("Jn 11:35").unwrap();
+/// assert_eq!(
+///     AnsiRenderer::new(options).render_range(&text, &range),
+///     "\x1b[2m35\x1b[0m Jesus wept.\n"
+/// );
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct AnsiRenderer {
+    pub options: RenderOptions,
+}
+
+#[allow(dead_code)]
+impl AnsiRenderer {
+    pub fn new(options: RenderOptions) -> Self {
+        AnsiRenderer { options }
+    }
+}
+
+impl PassageRenderer for AnsiRenderer {
+    fn render(&self, passage: &Passage) -> String {
+        render_text(passage, &self.options, Some(&CODES))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{range, sample};
+
+    #[test]
+    fn test_render() {
+        let text = sample();
+        let rendered = AnsiRenderer::default().render_range(&text, &range());
+        assert!(rendered.starts_with("\x1b[1mJn 14:31-15:2\x1b[0m\n\n\x1b[2m31\x1b[0m But"));
+        assert!(rendered.contains("\x1b[31mArise, let us go hence.\x1b[0m"));
+        assert!(rendered.contains("\x1b[1m15:1\x1b[0m \x1b[31mI am"));
+        assert!(rendered.ends_with("\x1b[2m[a]\x1b[0m Or vinedresser\n"));
+        let plain = RenderOptions {
+            red_letter: false,
+            ..Default::default()
+        };
+        let rendered = AnsiRenderer::new(plain).render_range(&text, &range());
+        assert!(!rendered.contains("\x1b[31m"));
+    }
+}
@@ -0,0 +1,197 @@
+use super::{Event, Passage, PassageRenderer, RenderOptions, note_caller};
+use crate::html::write_escaped;
+use crate::text::versetext::BreakKind;
+
+/// Renders a passage as an HTML fragment
+///
+/// The passage is a `<div class="passage">` with an `<h2>` heading. Paragraphs are
+/// `<p>` and lines of poetry `<p class="q1">`, `<p class="q2">` and so on. Verse
+/// numbers are `<sup class="verse">`, and a chapter starts with
+/// `<span class="chapter">` in place of the number of its first verse. The words of
+/// Jesus are in `<span class="wj">` and other character styles in a span with the
+/// class of their USFM marker. Notes link to a list after the passage.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+/// use bible_data::render::{HtmlRenderer, PassageRenderer};
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let text = BibleText::from_verses(
+///     Metadata::default(),
+///     [VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.")],
+/// );
+/// let range = BibleVerseRange::parse("Jn 11:35").unwrap();
+/// assert_eq!(
+///     HtmlRenderer::default().render_range(&text, &range),
+///     "<div class=\"passage\">\n<h2>Jn 11:35</h2>\n<p><sup class=\"verse\">35</sup>Jesus wept.</p>\n</div>\n"
+/// );
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct HtmlRenderer {
+    pub options: RenderOptions,
+}
+
+#[allow(dead_code)]
+impl HtmlRenderer {
+    pub fn new(options: RenderOptions) -> Self {
+        HtmlRenderer { options }
+    }
+}
+
+/// Tracks the block being written so that blocks are only opened when they have content
+struct Blocks {
+    out: String,
+    class: Option<String>,
+    open: bool,
+}
+
+impl Blocks {
+    fn close(&mut self) {
+        if self.open {
+            self.out.push_str("</p>\n");
+            self.open = false;
+        }
+    }
+
+    fn content(&mut self) -> &mut String {
+        if !self.open {
+            match self.class.take() {
+                Some(class) => self.out.push_str(&format!("<p class=\"{}\">", class)),
+                None => self.out.push_str("<p>"),
+            }
+            self.open = true;
+        }
+        &mut self.out
+    }
+}
+
+impl PassageRenderer for HtmlRenderer {
+    fn render(&self, passage: &Passage) -> String {
+        let options = &self.options;
+        let mut blocks = Blocks {
+            out: String::from("<div class=\"passage\">\n"),
+            class: None,
+            open: false,
+        };
+        if options.heading {
+            blocks.out.push_str("<h2>");
+            write_escaped(&mut blocks.out, &passage.heading());
+            blocks.out.push_str("</h2>\n");
+        }
+        let mut chapter = false;
+        let mut notes = Vec::new();
+        for event in passage.events() {
+            match event {
+                Event::Break(kind) => {
+                    blocks.close();
+                    blocks.class = match kind {
+                        BreakKind::Paragraph => None,
+                        BreakKind::Poetry(level) => Some(format!("q{}", level)),
+                        BreakKind::Blank => {
+                            blocks.out.push_str("<p class=\"b\"></p>\n");
+                            None
+                        }
+                    };
+                }
+                Event::Chapter(number) => {
+                    if options.chapter_numbers {
+                        let out = blocks.content();
+                        out.push_str(&format!("<span class=\"chapter\">{}</span>", number));
+                        chapter = true;
+                    }
+                }
+                Event::Verse(verse) => {
+                    if options.verse_numbers && !std::mem::take(&mut chapter) {
+                        let out = blocks.content();
+                        if !out.ends_with('>') && !out.ends_with(' ') {
+                            out.push(' ');
+                        }
+                        out.push_str(&format!("<sup class=\"verse\">{}</sup>", verse.verse));
+                    }
+                }
+                Event::Text {
+                    text,
+                    words_of_jesus,
+                    styles,
+                } => {
+                    let starting = !blocks.open;
+                    let out = blocks.content();
+                    let text = match starting {
+                        true => text.trim_start(),
+                        false => text,
+                    };
+                    let mut classes = Vec::new();
+                    if words_of_jesus && options.red_letter {
+                        classes.push("wj");
+                    }
+                    classes.extend(styles);
+                    match classes.is_empty() {
+                        true => write_escaped(out, text),
+                        false => {
+                            out.push_str(&format!("<span class=\"{}\">", classes.join(" ")));
+                            write_escaped(out, text);
+                            out.push_str("</span>");
+                        }
+                    }
+                }
+                Event::Note { number, note } => {
+                    if options.notes {
+                        let caller = note_caller(number, note);
+                        let out = blocks.content();
+                        out.push_str(&format!(
+                            "<sup class=\"note\"><a href=\"#note-{}\">",
+                            number
+                        ));
+                        write_escaped(out, &caller);
+                        out.push_str("</a></sup>");
+                        notes.push((number, caller, note));
+                    }
+                }
+            }
+        }
+        blocks.close();
+        let mut out = blocks.out;
+        if !notes.is_empty() {
+            out.push_str("<ol class=\"notes\">\n");
+            for (number, caller, note) in notes {
+                out.push_str(&format!(
+                    "<li id=\"note-{}\"><span class=\"caller\">",
+                    number
+                ));
+                write_escaped(&mut out, &caller);
+                out.push_str("</span> ");
+                write_escaped(&mut out, &note.text);
+                out.push_str("</li>\n");
+            }
+            out.push_str("</ol>\n");
+        }
+        out.push_str("</div>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{range, sample};
+
+    #[test]
+    fn test_render() {
+        let text = sample();
+        assert_eq!(
+            HtmlRenderer::default().render_range(&text, &range()),
+            r##"<div class="passage">
+<h2>Jn 14:31-15:2</h2>
+<p><sup class="verse">31</sup>But that the world may know that I love the Father; <span class="wj">Arise, let us go hence.</span></p>
+<p><span class="chapter">15</span><span class="wj">I am the true vine, and my Father is the husbandman.</span><sup class="note"><a href="#note-1">a</a></sup></p>
+<p class="q1"><sup class="verse">2</sup>Every branch in me that beareth not fruit</p>
+<p class="q2">he taketh away: &amp; &lt;more&gt;</p>
+<ol class="notes">
+<li id="note-1"><span class="caller">a</span> Or vinedresser</li>
+</ol>
+</div>
+"##
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use super::{Event, Passage, PassageRenderer, RenderOptions};
+use crate::BibleVerseRangeList;
+use crate::text::BibleText;
+use crate::text::versetext::BreakKind;
+
+/// Append text with the characters that are special in LaTeX escaped
+fn write_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+}
+
+fn escaped(text: &str) -> String {
+    let mut out = String::new();
+    write_escaped(&mut out, text);
+    out
+}
+
+/// Replace the `{{name}}` placeholder in a template
+fn fill(template: &str, name: &str, value: &str) -> String {
+    template.replace(&format!("{{{{{}}}}}", name), value)
+}
+
+/// The LaTeX produced for each part of a passage
+///
+/// Each template is LaTeX with placeholders in double braces, such as `{{number}}`,
+/// which are replaced when the passage is rendered. Text put into a placeholder has
+/// already been escaped.
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatexTemplate {
+    /// The whole document, with `{{body}}`. Used by [LatexRenderer::document].
+    pub document: String,
+    /// A passage, with `{{heading}}` and `{{body}}`
+    pub passage: String,
+    /// The number at the start of a chapter, with `{{number}}`
+    pub chapter: String,
+    /// The number of a verse, with `{{number}}`
+    pub verse: String,
+    /// The words of Jesus, with `{{text}}`
+    pub words_of_jesus: String,
+    /// Added and italic words, with `{{text}}`
+    pub italic: String,
+    /// The name of God and small caps, with `{{text}}`
+    pub small_caps: String,
+    /// A footnote or cross-reference, with `{{text}}`
+    pub footnote: String,
+    /// The indent of a line of poetry, with `{{level}}`
+    pub poetry_indent: String,
+}
+
+impl Default for LatexTemplate {
+    fn default() -> Self {
+        LatexTemplate::superscript()
+    }
+}
+
+#[allow(dead_code)]
+impl LatexTemplate {
+    /// Verse numbers in superscript and chapters as drop capitals using the `lettrine` package
+    pub fn superscript() -> Self {
+        LatexTemplate {
+            document: "\\documentclass{article}\n\\usepackage{lettrine}\n\\usepackage{xcolor}\n\
+                \\begin{document}\n{{body}}\\end{document}\n"
+                .to_string(),
+            passage: "\\section*{{{heading}}}\n{{body}}\n".to_string(),
+            chapter: "\\lettrine{{{number}}}{}".to_string(),
+            verse: "\\textsuperscript{{{number}}}".to_string(),
+            words_of_jesus: "\\textcolor{red}{{{text}}}".to_string(),
+            italic: "\\textit{{{text}}}".to_string(),
+            small_caps: "\\textsc{{{text}}}".to_string(),
+            footnote: "\\footnote{{{text}}}".to_string(),
+            poetry_indent: "\\hspace*{{{level}}em}".to_string(),
+        }
+    }
+
+    /// Verse numbers in the margin, as used for study editions
+    pub fn margin() -> Self {
+        LatexTemplate {
+            verse: "\\marginpar{\\footnotesize {{number}}}".to_string(),
+            ..LatexTemplate::superscript()
+        }
+    }
+}
+
+/// Renders a passage as LaTeX for typesetting
+///
+/// Paragraphs are separated by blank lines, lines of poetry end with `\\` and notes
+/// become footnotes. How each part is written is set by a [LatexTemplate].
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+/// use bible_data::render::{LatexRenderer, PassageRenderer};
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let text = BibleText::from_verses(
+///     Metadata::default(),
+///     [VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.")],
+/// );
+/// let range = BibleVerseRange::parse("Jn 11:35").unwrap();
+/// assert_eq!(
+///     LatexRenderer::default().render_range(&text, &range),
+///     "\\section*{Jn 11:35}\n\\textsuperscript{35}Jesus wept.\n"
+/// );
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct LatexRenderer {
+    pub options: RenderOptions,
+    pub template: LatexTemplate,
+}
+
+#[allow(dead_code)]
+impl LatexRenderer {
+    pub fn new(options: RenderOptions, template: LatexTemplate) -> Self {
+        LatexRenderer { options, template }
+    }
+
+    /// Render the body of a passage, without its heading
+    fn body(&self, passage: &Passage) -> String {
+        let options = &self.options;
+        let template = &self.template;
+        let mut out = String::new();
+        let mut line_start = true;
+        let mut in_poetry = false;
+        let mut chapter = false;
+        for event in passage.events() {
+            match event {
+                Event::Break(kind) => {
+                    if !out.is_empty() {
+                        out.push_str(match (kind, in_poetry) {
+                            (BreakKind::Poetry(_), true) => " \\\\\n",
+                            _ => "\n\n",
+                        });
+                    }
+                    in_poetry = matches!(kind, BreakKind::Poetry(_));
+                    match kind {
+                        BreakKind::Poetry(level) if level > 1 => out.push_str(&fill(
+                            &template.poetry_indent,
+                            "level",
+                            &(level - 1).to_string(),
+                        )),
+                        BreakKind::Blank => out.push_str("\\bigskip\n\n"),
+                        _ => {}
+                    }
+                    line_start = true;
+                }
+                Event::Chapter(number) => {
+                    if options.chapter_numbers {
+                        out.push_str(&fill(&template.chapter, "number", &number.to_string()));
+                        chapter = true;
+                        line_start = false;
+                    }
+                }
+                Event::Verse(verse) => {
+                    if options.verse_numbers && !std::mem::take(&mut chapter) {
+                        if !line_start && !out.ends_with(' ') {
+                            out.push(' ');
+                        }
+                        out.push_str(&fill(&template.verse, "number", &verse.verse.to_string()));
+                        line_start = false;
+                    }
+                }
+                Event::Text {
+                    text,
+                    words_of_jesus,
+                    styles,
+                } => {
+                    let text = match line_start {
+                        true => text.trim_start(),
+                        false => text,
+                    };
+                    let mut latex = escaped(text);
+                    if styles.iter().any(|s| matches!(*s, "add" | "it" | "tl")) {
+                        latex = fill(&template.italic, "text", &latex);
+                    }
+                    if styles.iter().any(|s| matches!(*s, "nd" | "sc")) {
+                        latex = fill(&template.small_caps, "text", &latex);
+                    }
+                    if words_of_jesus && options.red_letter {
+                        latex = fill(&template.words_of_jesus, "text", &latex);
+                    }
+                    out.push_str(&latex);
+                    line_start = false;
+                }
+                Event::Note { note, .. } => {
+                    if options.notes {
+                        out.push_str(&fill(&template.footnote, "text", &escaped(&note.text)));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn passage(&self, heading: &str, body: &str) -> String {
+        let heading = match self.options.heading {
+            true => escaped(heading),
+            false => String::new(),
+        };
+        let mut passage = self.template.passage.clone();
+        if !self.options.heading {
+            // Drop the line holding the heading
+            passage = passage
+                .lines()
+                .filter(|line| !line.contains("{{heading}}"))
+                .map(|line| format!("{}\n", line))
+                .collect();
+        }
+        fill(&fill(&passage, "heading", &heading), "body", body)
+    }
+
+    /// Render each range of a list one after the other under a single heading
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse, BibleVerseRangeList};
+    /// use bible_data::render::LatexRenderer;
+    /// use bible_data::text::{BibleText, Metadata, VerseText};
+    /// let text = BibleText::from_verses(
+    ///     Metadata::default(),
+    ///     [
+    ///         VerseText::new(BibleVerse::new(BibleBook::Romans, 8, 28), "And we know"),
+    ///         VerseText::new(BibleVerse::new(BibleBook::Romans, 8, 31), "What shall we then say"),
+    ///     ],
+    /// );
+    /// let list = BibleVerseRangeList::parse("Ro 8:28, 31-39").unwrap();
+    /// assert_eq!(
+    ///     LatexRenderer::default().render_list(&text, &list),
+    ///     "\\section*{Ro 8:28, 31-39}\n\\textsuperscript{28}And we know\n\n\\textsuperscript{31}What shall we then say\n"
+    /// );
+    /// ```
+    pub fn render_list(&self, text: &BibleText, list: &BibleVerseRangeList) -> String {
+        let bodies: Vec<String> = list
+            .iter()
+            .map(|range| self.body(&Passage::new(text, &range)))
+            .collect();
+        self.passage(&list.to_string(), &bodies.join("\n\n"))
+    }
+
+    /// Wrap rendered passages in the document template
+    pub fn document(&self, body: &str) -> String {
+        fill(&self.template.document, "body", body)
+    }
+}
+
+impl PassageRenderer for LatexRenderer {
+    fn render(&self, passage: &Passage) -> String {
+        self.passage(&passage.heading(), &self.body(passage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{range, sample};
+
+    #[test]
+    fn test_render() {
+        let text = sample();
+        assert_eq!(
+            LatexRenderer::default().render_range(&text, &range()),
+            r"\section*{Jn 14:31-15:2}
+\textsuperscript{31}But that the world may know that I love the Father; \textcolor{red}{Arise, let us go hence.}
+
+\lettrine{15}{}\textcolor{red}{I am the true vine, and my Father is the husbandman.}\footnote{Or vinedresser}
+
+\textsuperscript{2}Every branch in me that beareth not fruit \\
+\hspace*{1em}he taketh away: \& <more>
+"
+        );
+    }
+
+    #[test]
+    fn test_margin_template() {
+        let text = sample();
+        let options = RenderOptions {
+            heading: false,
+            chapter_numbers: false,
+            ..Default::default()
+        };
+        let renderer = LatexRenderer::new(options, LatexTemplate::margin());
+        let rendered = renderer.render_range(&text, &range());
+        assert!(rendered.starts_with("\\marginpar{\\footnotesize 31}But"));
+        assert!(rendered.contains("\\marginpar{\\footnotesize 1}\\textcolor{red}{I am"));
+        let document = renderer.document(&rendered);
+        assert!(document.starts_with("\\documentclass{article}"));
+        assert!(document.ends_with("\\end{document}\n"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escaped(r"50% of $5 & #1_{x}~^\"),
+            r"50\% of \$5 \& \#1\_\{x\}\textasciitilde{}\textasciicircum{}\textbackslash{}"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use super::{Event, Passage, PassageRenderer, RenderOptions};
+use crate::text::versetext::BreakKind;
+
+/// Append text with the characters that are special in Markdown escaped
+fn write_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '[' | ']' | '<' | '>' | '`' | '#' | '|'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// Append text wrapped in emphasis markers, keeping any surrounding whitespace
+/// outside them as Markdown requires
+fn write_wrapped(out: &mut String, text: &str, open: &str, close: &str) {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        out.push_str(text);
+        return;
+    }
+    let start = text.len() - text.trim_start().len();
+    out.push_str(&text[..start]);
+    out.push_str(open);
+    write_escaped(out, trimmed);
+    out.push_str(close);
+    out.push_str(&text[start + trimmed.len()..]);
+}
+
+/// Renders a passage as Markdown
+///
+/// The heading is a level 2 heading and verse numbers are `<sup>`. A chapter starts
+/// with its number in bold in place of the number of its first verse. Lines of poetry
+/// are joined with hard line breaks and indented with em spaces. Added words are in
+/// italics and the words of Jesus in `<span class="wj">`, as Markdown has no colour.
+/// Notes are footnotes (`[^1]`) as supported by CommonMark extensions.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+/// use bible_data::render::{MarkdownRenderer, PassageRenderer};
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let text = BibleText::from_verses(
+///     Metadata::default(),
+///     [VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.")],
+/// );
+/// let range = BibleVerseRange::parse("Jn 11:35").unwrap();
+/// assert_eq!(
+///     MarkdownRenderer::default().render_range(&text, &range),
+///     "## Jn 11:35\n\n<sup>35</sup> Jesus wept.\n"
+/// );
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownRenderer {
+    pub options: RenderOptions,
+}
+
+#[allow(dead_code)]
+impl MarkdownRenderer {
+    pub fn new(options: RenderOptions) -> Self {
+        MarkdownRenderer { options }
+    }
+}
+
+impl PassageRenderer for MarkdownRenderer {
+    fn render(&self, passage: &Passage) -> String {
+        let options = &self.options;
+        let mut out = String::new();
+        if options.heading {
+            out.push_str("## ");
+            write_escaped(&mut out, &passage.heading());
+            out.push_str("\n\n");
+        }
+        let body_start = out.len();
+        let mut line_start = true;
+        let mut in_poetry = false;
+        let mut chapter = false;
+        let mut notes = Vec::new();
+        for event in passage.events() {
+            match event {
+                Event::Break(kind) => {
+                    if out.len() > body_start {
+                        out.push_str(match (kind, in_poetry) {
+                            (BreakKind::Poetry(_), true) => "\\\n",
+                            _ => "\n\n",
+                        });
+                    }
+                    in_poetry = matches!(kind, BreakKind::Poetry(_));
+                    if let BreakKind::Poetry(level) = kind {
+                        out.push_str(&"&emsp;".repeat(level.saturating_sub(1) as usize));
+                    }
+                    line_start = true;
+                }
+                Event::Chapter(number) => {
+                    if options.chapter_numbers {
+                        if !line_start {
+                            out.push(' ');
+                        }
+                        out.push_str(&format!("**{}** ", number));
+                        chapter = true;
+                        line_start = false;
+                    }
+                }
+                Event::Verse(verse) => {
+                    if options.verse_numbers && !std::mem::take(&mut chapter) {
+                        if !line_start && !out.ends_with(' ') {
+                            out.push(' ');
+                        }
+                        out.push_str(&format!("<sup>{}</sup> ", verse.verse));
+                        line_start = false;
+                    }
+                }
+                Event::Text {
+                    text,
+                    words_of_jesus,
+                    styles,
+                } => {
+                    let text = match line_start {
+                        true => text.trim_start(),
+                        false => text,
+                    };
+                    let (mut open, mut close) = (String::new(), String::new());
+                    if words_of_jesus && options.red_letter {
+                        open.push_str("<span class=\"wj\">");
+                        close.insert_str(0, "</span>");
+                    }
+                    if styles.iter().any(|s| matches!(*s, "bd" | "bdit")) {
+                        open.push_str("**");
+                        close.insert_str(0, "**");
+                    }
+                    if styles
+                        .iter()
+                        .any(|s| matches!(*s, "add" | "it" | "bdit" | "tl"))
+                    {
+                        open.push('*');
+                        close.insert(0, '*');
+                    }
+                    match open.is_empty() {
+                        true => write_escaped(&mut out, text),
+                        false => write_wrapped(&mut out, text, &open, &close),
+                    }
+                    line_start = false;
+                }
+                Event::Note { number, note } => {
+                    if options.notes {
+                        out.push_str(&format!("[^{}]", number));
+                        notes.push((number, note));
+                    }
+                }
+            }
+        }
+        out.push('\n');
+        if !notes.is_empty() {
+            out.push('\n');
+            for (number, note) in notes {
+                out.push_str(&format!("[^{}]: ", number));
+                write_escaped(&mut out, &note.text);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{range, sample};
+
+    #[test]
+    fn test_render() {
+        let text = sample();
+        assert_eq!(
+            MarkdownRenderer::default().render_range(&text, &range()),
+            r#"## Jn 14:31-15:2
+
+<sup>31</sup> But that the world may know that I love the Father; <span class="wj">Arise, let us go hence.</span>
+
+**15** <span class="wj">I am the true vine, and my Father is the husbandman.</span>[^1]
+
+<sup>2</sup> Every branch in me that beareth not fruit\
+&emsp;he taketh away: & \<more\>
+
+[^1]: Or vinedresser
+"#
+        );
+    }
+
+    #[test]
+    fn test_wrapped() {
+        let mut out = String::new();
+        write_wrapped(&mut out, " the man* ", "*", "*");
+        assert_eq!(out, " *the man\\** ");
+    }
+}
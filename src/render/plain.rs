@@ -0,0 +1,182 @@
+use super::{Event, Passage, PassageRenderer, RenderOptions, note_caller};
+use crate::text::versetext::BreakKind;
+
+/// Escape codes used by the [AnsiRenderer](super::AnsiRenderer)
+pub(crate) struct AnsiCodes {
+    pub(crate) heading: &'static str,
+    pub(crate) number: &'static str,
+    pub(crate) chapter: &'static str,
+    pub(crate) words_of_jesus: &'static str,
+    pub(crate) italic: &'static str,
+    pub(crate) reset: &'static str,
+}
+
+/// Render a passage as lines of text, with escape codes if given
+pub(crate) fn render_text(
+    passage: &Passage,
+    options: &RenderOptions,
+    ansi: Option<&AnsiCodes>,
+) -> String {
+    let style = |code: fn(&AnsiCodes) -> &'static str| ansi.map(code).unwrap_or_default();
+    let reset = style(|a| a.reset);
+    let mut out = String::new();
+    if options.heading {
+        out.push_str(&format!(
+            "{}{}{}\n\n",
+            style(|a| a.heading),
+            passage.heading(),
+            reset
+        ));
+    }
+    let body_start = out.len();
+    let mut line_start = true;
+    let mut chapter = None;
+    let mut notes = Vec::new();
+    for event in passage.events() {
+        match event {
+            Event::Break(kind) => {
+                if out.len() > body_start {
+                    out.push_str(match kind {
+                        BreakKind::Poetry(_) => "\n",
+                        BreakKind::Paragraph | BreakKind::Blank => "\n\n",
+                    });
+                }
+                if let BreakKind::Poetry(level) = kind {
+                    out.push_str(&"  ".repeat(level as usize));
+                }
+                line_start = true;
+            }
+            Event::Chapter(number) => {
+                if options.chapter_numbers {
+                    chapter = Some(number);
+                }
+            }
+            Event::Verse(verse) => {
+                let number = match (chapter.take(), options.verse_numbers) {
+                    (Some(chapter), true) => format!("{}:{}", chapter, verse.verse),
+                    (Some(chapter), false) => chapter.to_string(),
+                    (None, true) => verse.verse.to_string(),
+                    (None, false) => String::new(),
+                };
+                if !line_start && !out.ends_with(' ') {
+                    out.push(' ');
+                }
+                if !number.is_empty() {
+                    let code = match number.contains(':') || !options.verse_numbers {
+                        true => style(|a| a.chapter),
+                        false => style(|a| a.number),
+                    };
+                    out.push_str(&format!("{}{}{} ", code, number, reset));
+                }
+                line_start = false;
+            }
+            Event::Text {
+                text,
+                words_of_jesus,
+                styles,
+            } => {
+                let text = match line_start {
+                    true => text.trim_start(),
+                    false => text,
+                };
+                let mut codes = String::new();
+                if words_of_jesus && options.red_letter {
+                    codes.push_str(style(|a| a.words_of_jesus));
+                }
+                if styles.iter().any(|s| matches!(*s, "add" | "it" | "tl")) {
+                    codes.push_str(style(|a| a.italic));
+                }
+                match codes.is_empty() {
+                    true => out.push_str(text),
+                    false => out.push_str(&format!("{}{}{}", codes, text, reset)),
+                }
+                line_start = false;
+            }
+            Event::Note { number, note } => {
+                if options.notes {
+                    let caller = note_caller(number, note);
+                    out.push_str(&format!("{}[{}]{}", style(|a| a.number), caller, reset));
+                    notes.push((caller, note));
+                }
+            }
+        }
+    }
+    out.push('\n');
+    if !notes.is_empty() {
+        out.push('\n');
+        for (caller, note) in notes {
+            out.push_str(&format!(
+                "{}[{}]{} {}\n",
+                style(|a| a.number),
+                caller,
+                reset,
+                note.text
+            ));
+        }
+    }
+    out
+}
+
+/// Renders a passage as plain text
+///
+/// Verse numbers are written before each verse, with the chapter added at the start
+/// of a chapter eg. `15:1`. Paragraphs are separated by a blank line and lines of
+/// poetry indented by two spaces per level. Notes are marked `[a]` and listed after
+/// the passage.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct PlainTextRenderer {
+    pub options: RenderOptions,
+}
+
+#[allow(dead_code)]
+impl PlainTextRenderer {
+    pub fn new(options: RenderOptions) -> Self {
+        PlainTextRenderer { options }
+    }
+}
+
+impl PassageRenderer for PlainTextRenderer {
+    fn render(&self, passage: &Passage) -> String {
+        render_text(passage, &self.options, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{range, sample};
+
+    #[test]
+    fn test_render() {
+        let text = sample();
+        assert_eq!(
+            PlainTextRenderer::default().render_range(&text, &range()),
+            "Jn 14:31-15:2
+
+31 But that the world may know that I love the Father; Arise, let us go hence.
+
+15:1 I am the true vine, and my Father is the husbandman.[a]
+  2 Every branch in me that beareth not fruit
+    he taketh away: & <more>
+
+[a] Or vinedresser
+"
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let text = sample();
+        let options = RenderOptions {
+            heading: false,
+            verse_numbers: false,
+            notes: false,
+            ..Default::default()
+        };
+        let rendered = PlainTextRenderer::new(options).render_range(&text, &range());
+        assert!(rendered.starts_with("But that the world"));
+        assert!(rendered.contains("\n\n15 I am the true vine"));
+        assert!(!rendered.contains("[a]"));
+    }
+}
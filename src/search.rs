@@ -0,0 +1,32 @@
+//! Mod for full-text search of a [BibleText](crate::text::BibleText)
+//!
+//! Text is split into words which are folded to lower case without diacritics, and
+//! an inverted index of them is built. Queries can combine words, phrases and
+//! prefixes with AND, OR and NOT, and be limited to a part of the Bible.
+//!
+//! # Example
+//! ```rust
+//! use bible_data::{BibleBook, BibleVerse};
+//! use bible_data::search::{Query, Scope, SearchIndex, highlight};
+//! use bible_data::text::{BibleText, Metadata, VerseText};
+//! let text = BibleText::from_verses(
+//!     Metadata::default(),
+//!     [VerseText::new(BibleVerse::new(BibleBook::Psalms, 23, 1), "The LORD is my shepherd; I shall not want.")],
+//! );
+//! let index = SearchIndex::from(&text);
+//! let query = Query::parse("shepherd*").unwrap();
+//! let hits = index.ranked(&query, &Scope::OldTestament);
+//! let verse = text.get(&hits[0].verse).unwrap();
+//! assert_eq!(highlight(verse, &query, "<em>", "</em>"), "The LORD is my <em>shepherd</em>; I shall not want.");
+//! ```
+pub mod highlight;
+pub mod index;
+pub mod query;
+pub mod strongs;
+pub mod tokenize;
+
+pub use crate::structs::errors::QueryError;
+pub use highlight::{highlight, matches, snippet};
+pub use index::{Hit, Scope, SearchIndex};
+pub use query::Query;
+pub use strongs::{StrongsEntry, StrongsIndex};
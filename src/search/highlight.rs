@@ -0,0 +1,172 @@
+use super::query::Query;
+use super::tokenize::{Token, tokenize};
+use std::ops::Range;
+
+/// Collect the terms and phrases that a verse must contain to match, ignoring
+/// anything that is excluded
+fn positive_terms<'a>(query: &'a Query, terms: &mut Vec<&'a Query>) {
+    match query {
+        Query::Term(_) | Query::Prefix(_) | Query::Phrase(_) => terms.push(query),
+        Query::And(queries) | Query::Or(queries) => {
+            for query in queries {
+                positive_terms(query, terms);
+            }
+        }
+        Query::Not(_) => {}
+    }
+}
+
+/// Return the byte ranges of the words in some text that match a query
+/// Phrases are returned as a single range. Overlapping matches are merged.
+///
+/// # Example
+/// ```rust
+/// use bible_data::search::{Query, matches};
+/// let query = Query::parse("\"living water\" | gift").unwrap();
+/// let text = "If thou knewest the gift of God, he would have given thee living water.";
+/// assert_eq!(matches(text, &query), vec![20..24, 58..70]);
+/// ```
+pub fn matches(text: &str, query: &Query) -> Vec<Range<usize>> {
+    let mut terms = Vec::new();
+    positive_terms(query, &mut terms);
+    let tokens: Vec<Token> = tokenize(text).collect();
+    let mut ranges = Vec::new();
+    for term in terms {
+        match term {
+            Query::Phrase(words) => {
+                for window in tokens.windows(words.len()) {
+                    if window.iter().zip(words).all(|(t, w)| t.word == *w) {
+                        ranges.push(window[0].range.start..window[words.len() - 1].range.end);
+                    }
+                }
+            }
+            _ => ranges.extend(
+                tokens
+                    .iter()
+                    .filter(|t| term.matches_word(&t.word))
+                    .map(|t| t.range.clone()),
+            ),
+        }
+    }
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Surround the words in some text that match a query with `open` and `close`
+///
+/// # Example
+/// ```rust
+/// use bible_data::search::{Query, highlight};
+/// let query = Query::parse("wept").unwrap();
+/// assert_eq!(highlight("Jesus wept.", &query, "<b>", "</b>"), "Jesus <b>wept</b>.");
+/// ```
+pub fn highlight(text: &str, query: &Query, open: &str, close: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in matches(text, query) {
+        result.push_str(&text[last..range.start]);
+        result.push_str(open);
+        result.push_str(&text[range.clone()]);
+        result.push_str(close);
+        last = range.end;
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+/// Return a highlighted extract of some text around the first match of a query
+///
+/// Up to `context` words either side of the match are kept and `…` marks where
+/// the text has been cut. If nothing matches the start of the text is returned.
+///
+/// # Example
+/// ```rust
+/// use bible_data::search::{Query, snippet};
+/// let query = Query::parse("light").unwrap();
+/// let text = "And God said, Let there be light: and there was light.";
+/// assert_eq!(snippet(text, &query, 2, "[", "]"), "…there be [light]: and there…");
+/// ```
+pub fn snippet(text: &str, query: &Query, context: usize, open: &str, close: &str) -> String {
+    let tokens: Vec<Token> = tokenize(text).collect();
+    let first = matches(text, query).into_iter().next();
+    let centre = first
+        .as_ref()
+        .and_then(|m| tokens.iter().position(|t| t.range.start >= m.start))
+        .unwrap_or(0);
+    let last = first
+        .as_ref()
+        .and_then(|m| tokens.iter().rposition(|t| t.range.end <= m.end))
+        .unwrap_or(centre)
+        .max(centre);
+    let start = match centre.checked_sub(context) {
+        Some(i) if i > 0 => tokens[i].range.start,
+        _ => 0,
+    };
+    let end = match tokens.get(last + context + 1) {
+        Some(_) => tokens[last + context].range.end,
+        None => text.len(),
+    };
+    let mut result = String::new();
+    if start > 0 {
+        result.push('…');
+    }
+    let extract = &text[start..end];
+    result.push_str(&highlight(extract, query, open, close));
+    if end < text.len() {
+        result.push('…');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let text = "In the beginning was the Word, and the Word was with God";
+        let query = Query::parse("word -god").unwrap();
+        assert_eq!(matches(text, &query), vec![25..29, 39..43]);
+        let query = Query::parse("\"the word\" wor*").unwrap();
+        assert_eq!(matches(text, &query), vec![21..29, 35..43]);
+        assert!(matches(text, &Query::parse("light").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_highlight() {
+        let query = Query::parse("lord").unwrap();
+        assert_eq!(
+            highlight("The LORD is my shepherd", &query, "*", "*"),
+            "The *LORD* is my shepherd"
+        );
+        let query = Query::parse("eglise").unwrap();
+        assert_eq!(highlight("l'Église", &query, "<", ">"), "l'Église");
+        assert_eq!(highlight("une Église", &query, "<", ">"), "une <Église>");
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = "In the beginning God created the heaven and the earth.";
+        let query = Query::parse("\"the heaven\"").unwrap();
+        assert_eq!(
+            snippet(text, &query, 1, "[", "]"),
+            "…created [the heaven] and…"
+        );
+        let query = Query::parse("beginning").unwrap();
+        assert_eq!(
+            snippet(text, &query, 5, "[", "]"),
+            "In the [beginning] God created the heaven and…"
+        );
+        let query = Query::parse("earth").unwrap();
+        assert_eq!(snippet(text, &query, 1, "[", "]"), "…the [earth].");
+        let query = Query::parse("light").unwrap();
+        assert_eq!(snippet(text, &query, 1, "[", "]"), "In the…");
+    }
+}
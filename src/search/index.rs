@@ -0,0 +1,411 @@
+use super::query::Query;
+use super::tokenize::tokenize;
+use crate::text::{BibleText, verse_at, verse_index};
+use crate::{BibleBook, BibleVerse, BibleVerseRange};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The part of the Bible to search
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Scope {
+    #[default]
+    All,
+    OldTestament,
+    NewTestament,
+    Book(BibleBook),
+    Range(BibleVerseRange),
+    /// Any of these scopes
+    Any(Vec<Scope>),
+}
+
+#[allow(dead_code)]
+impl Scope {
+    /// Return if a verse is within this scope
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+    /// use bible_data::search::Scope;
+    /// let verse = BibleVerse::new(BibleBook::John, 3, 16);
+    /// assert!(Scope::NewTestament.contains(&verse));
+    /// assert!(!Scope::Book(BibleBook::Genesis).contains(&verse));
+    /// assert!(Scope::Range(BibleVerseRange::parse("Jn 3:1-21").unwrap()).contains(&verse));
+    /// ```
+    pub fn contains(&self, verse: &BibleVerse) -> bool {
+        match self {
+            Scope::All => true,
+            Scope::OldTestament => verse.book.is_old_testament(),
+            Scope::NewTestament => verse.book.is_new_testament(),
+            Scope::Book(book) => verse.book == *book,
+            Scope::Range(range) => range.contains(verse),
+            Scope::Any(scopes) => scopes.iter().any(|s| s.contains(verse)),
+        }
+    }
+}
+
+/// A verse matching a search
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    pub verse: BibleVerse,
+    /// How well the verse matches. Higher is better.
+    pub score: f64,
+}
+
+/// The occurrences of a word in a verse
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Posting {
+    verse: u32,
+    /// Positions of the word among the words of the verse
+    positions: Vec<u32>,
+}
+
+/// An inverted index of the words in a text, for full-text search
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::search::{Query, Scope, SearchIndex};
+/// let index = SearchIndex::new([
+///     (BibleVerse::new(BibleBook::John, 4, 10), "he would have given thee living water."),
+///     (BibleVerse::new(BibleBook::John, 4, 11), "from whence then hast thou that living water?"),
+///     (BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept."),
+/// ]);
+/// let hits = index.search(&Query::parse("\"living water\" -thou").unwrap(), &Scope::All);
+/// assert_eq!(hits.len(), 1);
+/// assert_eq!(hits[0].verse, BibleVerse::new(BibleBook::John, 4, 10));
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    postings: BTreeMap<String, Vec<Posting>>,
+    /// Number of words in each verse that has been indexed
+    lengths: BTreeMap<u32, u32>,
+}
+
+/// Scores of the verses matching part of a query, keyed by verse index
+type Matches = BTreeMap<u32, f64>;
+
+#[allow(dead_code)]
+impl SearchIndex {
+    /// Construct an index of the given verses
+    /// Verses that are not in the versification of this crate are ignored.
+    pub fn new<'a>(verses: impl IntoIterator<Item = (BibleVerse, &'a str)>) -> Self {
+        let mut index = SearchIndex::default();
+        for (verse, text) in verses {
+            index.add(&verse, text);
+        }
+        index
+    }
+
+    /// Add the text of a verse to the index
+    /// Adding the same verse twice indexes the text of both.
+    pub fn add(&mut self, verse: &BibleVerse, text: &str) {
+        let Some(verse) = verse_index(verse) else {
+            return;
+        };
+        let verse = verse as u32;
+        let start = self.lengths.get(&verse).copied().unwrap_or(0);
+        let mut count = 0;
+        for (position, token) in tokenize(text).enumerate() {
+            let position = start + position as u32;
+            let postings = self.postings.entry(token.word).or_default();
+            // Verses are usually added in order, so the verse is at the end if anywhere
+            match postings.last_mut() {
+                Some(posting) if posting.verse == verse => posting.positions.push(position),
+                Some(posting) if posting.verse < verse => postings.push(Posting {
+                    verse,
+                    positions: vec![position],
+                }),
+                None => postings.push(Posting {
+                    verse,
+                    positions: vec![position],
+                }),
+                Some(_) => match postings.binary_search_by_key(&verse, |p| p.verse) {
+                    Ok(at) => postings[at].positions.push(position),
+                    Err(at) => postings.insert(
+                        at,
+                        Posting {
+                            verse,
+                            positions: vec![position],
+                        },
+                    ),
+                },
+            }
+            count += 1;
+        }
+        self.lengths.insert(verse, start + count);
+    }
+
+    /// Return the number of verses indexed
+    pub fn len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lengths.is_empty()
+    }
+
+    /// Iterate over the distinct words in the index in alphabetical order
+    pub fn words(&self) -> impl Iterator<Item = &str> + '_ {
+        self.postings.keys().map(String::as_str)
+    }
+
+    /// Return the number of verses containing a folded word
+    pub fn document_frequency(&self, word: &str) -> usize {
+        self.postings.get(word).map_or(0, Vec::len)
+    }
+
+    /// Return the verses that match a query, in canonical order
+    pub fn search(&self, query: &Query, scope: &Scope) -> Vec<Hit> {
+        self.evaluate(query)
+            .into_iter()
+            .filter_map(|(index, score)| {
+                let verse = verse_at(index as usize)?;
+                match scope.contains(&verse) {
+                    true => Some(Hit { verse, score }),
+                    false => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Return the verses that match a query, best match first
+    /// Verses with the same score are in canonical order.
+    pub fn ranked(&self, query: &Query, scope: &Scope) -> Vec<Hit> {
+        let mut hits = self.search(query, scope);
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits
+    }
+
+    /// Weight of a word, higher for rarer words
+    fn idf(&self, document_frequency: usize) -> f64 {
+        (1.0 + self.lengths.len() as f64 / document_frequency.max(1) as f64).ln()
+    }
+
+    /// Score for a word occurring `count` times in a verse
+    fn score(&self, verse: u32, count: usize, idf: f64) -> f64 {
+        let length = self.lengths.get(&verse).copied().unwrap_or(1).max(1);
+        count as f64 * idf / (length as f64).sqrt()
+    }
+
+    fn term(&self, postings: &[Posting], matches: &mut Matches) {
+        let idf = self.idf(postings.len());
+        for posting in postings {
+            *matches.entry(posting.verse).or_default() +=
+                self.score(posting.verse, posting.positions.len(), idf);
+        }
+    }
+
+    fn phrase(&self, words: &[String]) -> Matches {
+        let mut matches = Matches::new();
+        let Some(lists) = words
+            .iter()
+            .map(|w| self.postings.get(w))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return matches;
+        };
+        let idf: f64 = lists.iter().map(|l| self.idf(l.len())).sum();
+        for first in lists[0] {
+            let rest: Option<Vec<&Posting>> = lists[1..]
+                .iter()
+                .map(|list| {
+                    let at = list.binary_search_by_key(&first.verse, |p| p.verse).ok()?;
+                    Some(&list[at])
+                })
+                .collect();
+            let Some(rest) = rest else { continue };
+            let count = first
+                .positions
+                .iter()
+                .filter(|&&start| {
+                    rest.iter()
+                        .enumerate()
+                        .all(|(i, p)| p.positions.contains(&(start + i as u32 + 1)))
+                })
+                .count();
+            if count > 0 {
+                matches.insert(first.verse, self.score(first.verse, count, idf));
+            }
+        }
+        matches
+    }
+
+    fn evaluate(&self, query: &Query) -> Matches {
+        match query {
+            Query::Term(word) => {
+                let mut matches = Matches::new();
+                if let Some(postings) = self.postings.get(word) {
+                    self.term(postings, &mut matches);
+                }
+                matches
+            }
+            Query::Prefix(prefix) => {
+                let mut matches = Matches::new();
+                for (_, postings) in self
+                    .postings
+                    .range(prefix.clone()..)
+                    .take_while(|(word, _)| word.starts_with(prefix.as_str()))
+                {
+                    self.term(postings, &mut matches);
+                }
+                matches
+            }
+            Query::Phrase(words) => self.phrase(words),
+            Query::Or(queries) => {
+                let mut matches = Matches::new();
+                for query in queries {
+                    for (verse, score) in self.evaluate(query) {
+                        *matches.entry(verse).or_default() += score;
+                    }
+                }
+                matches
+            }
+            Query::And(queries) => {
+                // Exclusions only filter the verses matched by the rest
+                let (negative, positive): (Vec<&Query>, Vec<&Query>) =
+                    queries.iter().partition(|q| q.is_negative());
+                let mut matches = match positive.split_first() {
+                    Some((first, _)) => self.evaluate(first),
+                    None => self.everything(),
+                };
+                for query in positive.iter().skip(1) {
+                    let other = self.evaluate(query);
+                    matches.retain(|verse, score| match other.get(verse) {
+                        Some(extra) => {
+                            *score += extra;
+                            true
+                        }
+                        None => false,
+                    });
+                }
+                for query in negative {
+                    let other: BTreeSet<u32> = self.evaluate(query).into_keys().collect();
+                    matches.retain(|verse, _| other.contains(verse));
+                }
+                matches
+            }
+            Query::Not(query) => {
+                let excluded = self.evaluate(query);
+                self.lengths
+                    .keys()
+                    .filter(|verse| !excluded.contains_key(verse))
+                    .map(|&verse| (verse, 0.0))
+                    .collect()
+            }
+        }
+    }
+
+    /// Every indexed verse with a score of zero
+    fn everything(&self) -> Matches {
+        self.lengths.keys().map(|&verse| (verse, 0.0)).collect()
+    }
+}
+
+impl From<&BibleText> for SearchIndex {
+    fn from(text: &BibleText) -> Self {
+        SearchIndex::new(text.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SearchIndex {
+        SearchIndex::new([
+            (
+                BibleVerse::new(BibleBook::Genesis, 1, 1),
+                "In the beginning God created the heaven and the earth.",
+            ),
+            (
+                BibleVerse::new(BibleBook::Genesis, 1, 3),
+                "And God said, Let there be light: and there was light.",
+            ),
+            (
+                BibleVerse::new(BibleBook::John, 1, 1),
+                "In the beginning was the Word, and the Word was with God, and the Word was God.",
+            ),
+            (
+                BibleVerse::new(BibleBook::John, 3, 16),
+                "For God so loved the world, that he gave his only begotten Son,",
+            ),
+            (
+                BibleVerse::new(BibleBook::John, 8, 12),
+                "I am the light of the world: he that followeth me shall not walk in darkness,",
+            ),
+        ])
+    }
+
+    fn verses(hits: &[Hit]) -> Vec<String> {
+        hits.iter().map(|h| h.verse.to_string()).collect()
+    }
+
+    fn search(query: &str, scope: &Scope) -> Vec<String> {
+        verses(&index().search(&Query::parse(query).unwrap(), scope))
+    }
+
+    #[test]
+    fn test_search() {
+        let all = Scope::All;
+        assert_eq!(index().len(), 5);
+        assert_eq!(search("GOD", &all).len(), 4);
+        assert_eq!(search("light world", &all), vec!["Jn 8:12"]);
+        assert_eq!(search("\"in the beginning\"", &all).len(), 2);
+        assert_eq!(search("\"beginning in\"", &all).len(), 0);
+        assert_eq!(search("light | loved", &all).len(), 3);
+        assert_eq!(search("god -light", &all).len(), 3);
+        assert_eq!(search("-god", &all), vec!["Jn 8:12"]);
+        assert_eq!(search("wor*", &all).len(), 3);
+        assert_eq!(search("(light OR beginning) NOT created", &all).len(), 3);
+        assert!(search("jerusalem", &all).is_empty());
+    }
+
+    #[test]
+    fn test_scope() {
+        assert_eq!(search("god", &Scope::OldTestament).len(), 2);
+        assert_eq!(search("god", &Scope::NewTestament).len(), 2);
+        assert_eq!(
+            search("beginning", &Scope::Book(BibleBook::John)),
+            vec!["Jn 1:1"]
+        );
+        let range = Scope::Range(BibleVerseRange::parse("Jn 3:1-8:59").unwrap());
+        assert_eq!(search("world", &range).len(), 2);
+        let any = Scope::Any(vec![
+            Scope::Book(BibleBook::Genesis),
+            Scope::Range(BibleVerseRange::parse("Jn 1:1").unwrap()),
+        ]);
+        assert_eq!(search("beginning", &any).len(), 2);
+    }
+
+    #[test]
+    fn test_ranked() {
+        let index = index();
+        let hits = index.ranked(&Query::parse("word | light").unwrap(), &Scope::All);
+        assert_eq!(hits.len(), 3);
+        // Three occurrences of word beats two of light
+        assert_eq!(hits[0].verse.to_string(), "Jn 1:1");
+        assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(index.document_frequency("light"), 2);
+        assert!(index.words().any(|w| w == "begotten"));
+    }
+
+    #[test]
+    fn test_out_of_order() {
+        let mut index = SearchIndex::default();
+        let (first, second) = (
+            BibleVerse::new(BibleBook::Genesis, 1, 3),
+            BibleVerse::new(BibleBook::Genesis, 1, 1),
+        );
+        index.add(&first, "Let there be light");
+        index.add(&second, "In the beginning");
+        index.add(&first, "and there was light.");
+        let all = Scope::All;
+        let hits = |query: &str| verses(&index.search(&Query::parse(query).unwrap(), &all));
+        assert_eq!(hits("there"), vec!["Ge 1:3"]);
+        assert_eq!(hits("the"), vec!["Ge 1:1"]);
+        assert_eq!(hits("\"light and\""), vec!["Ge 1:3"]);
+        assert_eq!(index.document_frequency("light"), 1);
+    }
+}
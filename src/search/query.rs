@@ -0,0 +1,330 @@
+use super::tokenize::{fold, tokenize};
+use crate::structs::errors::QueryError;
+
+/// A search query
+///
+/// Terms are matched against words folded with [fold](super::tokenize::fold), so
+/// queries are not case or accent sensitive.
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// A single word
+    Term(String),
+    /// Any word beginning with this prefix eg. `bless*`
+    Prefix(String),
+    /// Words next to each other in this order eg. `"living water"`
+    Phrase(Vec<String>),
+    /// Verses matching all of these queries
+    And(Vec<Query>),
+    /// Verses matching any of these queries
+    Or(Vec<Query>),
+    /// Verses not matching this query
+    Not(Box<Query>),
+}
+
+/// A piece of query text
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Lexeme {
+    Word(String),
+    Quoted(String),
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+}
+
+fn lex(text: &str) -> Result<Vec<Lexeme>, QueryError> {
+    let mut lexemes = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => lexemes.push(Lexeme::Open),
+            ')' => lexemes.push(Lexeme::Close),
+            '|' => lexemes.push(Lexeme::Or),
+            '-' | '!' => lexemes.push(Lexeme::Not),
+            '"' | '“' | '”' => {
+                let mut phrase = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"' | '“' | '”') => break,
+                        Some(c) => phrase.push(c),
+                        None => {
+                            return Err(QueryError::new(format!("Unclosed quote in {:?}", text)));
+                        }
+                    }
+                }
+                lexemes.push(Lexeme::Quoted(phrase));
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | ')' | '|' | '"' | '“' | '”') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                lexemes.push(match word.as_str() {
+                    "AND" | "&&" | "&" => Lexeme::And,
+                    "OR" | "||" => Lexeme::Or,
+                    "NOT" => Lexeme::Not,
+                    _ => Lexeme::Word(word),
+                });
+            }
+        }
+    }
+    Ok(lexemes)
+}
+
+/// The longest query text accepted, in bytes
+const MAX_QUERY_LEN: usize = 1024;
+
+/// The deepest nesting of brackets and `NOT`s accepted, so that parsing and searching
+/// cannot overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// Recursive descent parser over the lexemes of a query
+struct Parser<'a> {
+    lexemes: &'a [Lexeme],
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Lexeme> {
+        self.lexemes.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Lexeme> {
+        let lexeme = self.lexemes.get(self.position);
+        self.position += 1;
+        lexeme
+    }
+
+    /// or := and (OR and)*
+    fn or(&mut self) -> Result<Query, QueryError> {
+        let mut terms = vec![self.and()?];
+        while self.peek() == Some(&Lexeme::Or) {
+            self.position += 1;
+            terms.push(self.and()?);
+        }
+        Ok(combine(terms, Query::Or))
+    }
+
+    /// and := unary (AND? unary)*
+    fn and(&mut self) -> Result<Query, QueryError> {
+        let mut terms = vec![self.unary()?];
+        loop {
+            match self.peek() {
+                None | Some(Lexeme::Or) | Some(Lexeme::Close) => break,
+                Some(Lexeme::And) => self.position += 1,
+                _ => {}
+            }
+            terms.push(self.unary()?);
+        }
+        Ok(combine(terms, Query::And))
+    }
+
+    /// Parse a nested part of the query, failing if it is nested too deeply
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, QueryError>,
+    ) -> Result<T, QueryError> {
+        if self.depth == MAX_DEPTH {
+            return Err(QueryError::new(format!(
+                "Query nested more than {} deep",
+                MAX_DEPTH
+            )));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// unary := NOT unary | ( or ) | word | "phrase"
+    fn unary(&mut self) -> Result<Query, QueryError> {
+        match self.next().cloned() {
+            Some(Lexeme::Not) => Ok(Query::Not(Box::new(self.nested(Self::unary)?))),
+            Some(Lexeme::Open) => {
+                let query = self.nested(Self::or)?;
+                match self.next() {
+                    Some(Lexeme::Close) => Ok(query),
+                    _ => Err(QueryError::new("Unclosed bracket".to_string())),
+                }
+            }
+            Some(Lexeme::Word(word)) => word_query(&word),
+            Some(Lexeme::Quoted(phrase)) => {
+                let words: Vec<String> = tokenize(&phrase).map(|t| t.word).collect();
+                match words.len() {
+                    0 => Err(QueryError::new(format!("Empty phrase {:?}", phrase))),
+                    1 => Ok(Query::Term(words.into_iter().next().unwrap())),
+                    _ => Ok(Query::Phrase(words)),
+                }
+            }
+            Some(other) => Err(QueryError::new(format!("Unexpected {:?}", other))),
+            None => Err(QueryError::new("Unexpected end of query".to_string())),
+        }
+    }
+}
+
+fn combine(mut terms: Vec<Query>, make: fn(Vec<Query>) -> Query) -> Query {
+    match terms.len() {
+        1 => terms.pop().unwrap(),
+        _ => make(terms),
+    }
+}
+
+/// The query for a single unquoted word, which may end with `*` for a prefix
+/// Punctuation in the word is treated as it is in the text, so `LORD's` is one
+/// term and `well-pleased` is the phrase `"well pleased"`.
+fn word_query(word: &str) -> Result<Query, QueryError> {
+    if let Some(prefix) = word.strip_suffix('*') {
+        let folded = fold(prefix);
+        return match folded.is_empty() || !folded.chars().all(|c| c.is_alphanumeric() || c == '\'')
+        {
+            true => Err(QueryError::new(format!("Invalid prefix {:?}", word))),
+            false => Ok(Query::Prefix(folded)),
+        };
+    }
+    let mut words: Vec<String> = tokenize(word).map(|t| t.word).collect();
+    match words.len() {
+        0 => Err(QueryError::new(format!("No words in {:?}", word))),
+        1 => Ok(Query::Term(words.pop().unwrap())),
+        _ => Ok(Query::Phrase(words)),
+    }
+}
+
+#[allow(dead_code)]
+impl Query {
+    /// Parse a query
+    ///
+    /// Words next to each other must all match. `OR` or `|` matches either side and
+    /// `-` or `NOT` excludes verses. Quotes match a phrase, a trailing `*` matches any
+    /// word with that prefix and brackets group.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::search::Query;
+    /// let query = Query::parse("love -(hate | \"evil doers\") bless*").unwrap();
+    /// assert_eq!(
+    ///     query,
+    ///     Query::And(vec![
+    ///         Query::Term("love".to_string()),
+    ///         Query::Not(Box::new(Query::Or(vec![
+    ///             Query::Term("hate".to_string()),
+    ///             Query::Phrase(vec!["evil".to_string(), "doers".to_string()]),
+    ///         ]))),
+    ///         Query::Prefix("bless".to_string()),
+    ///     ])
+    /// );
+    /// assert!(Query::parse("(love").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        if text.len() > MAX_QUERY_LEN {
+            return Err(QueryError::new(format!(
+                "Query longer than {} bytes",
+                MAX_QUERY_LEN
+            )));
+        }
+        let lexemes = lex(text)?;
+        let mut parser = Parser {
+            lexemes: &lexemes,
+            position: 0,
+            depth: 0,
+        };
+        let query = parser.or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(other) => Err(QueryError::new(format!("Unexpected {:?}", other))),
+        }
+    }
+
+    /// Return if this query only excludes verses, so cannot match anything on its own
+    pub fn is_negative(&self) -> bool {
+        match self {
+            Query::Not(_) => true,
+            Query::And(terms) => terms.iter().all(Query::is_negative),
+            Query::Or(terms) => terms.iter().any(Query::is_negative),
+            _ => false,
+        }
+    }
+
+    /// Return if a folded word matches this query as a single term
+    pub(crate) fn matches_word(&self, word: &str) -> bool {
+        match self {
+            Query::Term(term) => term == word,
+            Query::Prefix(prefix) => word.starts_with(prefix.as_str()),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(word: &str) -> Query {
+        Query::Term(word.to_string())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Query::parse("Love").unwrap(), term("love"));
+        assert_eq!(
+            Query::parse("faith AND hope OR charity").unwrap(),
+            Query::Or(vec![
+                Query::And(vec![term("faith"), term("hope")]),
+                term("charity")
+            ])
+        );
+        assert_eq!(
+            Query::parse("NOT Égypte").unwrap(),
+            Query::Not(Box::new(term("egypte")))
+        );
+        assert_eq!(
+            Query::parse("“living water”").unwrap(),
+            Query::Phrase(vec!["living".to_string(), "water".to_string()])
+        );
+        assert_eq!(Query::parse("LORD's").unwrap(), term("lord's"));
+        assert_eq!(
+            Query::parse("well-pleased").unwrap(),
+            Query::Phrase(vec!["well".to_string(), "pleased".to_string()])
+        );
+        assert_eq!(
+            Query::parse("Bless*").unwrap(),
+            Query::Prefix("bless".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Query::parse("").is_err());
+        assert!(Query::parse("\"unclosed").is_err());
+        assert!(Query::parse("love)").is_err());
+        assert!(Query::parse("love OR").is_err());
+        assert!(Query::parse("*").is_err());
+        assert!(Query::parse("\"\"").is_err());
+    }
+
+    #[test]
+    fn test_limits() {
+        let nested = |depth: usize| format!("{}love{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(Query::parse(&nested(MAX_DEPTH)).unwrap(), term("love"));
+        assert!(Query::parse(&nested(MAX_DEPTH + 1)).is_err());
+        assert!(Query::parse(&"(".repeat(200_000)).is_err());
+        assert!(Query::parse(&format!("{}love", "NOT ".repeat(MAX_DEPTH))).is_ok());
+        assert!(Query::parse(&format!("{}love", "NOT ".repeat(MAX_DEPTH + 1))).is_err());
+        assert!(Query::parse(&format!("{}love", "-".repeat(200))).is_err());
+        assert!(Query::parse(&"love ".repeat(300)).is_err());
+    }
+
+    #[test]
+    fn test_is_negative() {
+        assert!(Query::parse("-love").unwrap().is_negative());
+        assert!(!Query::parse("faith -love").unwrap().is_negative());
+        assert!(Query::parse("faith | -love").unwrap().is_negative());
+    }
+}
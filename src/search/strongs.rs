@@ -0,0 +1,130 @@
+use super::tokenize::fold;
+use crate::text::BibleText;
+use crate::{BibleVerse, StrongsNumber};
+use std::collections::BTreeMap;
+
+/// The uses of a Strong's number in a tagged text
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StrongsEntry {
+    /// The verses the number occurs in, in canonical order
+    pub verses: Vec<BibleVerse>,
+    /// The folded words of the translation tagged with the number, with how often
+    /// each is used
+    pub renderings: BTreeMap<String, usize>,
+}
+
+/// An index from Strong's numbers to the verses and words of a tagged text
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, StrongsNumber};
+/// use bible_data::import::usfm::UsfmReader;
+/// use bible_data::search::StrongsIndex;
+/// use bible_data::text::{BibleText, Metadata};
+/// let usfm = r#"\id GEN
+/// \c 1
+/// \v 1 In \w the beginning|strong="H7225"\w* \w God|strong="H430"\w* created
+/// \c 49
+/// \v 3 Reuben, thou art my firstborn, my might, and \w the beginning|strong="H7225"\w* of my strength"#;
+/// let text = BibleText::from_verses(Metadata::default(), UsfmReader::new(usfm.as_bytes()).map(|v| v.unwrap()));
+/// let index = StrongsIndex::new(&text);
+/// let beginning = StrongsNumber::parse("H7225").unwrap();
+/// assert_eq!(index.verses(&beginning).len(), 2);
+/// assert_eq!(index.get(&beginning).unwrap().renderings["the beginning"], 2);
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StrongsIndex {
+    entries: BTreeMap<StrongsNumber, StrongsEntry>,
+}
+
+#[allow(dead_code)]
+impl StrongsIndex {
+    /// Index the tagged words of a text
+    pub fn new(text: &BibleText) -> Self {
+        let mut entries: BTreeMap<StrongsNumber, StrongsEntry> = BTreeMap::new();
+        for (verse, markup) in text.markup_iter() {
+            let Some(verse_text) = text.get(&verse) else {
+                continue;
+            };
+            for word in markup.words.iter() {
+                let rendering = verse_text.get(word.range.clone()).map(fold);
+                for number in word.strongs.iter() {
+                    let entry = entries.entry(*number).or_default();
+                    if entry.verses.last() != Some(&verse) {
+                        entry.verses.push(verse);
+                    }
+                    if let Some(rendering) = &rendering {
+                        *entry.renderings.entry(rendering.clone()).or_default() += 1;
+                    }
+                }
+            }
+        }
+        StrongsIndex { entries }
+    }
+
+    pub fn get(&self, number: &StrongsNumber) -> Option<&StrongsEntry> {
+        self.entries.get(number)
+    }
+
+    /// Return the verses containing a Strong's number
+    pub fn verses(&self, number: &StrongsNumber) -> &[BibleVerse] {
+        self.entries
+            .get(number)
+            .map_or(&[], |entry| entry.verses.as_slice())
+    }
+
+    /// Iterate over the Strong's numbers used in the text, Hebrew first
+    pub fn numbers(&self) -> impl Iterator<Item = &StrongsNumber> + '_ {
+        self.entries.keys()
+    }
+
+    /// Return the number of different Strong's numbers used in the text
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BibleBook;
+    use crate::import::osis::OsisReader;
+    use crate::text::Metadata;
+
+    #[test]
+    fn test_index() {
+        let osis = r#"<osis><div type="book" osisID="John">
+<verse osisID="John.3.16">For <w lemma="strong:G2316">God</w> so <w lemma="strong:G25" morph="robinson:V-AAI-3S">loved</w> the world</verse>
+<verse osisID="John.15.13">Greater <w lemma="strong:G26">love</w> hath no man than this</verse>
+<verse osisID="John.21.15"><w lemma="strong:G25">lovest</w> thou me more than these? <w lemma="strong:G25">Lovest</w> thou</verse>
+</div></osis>"#;
+        let verses = OsisReader::new(osis.as_bytes()).map(|v| v.unwrap());
+        let text = BibleText::from_verses(Metadata::default(), verses);
+        let index = StrongsIndex::new(&text);
+        assert_eq!(index.len(), 3);
+        let agapao = StrongsNumber::greek(25);
+        assert_eq!(
+            index.verses(&agapao),
+            &[
+                BibleVerse::new(BibleBook::John, 3, 16),
+                BibleVerse::new(BibleBook::John, 21, 15)
+            ]
+        );
+        assert_eq!(
+            index.get(&agapao).unwrap().renderings,
+            BTreeMap::from([("loved".to_string(), 1), ("lovest".to_string(), 2)])
+        );
+        assert!(index.verses(&StrongsNumber::hebrew(7225)).is_empty());
+        let markup = text
+            .markup(&BibleVerse::new(BibleBook::John, 3, 16))
+            .unwrap();
+        assert_eq!(markup.words[1].morph.as_deref(), Some("robinson:V-AAI-3S"));
+        assert_eq!(text.strongs(&StrongsNumber::greek(26)).count(), 1);
+    }
+}
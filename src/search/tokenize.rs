@@ -0,0 +1,177 @@
+use std::ops::Range;
+
+/// A word in a verse, folded for searching
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The word folded to lower case without diacritics
+    pub word: String,
+    /// Where the word is in the original text, in bytes
+    pub range: Range<usize>,
+}
+
+/// Return if a character is a CJK ideograph. These are written without spaces so each
+/// is treated as a word of its own.
+fn is_ideograph(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}')
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+/// The letter without any diacritic, for the Latin and Greek letters used by the
+/// translations this crate has book names for
+fn strip_diacritic(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ά' => "α",
+        'έ' => "ε",
+        'ή' => "η",
+        'ί' | 'ϊ' | 'ΐ' => "ι",
+        'ό' => "ο",
+        'ύ' | 'ϋ' | 'ΰ' => "υ",
+        'ώ' => "ω",
+        '\u{2019}' => "'",
+        _ => return None,
+    })
+}
+
+/// Fold a word to lower case and remove any diacritics, so that searches match
+/// regardless of case or accents
+///
+/// # Example
+/// ```rust
+/// use bible_data::search::tokenize::fold;
+/// assert_eq!(fold("LORD"), "lord");
+/// assert_eq!(fold("Génesis"), "genesis");
+/// assert_eq!(fold("Straße"), "strasse");
+/// ```
+pub fn fold(word: &str) -> String {
+    let mut folded = String::with_capacity(word.len());
+    for c in word.chars().flat_map(char::to_lowercase) {
+        // Combining diacritical marks, for text that has been decomposed
+        if ('\u{0300}'..='\u{036F}').contains(&c) {
+            continue;
+        }
+        match strip_diacritic(c) {
+            Some(stripped) => folded.push_str(stripped),
+            None => folded.push(c),
+        }
+    }
+    folded
+}
+
+/// Split text into folded words
+///
+/// Words are runs of letters and digits. An apostrophe within a word is part of it,
+/// so `LORD's` is one word. CJK ideographs are each a word of their own.
+///
+/// # Example
+/// ```rust
+/// use bible_data::search::tokenize::tokenize;
+/// let words: Vec<String> = tokenize("The LORD's Prayer.").map(|t| t.word).collect();
+/// assert_eq!(words, vec!["the", "lord's", "prayer"]);
+/// ```
+pub fn tokenize(text: &str) -> impl Iterator<Item = Token> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        // Skip to the start of the next word
+        let (start, first) = loop {
+            let (i, c) = chars.next()?;
+            if c.is_alphanumeric() {
+                break (i, c);
+            }
+        };
+        let mut end = start + first.len_utf8();
+        if !is_ideograph(first) {
+            while let Some(&(i, c)) = chars.peek() {
+                if is_ideograph(c) {
+                    break;
+                }
+                if c.is_alphanumeric() || ('\u{0300}'..='\u{036F}').contains(&c) {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else if is_apostrophe(c) {
+                    // Only part of the word if a letter follows
+                    let mut ahead = chars.clone();
+                    ahead.next();
+                    match ahead.peek() {
+                        Some(&(_, next)) if next.is_alphanumeric() && !is_ideograph(next) => {
+                            chars.next();
+                        }
+                        _ => break,
+                    }
+                } else {
+                    break;
+                }
+            }
+        }
+        Some(Token {
+            word: fold(&text[start..end]),
+            range: start..end,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        tokenize(text).map(|t| t.word).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            words("In the beginning God created the heaven and the earth."),
+            vec![
+                "in",
+                "the",
+                "beginning",
+                "god",
+                "created",
+                "the",
+                "heaven",
+                "and",
+                "the",
+                "earth"
+            ]
+        );
+        assert_eq!(
+            words("men’s hearts' 'quoted'"),
+            vec!["men's", "hearts", "quoted"]
+        );
+        assert_eq!(
+            words("Denn also hat Gott die Welt geliebt"),
+            words("DENN ALSO HAT GOTT DIE WELT GELIEBT")
+        );
+        assert_eq!(words("Él dijo: «Señor»"), vec!["el", "dijo", "senor"]);
+        assert_eq!(words("神爱世人"), vec!["神", "爱", "世", "人"]);
+        assert_eq!(words("e\u{0301}glise"), vec!["eglise"]);
+        let tokens: Vec<Token> = tokenize("Jesus wept.").collect();
+        assert_eq!(tokens[1].range, 6..10);
+    }
+}
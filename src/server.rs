@@ -0,0 +1,615 @@
+//! Mod for a small HTTP server answering JSON queries about references and texts
+//!
+//! Only built with the `server` feature. It is written on [TcpListener] alone, so it adds
+//! no dependencies, and is meant to be run locally by services that want the crate's
+//! reference parsing without linking to it. Every response is JSON, with an `error`
+//! member when the request could not be answered.
+//!
+//! | Endpoint                                   | Returns                                 |
+//! |--------------------------------------------|-----------------------------------------|
+//! | `GET /parse?ref=`                          | The kind, canonical form and OSIS of a reference |
+//! | `GET /books?locale=`                       | The books of the Bible                  |
+//! | `GET /books/{book}/chapters`               | The number of verses in each chapter    |
+//! | `GET /passage?ref=&translation=`           | The text of a passage                   |
+//! | `GET /search?q=&translation=&limit=`       | The verses best matching a query        |
+//!
+//! `translation` is the abbreviation of one of the translations given to the server and
+//! defaults to the first.
+//!
+//! # Example
+//! ```rust
+//! use bible_data::server::Server;
+//! let server = Server::new();
+//! let response = server.handle("GET", "/parse?ref=John%203%3A16");
+//! assert_eq!(response.status, 200);
+//! assert_eq!(
+//!     response.body,
+//!     r#"{"input": "John 3:16", "kind": "verse", "reference": "Jn 3:16", "book": "John", "osis": "John.3.16"}"#
+//! );
+//! ```
+use crate::json::write_string;
+use crate::search::{Query, Scope, SearchIndex};
+use crate::text::BibleText;
+use crate::{BibleBook, BibleReference, CHAPTER_VERSES, Locale};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Mutex, mpsc};
+use std::time::Duration;
+
+/// The number of search hits returned when no limit is given
+const DEFAULT_LIMIT: usize = 20;
+
+/// The most bytes read of a request's line and headers
+const MAX_REQUEST_LEN: u64 = 8192;
+
+/// How long to wait for a client to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The number of threads answering connections
+const WORKERS: usize = 8;
+
+/// The number of accepted connections that may wait for a free worker
+const QUEUE_LEN: usize = 64;
+
+/// How long to wait before accepting again after an accept fails
+const ACCEPT_RETRY: Duration = Duration::from_millis(50);
+
+/// A response to a request: its status code and JSON body
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[allow(dead_code)]
+impl Response {
+    fn ok(body: String) -> Self {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        let mut body = String::from("{\"error\": ");
+        write_string(&mut body, message);
+        body.push('}');
+        Response { status, body }
+    }
+
+    /// Return the reason phrase for the status code
+    pub fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Decode the `%XX` escapes and `+` spaces of a URL query component
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len()
+                && bytes[i + 1..i + 3].iter().all(u8::is_ascii_hexdigit) =>
+            {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The parameters of a request's query string
+struct Params(Vec<(String, String)>);
+
+impl Params {
+    fn parse(query: &str) -> Self {
+        Params(
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| match pair.split_once('=') {
+                    Some((name, value)) => (decode(name), decode(value)),
+                    None => (decode(pair), String::new()),
+                })
+                .collect(),
+        )
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn required(&self, name: &str) -> Result<&str, Response> {
+        self.get(name)
+            .ok_or_else(|| Response::error(400, &format!("Missing parameter {}", name)))
+    }
+}
+
+/// The server's translations, each with its search index
+///
+/// Requests are answered by [handle](Server::handle), which does not need a network
+/// connection, and [serve](Server::serve) answers them over HTTP.
+#[allow(dead_code)]
+#[derive(Default)]
+pub struct Server {
+    translations: Vec<(BibleText, SearchIndex)>,
+}
+
+#[allow(dead_code)]
+impl Server {
+    /// Construct a server with no translations, which can only answer questions about references
+    pub fn new() -> Self {
+        Server::default()
+    }
+
+    /// Add a translation, indexing it for search
+    pub fn with_translation(mut self, text: BibleText) -> Self {
+        let index = SearchIndex::from(&text);
+        self.translations.push((text, index));
+        self
+    }
+
+    /// Answer a request for a path and query string, such as `/books?locale=de`
+    pub fn handle(&self, method: &str, target: &str) -> Response {
+        if method != "GET" {
+            return Response::error(405, "Only GET is supported");
+        }
+        let (path, params) = match target.split_once('?') {
+            Some((path, query)) => (path, Params::parse(query)),
+            None => (target, Params(Vec::new())),
+        };
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(decode)
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let result = match segments.as_slice() {
+            ["parse"] => self.parse(&params),
+            ["books"] => self.books(&params),
+            ["books", book, "chapters"] => self.chapters(book),
+            ["passage"] => self.passage(&params),
+            ["search"] => self.search(&params),
+            _ => Err(Response::error(404, &format!("No such endpoint {}", path))),
+        };
+        result.unwrap_or_else(|response| response)
+    }
+
+    fn reference(params: &Params) -> Result<(&str, BibleReference), Response> {
+        let text = params.required("ref")?;
+        let reference = text
+            .parse::<BibleReference>()
+            .map_err(|e| Response::error(400, &e.to_string()))?;
+        Ok((text, reference))
+    }
+
+    fn translation(&self, params: &Params) -> Result<&(BibleText, SearchIndex), Response> {
+        match params.get("translation") {
+            None => self.translations.first(),
+            Some(abbrev) => self
+                .translations
+                .iter()
+                .find(|(text, _)| text.metadata().abbrev.eq_ignore_ascii_case(abbrev)),
+        }
+        .ok_or_else(|| match params.get("translation") {
+            Some(abbrev) => Response::error(404, &format!("No such translation {}", abbrev)),
+            None => Response::error(404, "No translations loaded"),
+        })
+    }
+
+    fn parse(&self, params: &Params) -> Result<Response, Response> {
+        let (text, reference) = Self::reference(params)?;
+        let mut body = String::from("{\"input\": ");
+        write_string(&mut body, text);
+        body.push_str(&format!(
+            ", \"kind\": \"{}\", \"reference\": ",
+            reference.kind()
+        ));
+        write_string(&mut body, &reference.to_string());
+        body.push_str(", \"book\": ");
+        write_string(&mut body, reference.book().name());
+        body.push_str(", \"osis\": ");
+        write_string(&mut body, &reference.to_osis());
+        body.push('}');
+        Ok(Response::ok(body))
+    }
+
+    fn books(&self, params: &Params) -> Result<Response, Response> {
+        let locale = match params.get("locale") {
+            None => Locale::default(),
+            Some(identifier) => Locale::from_identifier(identifier)
+                .ok_or_else(|| Response::error(400, &format!("Unknown locale {}", identifier)))?,
+        };
+        let books: Vec<String> = BibleBook::iter()
+            .map(|book| {
+                let mut json = format!("{{\"number\": {}, \"name\": ", book.book_number());
+                write_string(&mut json, book.name_in(locale));
+                json.push_str(", \"abbrev\": ");
+                write_string(&mut json, book.abbrev_in(locale));
+                json.push_str(&format!(
+                    ", \"osis\": \"{}\", \"usfm\": \"{}\", \"testament\": \"{}\", \"chapters\": {}}}",
+                    book.to_osis(),
+                    book.usfm_code(),
+                    match book.is_old_testament() {
+                        true => "old",
+                        false => "new",
+                    },
+                    book.number_of_chapters()
+                ));
+                json
+            })
+            .collect();
+        Ok(Response::ok(format!("[{}]", books.join(", "))))
+    }
+
+    fn chapters(&self, book: &str) -> Result<Response, Response> {
+        let book = BibleBook::parse(book)
+            .or_else(|| BibleBook::parse_name(book))
+            .or_else(|| BibleBook::from_osis(book).ok())
+            .or_else(|| BibleBook::from_usfm_code(book).ok())
+            .ok_or_else(|| Response::error(404, &format!("No such book {}", book)))?;
+        let chapters: Vec<String> = CHAPTER_VERSES[book.index()]
+            .iter()
+            .enumerate()
+            .map(|(i, verses)| format!("{{\"chapter\": {}, \"verses\": {}}}", i + 1, verses))
+            .collect();
+        let mut body = String::from("{\"book\": ");
+        write_string(&mut body, book.name());
+        body.push_str(&format!(", \"chapters\": [{}]}}", chapters.join(", ")));
+        Ok(Response::ok(body))
+    }
+
+    fn passage(&self, params: &Params) -> Result<Response, Response> {
+        let (_, reference) = Self::reference(params)?;
+        let (text, _) = self.translation(params)?;
+        let verses: Vec<String> = reference
+            .ranges()
+            .iter()
+            .flat_map(|range| text.passage(range).collect::<Vec<_>>())
+            .map(|(verse, verse_text)| {
+                let mut json = String::from("{\"verse\": ");
+                write_string(&mut json, &verse.to_string());
+                json.push_str(&format!(", \"osis\": \"{}\", \"text\": ", verse.to_osis()));
+                write_string(&mut json, verse_text);
+                json.push('}');
+                json
+            })
+            .collect();
+        let mut body = String::from("{\"reference\": ");
+        write_string(&mut body, &reference.to_string());
+        body.push_str(", \"translation\": ");
+        write_string(&mut body, &text.metadata().abbrev);
+        body.push_str(&format!(", \"verses\": [{}]}}", verses.join(", ")));
+        Ok(Response::ok(body))
+    }
+
+    fn search(&self, params: &Params) -> Result<Response, Response> {
+        let q = params.required("q")?;
+        let query = Query::parse(q).map_err(|e| Response::error(400, &e.to_string()))?;
+        let limit = match params.get("limit") {
+            None => DEFAULT_LIMIT,
+            Some(limit) => limit
+                .parse()
+                .map_err(|_| Response::error(400, &format!("Invalid limit {}", limit)))?,
+        };
+        let (text, index) = self.translation(params)?;
+        let hits = index.ranked(&query, &Scope::All);
+        let results: Vec<String> = hits
+            .iter()
+            .take(limit)
+            .map(|hit| {
+                let mut json = String::from("{\"verse\": ");
+                write_string(&mut json, &hit.verse.to_string());
+                json.push_str(&format!(", \"score\": {:.3}, \"text\": ", hit.score));
+                write_string(&mut json, text.get(&hit.verse).unwrap_or_default());
+                json.push('}');
+                json
+            })
+            .collect();
+        let mut body = String::from("{\"query\": ");
+        write_string(&mut body, q);
+        body.push_str(", \"translation\": ");
+        write_string(&mut body, &text.metadata().abbrev);
+        body.push_str(&format!(
+            ", \"total\": {}, \"hits\": [{}]}}",
+            hits.len(),
+            results.join(", ")
+        ));
+        Ok(Response::ok(body))
+    }
+
+    /// Answer requests on the listener, with a fixed pool of worker threads
+    ///
+    /// Connections wait in a short queue while every worker is busy, and after that in
+    /// the listener's backlog. A failed accept is logged and does not stop the server.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bible_data::server::Server;
+    /// let listener = std::net::TcpListener::bind("127.0.0.1:8080").unwrap();
+    /// Server::new().serve(listener).unwrap();
+    /// ```
+    pub fn serve(&self, listener: TcpListener) -> std::io::Result<()> {
+        let (sender, receiver) = mpsc::sync_channel::<TcpStream>(QUEUE_LEN);
+        let receiver = Mutex::new(receiver);
+        std::thread::scope(|scope| {
+            for _ in 0..WORKERS {
+                scope.spawn(|| {
+                    loop {
+                        // The lock is only held while waiting, not while responding
+                        let stream = match receiver.lock() {
+                            Ok(receiver) => receiver.recv(),
+                            Err(_) => return,
+                        };
+                        let Ok(stream) = stream else {
+                            return;
+                        };
+                        // A client that goes away only affects its own connection
+                        let _ = self.respond(stream);
+                    }
+                });
+            }
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        if sender.send(stream).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Could not accept a connection: {}", e);
+                        std::thread::sleep(ACCEPT_RETRY);
+                    }
+                }
+            }
+            drop(sender);
+            Ok(())
+        })
+    }
+
+    /// Read one request from a connection and write the response
+    fn respond(&self, stream: TcpStream) -> std::io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        // A client cannot hold a thread with a slow or endless request
+        let mut reader = BufReader::new(&stream).take(MAX_REQUEST_LEN);
+        let mut request = String::new();
+        reader.read_line(&mut request)?;
+        // The headers are not needed, but are read so the client sees a clean close
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+            header.clear();
+        }
+        let mut parts = request.split_whitespace();
+        let response = match (parts.next(), parts.next()) {
+            _ if !request.ends_with('\n') => Response::error(400, "Request line too long"),
+            (Some(method), Some(target)) => self.handle(method, target),
+            _ => Response::error(400, "Invalid request"),
+        };
+        let mut stream = &stream;
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            response.reason(),
+            response.body.len(),
+            response.body
+        )?;
+        stream.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BibleVerse;
+    use crate::text::{Metadata, VerseText};
+    use std::io::Read;
+
+    fn server() -> Server {
+        let verse = |chapter, verse, text| {
+            VerseText::new(BibleVerse::new(BibleBook::John, chapter, verse), text)
+        };
+        let kjv = BibleText::from_verses(
+            Metadata::new("King James Version", "KJV", "en"),
+            [
+                verse(3, 16, "For God so loved the world"),
+                verse(11, 35, "Jesus wept."),
+                verse(11, 36, "Then said the Jews, Behold how he loved him!"),
+            ],
+        );
+        Server::new().with_translation(kjv)
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("Jn+3%3A16"), "Jn 3:16");
+        assert_eq!(decode("R%C3%B6m%208"), "Röm 8");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz"), "%zz");
+        assert_eq!(decode("%+F"), "% F");
+        assert_eq!(decode("%-1"), "%-1");
+    }
+
+    #[test]
+    fn test_parse() {
+        let server = server();
+        let response = server.handle("GET", "/parse?ref=Ro+8:28,+31-39");
+        assert_eq!(response.status, 200);
+        assert!(
+            response
+                .body
+                .contains("\"kind\": \"list\", \"reference\": \"Ro 8:28, 31-39\"")
+        );
+        let response = server.handle("GET", "/parse?ref=Hezekiah+1");
+        assert_eq!(response.status, 400);
+        assert!(response.body.starts_with("{\"error\": "));
+        assert_eq!(
+            server.handle("GET", "/parse"),
+            Response::error(400, "Missing parameter ref")
+        );
+    }
+
+    #[test]
+    fn test_books() {
+        let server = server();
+        let response = server.handle("GET", "/books");
+        assert!(response.body.starts_with(
+            "[{\"number\": 1, \"name\": \"Genesis\", \"abbrev\": \"Ge\", \"osis\": \"Gen\", \
+             \"usfm\": \"GEN\", \"testament\": \"old\", \"chapters\": 50}, "
+        ));
+        assert_eq!(response.body.matches("\"number\"").count(), 66);
+        let response = server.handle("GET", "/books?locale=de");
+        assert!(response.body.contains("\"name\": \"1. Mose\""));
+        assert_eq!(server.handle("GET", "/books?locale=xx").status, 400);
+        assert_eq!(
+            server
+                .handle("GET", "/books/Ps/chapters")
+                .body
+                .matches("chapter\"")
+                .count(),
+            150
+        );
+        assert_eq!(
+            server.handle("GET", "/books/Jude/chapters").body,
+            "{\"book\": \"Jude\", \"chapters\": [{\"chapter\": 1, \"verses\": 25}]}"
+        );
+        assert_eq!(server.handle("GET", "/books/1Kgs/chapters").status, 200);
+        assert_eq!(
+            server.handle("GET", "/books/1%20Kings/chapters").status,
+            200
+        );
+        assert_eq!(server.handle("GET", "/books/Hezekiah/chapters").status, 404);
+    }
+
+    #[test]
+    fn test_passage() {
+        let server = server();
+        assert_eq!(
+            server
+                .handle("GET", "/passage?ref=Jn+11&translation=kjv")
+                .body,
+            "{\"reference\": \"Jn 11\", \"translation\": \"KJV\", \"verses\": [\
+             {\"verse\": \"Jn 11:35\", \"osis\": \"John.11.35\", \"text\": \"Jesus wept.\"}, \
+             {\"verse\": \"Jn 11:36\", \"osis\": \"John.11.36\", \"text\": \"Then said the Jews, Behold how he loved him!\"}]}"
+        );
+        assert_eq!(
+            server.handle("GET", "/passage?ref=Jn+3:16&translation=NIV"),
+            Response::error(404, "No such translation NIV")
+        );
+        assert_eq!(
+            Server::new().handle("GET", "/passage?ref=Jn+3:16"),
+            Response::error(404, "No translations loaded")
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let server = server();
+        let response = server.handle("GET", "/search?q=loved&limit=1");
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with(
+            "{\"query\": \"loved\", \"translation\": \"KJV\", \"total\": 2, \"hits\": [{\"verse\": "
+        ));
+        assert_eq!(response.body.matches("\"score\"").count(), 1);
+        assert_eq!(server.handle("GET", "/search?q=(loved").status, 400);
+        assert_eq!(
+            server.handle("GET", "/search?q=loved&limit=all").status,
+            400
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let server = server();
+        assert_eq!(server.handle("POST", "/books").status, 405);
+        assert_eq!(
+            server.handle("GET", "/verses"),
+            Response::error(404, "No such endpoint /verses")
+        );
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || server().serve(listener));
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "GET /parse?ref=Jn+11:35 HTTP/1.1\r\nHost: localhost\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json; charset=utf-8\r\n"));
+        assert!(response.ends_with("\"osis\": \"John.11.35\"}"));
+    }
+
+    #[test]
+    fn test_serve_many() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || server().serve(listener));
+        let clients: Vec<_> = (0..WORKERS * 3)
+            .map(|_| {
+                std::thread::spawn(move || {
+                    let mut stream = TcpStream::connect(address).unwrap();
+                    write!(stream, "GET /parse?ref=Jn+3:16 HTTP/1.1\r\n\r\n").unwrap();
+                    let mut response = String::new();
+                    stream.read_to_string(&mut response).unwrap();
+                    response
+                })
+            })
+            .collect();
+        for client in clients {
+            assert!(client.join().unwrap().starts_with("HTTP/1.1 200 OK\r\n"));
+        }
+    }
+
+    #[test]
+    fn test_serve_limits() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || server().serve(listener));
+        let send = |request: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let request = |target: &str| send(&format!("GET {} HTTP/1.1\r\n\r\n", target));
+        // Only the first MAX_REQUEST_LEN bytes are read, so no more are sent
+        let long = format!("GET /search?q={}", "l".repeat(8192));
+        let response = send(&long[..MAX_REQUEST_LEN as usize]);
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+        assert!(response.ends_with("{\"error\": \"Request line too long\"}"));
+        let nested = format!("/search?q={}love{}", "(".repeat(100), ")".repeat(100));
+        let response = request(&nested);
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+        assert!(response.contains("nested more than 64 deep"));
+        let response = request(&format!("/search?q={}", "love+".repeat(300)));
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    }
+}
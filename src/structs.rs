@@ -16,6 +16,19 @@ pub mod chapter;
 pub mod chapterandverse;
 pub mod chapterandverseorverse;
 pub mod chapterandverserange;
+#[cfg(feature = "serde")]
+pub mod compact;
 pub mod errors;
+pub mod locale;
+pub mod osis;
+pub mod reference;
+#[cfg(feature = "schema")]
+mod schema;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod strongs;
+pub mod style;
 pub mod verse;
 pub mod verserange;
+pub mod verserangelist;
+pub mod word;
@@ -1,5 +1,9 @@
 use super::errors::{NoSuchBookError, OutOfRangeError};
-use crate::{BOOK_ABBREVS, BOOK_CHAPTERS, BOOK_NAMES, parse_book_abbrev};
+use super::locale::Locale;
+use super::style::{ReferenceStyle, StyledDisplay};
+use crate::{
+    BOOK_ABBREVS, BOOK_CHAPTERS, BOOK_NAMES, BOOK_OSIS_IDS, BOOK_USFM_CODES, parse_book_abbrev,
+};
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -10,7 +14,7 @@ use std::str::FromStr;
 /// or parsed from an abbreviation with [parse_abbrev] or from the full name with
 /// [parse_name]. The method [parse] will try both.
 #[allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
 pub enum BibleBook {
     Genesis = 1,
@@ -87,6 +91,16 @@ impl Display for BibleBook {
     }
 }
 
+impl StyledDisplay for BibleBook {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.name_in(style.locale))
+    }
+}
+
 #[allow(dead_code)]
 impl BibleBook {
     /// Return the book number for this book as it occurs in the Bible.
@@ -137,6 +151,31 @@ impl BibleBook {
         BOOK_ABBREVS[self.index()]
     }
 
+    /// Return the name of this book in the given locale
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, Locale};
+    /// assert_eq!(BibleBook::Genesis.name_in(Locale::Spanish), "Génesis");
+    /// assert_eq!(BibleBook::Genesis.name_in(Locale::German), "1. Mose");
+    /// assert_eq!(BibleBook::Genesis.name_in(Locale::English), "Genesis");
+    /// ```
+    pub fn name_in(&self, locale: Locale) -> &'static str {
+        locale.book_names()[self.index()]
+    }
+
+    /// Return the abbreviation for this book in the given locale
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, Locale};
+    /// assert_eq!(BibleBook::Romans.abbrev_in(Locale::German), "Röm");
+    /// assert_eq!(BibleBook::Genesis.abbrev_in(Locale::Korean), "창");
+    /// ```
+    pub fn abbrev_in(&self, locale: Locale) -> &'static str {
+        locale.book_abbrevs()[self.index()]
+    }
+
     /// Construct a BibleBook from its book number.
     /// 1 = Genesis. 66 = Revelation.
     ///
@@ -246,6 +285,45 @@ impl BibleBook {
         value.parse().ok()
     }
 
+    /// Parse a string into a BibleBook instance using the names and abbreviations
+    /// of the given locale. The whole string must match.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, Locale};
+    /// assert_eq!(BibleBook::parse_in("Génesis", Locale::Spanish).unwrap(), BibleBook::Genesis);
+    /// assert_eq!(BibleBook::parse_in("Offb", Locale::German).unwrap(), BibleBook::Revelation);
+    /// assert!(BibleBook::parse_in("Genesis", Locale::Spanish).is_none());
+    /// ```
+    pub fn parse_in(value: &str, locale: Locale) -> Option<Self> {
+        match Self::split_in(value, locale) {
+            Some((book, None)) => Some(book),
+            _ => None,
+        }
+    }
+
+    /// Split a reference into the book at the start of the text and the remainder
+    /// after the space that follows it (if any).
+    /// The longest name or abbreviation in the locale that is followed by a space
+    /// or the end of the text wins, so "1 Juan 3" is not mistaken for anything shorter.
+    pub(crate) fn split_in(text: &str, locale: Locale) -> Option<(Self, Option<&str>)> {
+        let names = locale.book_names().iter().enumerate();
+        let abbrevs = locale.book_abbrevs().iter().enumerate();
+        let (index, len) = names
+            .chain(abbrevs)
+            .filter(|(_, candidate)| match text.strip_prefix(**candidate) {
+                Some(rest) => rest.is_empty() || rest.starts_with(' '),
+                None => false,
+            })
+            .map(|(index, candidate)| (index, candidate.len()))
+            .max_by_key(|(_, len)| *len)?;
+        let book = Self::from_index(index).ok()?;
+        match text.len() == len {
+            true => Some((book, None)),
+            false => Some((book, Some(&text[len + 1..]))),
+        }
+    }
+
     /// Return an iterator over all the books of the Bible as BibleBook instances
     ///
     /// # Example
@@ -302,6 +380,108 @@ impl BibleBook {
     pub fn number_of_chapters(&self) -> u32 {
         BOOK_CHAPTERS[self.index()] as u32
     }
+
+    /// Return the OSIS book identifier for this book
+    ///
+    /// ```rust
+    /// use bible_data::BibleBook;
+    /// assert_eq!(BibleBook::FirstKings.to_osis(), "1Kgs");
+    /// ```
+    pub fn to_osis(&self) -> &'static str {
+        BOOK_OSIS_IDS[self.index()]
+    }
+
+    /// Construct a BibleBook from its OSIS book identifier.
+    ///
+    /// Returns Ok([BibleBook]) or Err([NoSuchBookError])
+    ///
+    /// ```rust
+    /// use bible_data::BibleBook;
+    /// assert_eq!(BibleBook::from_osis("1Kgs").unwrap(), BibleBook::FirstKings);
+    /// assert!(BibleBook::from_osis("1Ki").is_err());
+    /// ```
+    pub fn from_osis(id: &str) -> Result<Self, NoSuchBookError> {
+        match BOOK_OSIS_IDS.iter().position(|osis| *osis == id) {
+            Some(index) => Ok(Self::from_index(index).unwrap()),
+            None => Err(NoSuchBookError::new(format!("No OSIS book id: {}", id))),
+        }
+    }
+
+    /// Return the three-letter USFM / Paratext code for this book
+    ///
+    /// ```rust
+    /// use bible_data::BibleBook;
+    /// assert_eq!(BibleBook::SongofSongs.usfm_code(), "SNG");
+    /// ```
+    pub fn usfm_code(&self) -> &'static str {
+        BOOK_USFM_CODES[self.index()]
+    }
+
+    /// Construct a BibleBook from its three-letter USFM / Paratext code.
+    /// Codes are matched case-insensitively.
+    ///
+    /// Returns Ok([BibleBook]) or Err([NoSuchBookError])
+    ///
+    /// ```rust
+    /// use bible_data::BibleBook;
+    /// assert_eq!(BibleBook::from_usfm_code("PHP").unwrap(), BibleBook::Philippians);
+    /// assert_eq!(BibleBook::from_usfm_code("php").unwrap(), BibleBook::Philippians);
+    /// assert!(BibleBook::from_usfm_code("PHIL").is_err());
+    /// ```
+    pub fn from_usfm_code(code: &str) -> Result<Self, NoSuchBookError> {
+        match BOOK_USFM_CODES
+            .iter()
+            .position(|usfm| usfm.eq_ignore_ascii_case(code))
+        {
+            Some(index) => Ok(Self::from_index(index).unwrap()),
+            None => Err(NoSuchBookError::new(format!("No USFM book code: {}", code))),
+        }
+    }
+
+    /// Return the number used for this book in USFM file names, eg. `41MAT.SFM`
+    /// These match [book_number](BibleBook::book_number) for the Old Testament but
+    /// skip 40 so that Matthew is 41 and Revelation is 67.
+    /// (Paratext's own numeric book ids are the same as [book_number](BibleBook::book_number).)
+    ///
+    /// ```rust
+    /// use bible_data::BibleBook;
+    /// assert_eq!(BibleBook::Malachi.usfm_number(), 39);
+    /// assert_eq!(BibleBook::Matthew.usfm_number(), 41);
+    /// ```
+    pub fn usfm_number(&self) -> u32 {
+        match self.is_new_testament() {
+            true => self.book_number() + 1,
+            false => self.book_number(),
+        }
+    }
+
+    /// Construct a BibleBook from the number used for it in USFM file names
+    ///
+    /// Returns Ok([BibleBook]) or Err([OutOfRangeError])
+    ///
+    /// ```rust
+    /// use bible_data::BibleBook;
+    /// assert_eq!(BibleBook::from_usfm_number(41).unwrap(), BibleBook::Matthew);
+    /// assert!(BibleBook::from_usfm_number(40).is_err());
+    /// ```
+    pub fn from_usfm_number(number: u32) -> Result<Self, OutOfRangeError> {
+        match number {
+            40 => Err(OutOfRangeError::new(String::from(
+                "40 is not used for a book in USFM file names",
+            ))),
+            41..=67 => Self::from_book_number(number - 1),
+            _ => Self::from_book_number(number),
+        }
+    }
+
+    /// Split a Paratext style reference into the book given by the USFM code at the
+    /// start and the remainder after the following space (if any)
+    pub(crate) fn split_usfm(text: &str) -> Option<(Self, Option<&str>)> {
+        match text.split_once(' ') {
+            None => Some((Self::from_usfm_code(text).ok()?, None)),
+            Some((code, remain)) => Some((Self::from_usfm_code(code).ok()?, Some(remain))),
+        }
+    }
 }
 
 // TryFrom / TryInto
@@ -368,6 +548,78 @@ mod tests {
         assert_eq!(BibleBook::Revelation.abbrev(), "Rev");
     }
 
+    #[test]
+    fn test_name_in() {
+        assert_eq!(BibleBook::Genesis.name_in(Locale::English), "Genesis");
+        assert_eq!(BibleBook::Genesis.name_in(Locale::Spanish), "Génesis");
+        assert_eq!(BibleBook::Genesis.name_in(Locale::Portuguese), "Gênesis");
+        assert_eq!(BibleBook::Genesis.name_in(Locale::German), "1. Mose");
+        assert_eq!(BibleBook::Genesis.name_in(Locale::French), "Genèse");
+        assert_eq!(BibleBook::Genesis.name_in(Locale::Korean), "창세기");
+        assert_eq!(BibleBook::Genesis.name_in(Locale::Chinese), "创世记");
+        assert_eq!(BibleBook::Revelation.name_in(Locale::German), "Offenbarung");
+        for book in BibleBook::iter() {
+            assert_eq!(book.name_in(Locale::English), book.name());
+        }
+    }
+
+    #[test]
+    fn test_abbrev_in() {
+        assert_eq!(BibleBook::John.abbrev_in(Locale::English), "Jn");
+        assert_eq!(BibleBook::John.abbrev_in(Locale::Spanish), "Jn");
+        assert_eq!(BibleBook::John.abbrev_in(Locale::Portuguese), "Jo");
+        assert_eq!(BibleBook::John.abbrev_in(Locale::German), "Joh");
+        assert_eq!(BibleBook::John.abbrev_in(Locale::French), "Jn");
+        assert_eq!(BibleBook::John.abbrev_in(Locale::Korean), "요");
+        assert_eq!(BibleBook::John.abbrev_in(Locale::Chinese), "约");
+        for book in BibleBook::iter() {
+            assert_eq!(book.abbrev_in(Locale::English), book.abbrev());
+        }
+    }
+
+    #[test]
+    fn test_parse_in() {
+        for locale in Locale::iter() {
+            for book in BibleBook::iter() {
+                assert_eq!(
+                    BibleBook::parse_in(book.name_in(locale), locale),
+                    Some(book)
+                );
+                assert_eq!(
+                    BibleBook::parse_in(book.abbrev_in(locale), locale),
+                    Some(book)
+                );
+            }
+        }
+        assert_eq!(
+            BibleBook::parse_in("1 Juan", Locale::Spanish),
+            Some(BibleBook::FirstJohn)
+        );
+        assert_eq!(
+            BibleBook::parse_in("Juan", Locale::Spanish),
+            Some(BibleBook::John)
+        );
+        assert_eq!(BibleBook::parse_in("Génesis 1", Locale::Spanish), None);
+        assert_eq!(BibleBook::parse_in("Genesis", Locale::German), None);
+    }
+
+    #[test]
+    fn test_split_in() {
+        assert_eq!(
+            BibleBook::split_in("1. Mose 1", Locale::German),
+            Some((BibleBook::Genesis, Some("1")))
+        );
+        assert_eq!(
+            BibleBook::split_in("창세기 1:1", Locale::Korean),
+            Some((BibleBook::Genesis, Some("1:1")))
+        );
+        assert_eq!(
+            BibleBook::split_in("2 Juan", Locale::Spanish),
+            Some((BibleBook::SecondJohn, None))
+        );
+        assert_eq!(BibleBook::split_in("Génesis1", Locale::Spanish), None);
+    }
+
     #[test]
     fn test_from_book_number() {
         assert_eq!(BibleBook::from_book_number(1).unwrap(), BibleBook::Genesis);
@@ -445,6 +697,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_is_new_testament() {
         assert_eq!(BibleBook::Genesis.is_new_testament(), false);
         assert_eq!(BibleBook::Revelation.is_new_testament(), true);
@@ -458,6 +711,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_is_old_testament() {
         assert_eq!(BibleBook::Genesis.is_old_testament(), true);
         assert_eq!(BibleBook::Revelation.is_old_testament(), false);
@@ -494,6 +748,43 @@ mod tests {
     }
 
     #[test]
+    fn test_osis() {
+        assert_eq!(BibleBook::Genesis.to_osis(), "Gen");
+        assert_eq!(BibleBook::Psalms.to_osis(), "Ps");
+        assert_eq!(BibleBook::SongofSongs.to_osis(), "Song");
+        assert_eq!(BibleBook::Philemon.to_osis(), "Phlm");
+        assert_eq!(BibleBook::Revelation.to_osis(), "Rev");
+        for book in BibleBook::iter() {
+            assert_eq!(BibleBook::from_osis(book.to_osis()).unwrap(), book);
+        }
+        assert!(BibleBook::from_osis("gen").is_err());
+        assert!(BibleBook::from_osis("Tob").is_err());
+    }
+
+    #[test]
+    fn test_usfm() {
+        assert_eq!(BibleBook::Genesis.usfm_code(), "GEN");
+        assert_eq!(BibleBook::Exodus.usfm_code(), "EXO");
+        assert_eq!(BibleBook::FirstSamuel.usfm_code(), "1SA");
+        assert_eq!(BibleBook::SongofSongs.usfm_code(), "SNG");
+        assert_eq!(BibleBook::Philippians.usfm_code(), "PHP");
+        assert_eq!(BibleBook::Revelation.usfm_code(), "REV");
+        for book in BibleBook::iter() {
+            assert_eq!(BibleBook::from_usfm_code(book.usfm_code()).unwrap(), book);
+            assert_eq!(
+                BibleBook::from_usfm_number(book.usfm_number()).unwrap(),
+                book
+            );
+        }
+        assert_eq!(BibleBook::Revelation.usfm_number(), 67);
+        assert!(BibleBook::from_usfm_code("TOB").is_err());
+        assert!(BibleBook::from_usfm_number(0).is_err());
+        assert!(BibleBook::from_usfm_number(40).is_err());
+        assert!(BibleBook::from_usfm_number(68).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_try_into() {
         fn is_single_chapter_book(value: impl TryInto<BibleBook>) -> Option<bool> {
             match value.try_into() {
@@ -518,6 +809,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_ord() {
         // lt
         assert!(BibleBook::Genesis < BibleBook::Exodus);
@@ -551,5 +843,10 @@ mod tests {
     fn test_display() {
         let display = format!("{}", BibleBook::FirstKings);
         assert_eq!(display, "1 Kings");
+        let display = format!(
+            "{}",
+            BibleBook::FirstKings.display_with(&ReferenceStyle::CONTINENTAL)
+        );
+        assert_eq!(display, "1. Könige");
     }
 }
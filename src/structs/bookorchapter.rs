@@ -3,7 +3,9 @@ use std::str::FromStr;
 
 use super::book::BibleBook;
 use super::chapter::BibleChapter;
-use super::errors::ParseError;
+use super::errors::{NoSuchBookError, ParseError};
+use super::locale::Locale;
+use super::style::{ReferenceStyle, StyledDisplay};
 
 #[allow(dead_code)]
 #[derive(Debug, PartialEq, Eq)]
@@ -14,9 +16,19 @@ pub enum BibleBookOrChapter {
 
 impl Display for BibleBookOrChapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, &ReferenceStyle::ENGLISH)
+    }
+}
+
+impl StyledDisplay for BibleBookOrChapter {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
         match self {
-            BibleBookOrChapter::Book(book) => write!(f, "{}", book.abbrev()),
-            BibleBookOrChapter::Chapter(chapt) => write!(f, "{}", chapt),
+            BibleBookOrChapter::Book(book) => write!(f, "{}", book.abbrev_in(style.locale)),
+            BibleBookOrChapter::Chapter(chapt) => chapt.fmt_with(f, style),
         }
     }
 }
@@ -29,6 +41,45 @@ impl BibleBookOrChapter {
             Some(_) => BibleChapter::parse(text).map(BibleBookOrChapter::Chapter),
         }
     }
+
+    /// Attempt to parse a book or chapter using the book names and abbreviations
+    /// of the given locale
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleBookOrChapter, Locale};
+    /// assert_eq!(
+    ///     BibleBookOrChapter::parse_in("1. Mose", Locale::German),
+    ///     Some(BibleBookOrChapter::Book(BibleBook::Genesis))
+    /// );
+    /// ```
+    pub fn parse_in(text: &str, locale: Locale) -> Option<Self> {
+        Self::from_str_in(text, locale).ok()
+    }
+
+    /// Parse a book or chapter using the book names and abbreviations of the
+    /// given locale, returning the reason for any failure
+    pub fn from_str_in(text: &str, locale: Locale) -> Result<Self, ParseError> {
+        Self::from_str_with(text, &ReferenceStyle::for_locale(locale))
+    }
+
+    /// Attempt to parse a book or chapter using the book names of the given style
+    pub fn parse_with(text: &str, style: &ReferenceStyle) -> Option<Self> {
+        Self::from_str_with(text, style).ok()
+    }
+
+    /// Parse a book or chapter using the book names of the given style,
+    /// returning the reason for any failure
+    pub fn from_str_with(text: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        match BibleBook::split_in(text, style.locale) {
+            None => {
+                Err(NoSuchBookError::new(format!("No matching book in {}", style.locale)).into())
+            }
+            Some((book, None)) => Ok(BibleBookOrChapter::Book(book)),
+            Some(_) => BibleChapter::from_str_with(text, style).map(BibleBookOrChapter::Chapter),
+        }
+    }
 }
 
 impl FromStr for BibleBookOrChapter {
@@ -64,11 +115,41 @@ mod tests {
     }
 
     #[test]
+    fn test_parse_in() {
+        assert_eq!(
+            BibleBookOrChapter::parse_in("1. Mose", Locale::German),
+            Some(Book(Genesis))
+        );
+        assert_eq!(
+            BibleBookOrChapter::parse_in("1. Mose 1", Locale::German),
+            Some(Chapter(BibleChapter {
+                book: Genesis,
+                chapter: 1
+            }))
+        );
+        assert_eq!(
+            BibleBookOrChapter::parse_in("1 Samuel", Locale::English),
+            Some(Book(FirstSamuel))
+        );
+        assert_eq!(
+            BibleBookOrChapter::parse_in("1. Mose 51", Locale::German),
+            None
+        );
+        assert_eq!(BibleBookOrChapter::parse_in("Ge", Locale::German), None);
+    }
+
+    #[test]
+    #[allow(clippy::useless_vec)]
     fn test_display() {
         for text in vec!["Ge", "Ge 1", "Ro 12", "Rev", "Rev 20"] {
             let boc = BibleBookOrChapter::parse(text).unwrap();
             let display = format!("{}", boc);
             assert_eq!(display, text);
         }
+        for text in ["1Mo", "1Mo 1", "Röm 12", "Offb", "Offb 20"] {
+            let boc = BibleBookOrChapter::parse_with(text, &ReferenceStyle::CONTINENTAL).unwrap();
+            let display = format!("{}", boc.display_with(&ReferenceStyle::CONTINENTAL));
+            assert_eq!(display, text);
+        }
     }
 }
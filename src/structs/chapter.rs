@@ -2,12 +2,16 @@ use super::book::BibleBook;
 use super::errors::{
     ChapterOutOfRange, NoChapterSpecified, NoSuchBookError, NotANumber, ParseError,
 };
+use super::locale::Locale;
+use super::osis::{parse_osis_parts, wrong_parts};
+use super::style::{ReferenceStyle, StyledDisplay};
+use crate::CHAPTER_VERSES;
 use std::{fmt::Display, str::FromStr};
 
 /// A struct representing a chapter in the Bible
 /// Contains the [BibleBook] and the chapter number
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct BibleChapter {
     pub book: BibleBook,
     pub chapter: u8,
@@ -15,7 +19,17 @@ pub struct BibleChapter {
 
 impl Display for BibleChapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.book.abbrev(), self.chapter)
+        self.fmt_with(f, &ReferenceStyle::ENGLISH)
+    }
+}
+
+impl StyledDisplay for BibleChapter {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
+        write!(f, "{} {}", self.book.abbrev_in(style.locale), self.chapter)
     }
 }
 
@@ -36,6 +50,21 @@ impl BibleChapter {
         range.contains(&(self.chapter as u32))
     }
 
+    /// Return the number of verses in this chapter, or 0 if the book has no such chapter
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleChapter};
+    /// assert_eq!(BibleChapter::new(BibleBook::Psalms, 117).unwrap().number_of_verses(), 2);
+    /// let missing = BibleChapter { book: BibleBook::Psalms, chapter: 151 };
+    /// assert_eq!(missing.number_of_verses(), 0);
+    /// ```
+    pub fn number_of_verses(&self) -> u32 {
+        (self.chapter as usize)
+            .checked_sub(1)
+            .and_then(|index| CHAPTER_VERSES[self.book.index()].get(index))
+            .map_or(0, |verses| *verses as u32)
+    }
+
     /// Attempt to parse a Bible book and chapter from a string
     ///
     /// # Example
@@ -80,22 +109,130 @@ impl FromStr for BibleChapter {
             .ok_or_else(|| NoSuchBookError::new(String::from("No matching abbreviation")))?;
         // Result of parse_book_abbrev ends with end of string or space character
         // We can find rest of strin (if any) by looking for the first space character
-        match s.find(" ") {
+        let remain = s.find(" ").map(|pos| &s[pos + 1..]);
+        Self::from_book_and_remainder(s, book, remain)
+    }
+}
+
+#[allow(dead_code)]
+impl BibleChapter {
+    /// Attempt to parse a Bible book and chapter from a string using the
+    /// book names and abbreviations of the given locale
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleChapter, Locale};
+    /// assert_eq!(
+    ///    BibleChapter::parse_in("1. Mose 3", Locale::German),
+    ///    Some(BibleChapter {
+    ///        book: BibleBook::Genesis,
+    ///        chapter: 3
+    ///    })
+    /// );
+    /// ```
+    pub fn parse_in(text: &str, locale: Locale) -> Option<Self> {
+        Self::from_str_in(text, locale).ok()
+    }
+
+    /// Parse a Bible book and chapter from a string using the book names and
+    /// abbreviations of the given locale, returning the reason for any failure
+    pub fn from_str_in(text: &str, locale: Locale) -> Result<Self, ParseError> {
+        Self::from_str_with(text, &ReferenceStyle::for_locale(locale))
+    }
+
+    /// Attempt to parse using the book names and separators of the given style
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleChapter, ReferenceStyle};
+    /// assert_eq!(
+    ///    BibleChapter::parse_with("Offb 22", &ReferenceStyle::CONTINENTAL),
+    ///    BibleChapter::new(BibleBook::Revelation, 22)
+    /// );
+    /// ```
+    pub fn parse_with(text: &str, style: &ReferenceStyle) -> Option<Self> {
+        Self::from_str_with(text, style).ok()
+    }
+
+    /// Parse using the book names and separators of the given style,
+    /// returning the reason for any failure
+    pub fn from_str_with(text: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        let (book, remain) = BibleBook::split_in(text, style.locale)
+            .ok_or_else(|| NoSuchBookError::new(format!("No matching book in {}", style.locale)))?;
+        Self::from_book_and_remainder(text, book, remain)
+    }
+
+    /// Return the OSIS identifier for this chapter
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleChapter};
+    /// assert_eq!(BibleChapter::new(BibleBook::Psalms, 119).unwrap().to_osis(), "Ps.119");
+    /// ```
+    pub fn to_osis(&self) -> String {
+        format!("{}.{}", self.book.to_osis(), self.chapter)
+    }
+
+    /// Construct a BibleChapter from an OSIS identifier such as `Ps.119`
+    /// Any work prefix or grain is ignored.
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleChapter};
+    /// assert_eq!(
+    ///     BibleChapter::from_osis("Ps.119").unwrap(),
+    ///     BibleChapter::new(BibleBook::Psalms, 119).unwrap()
+    /// );
+    /// ```
+    pub fn from_osis(id: &str) -> Result<Self, ParseError> {
+        match parse_osis_parts(id)? {
+            (book, numbers) if numbers.len() == 1 => Ok(BibleChapter {
+                book,
+                chapter: numbers[0],
+            }),
+            _ => Err(wrong_parts(id, "chapter")),
+        }
+    }
+
+    /// Return the Paratext style reference for this chapter
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleChapter};
+    /// assert_eq!(BibleChapter::new(BibleBook::Psalms, 119).unwrap().to_usfm(), "PSA 119");
+    /// ```
+    pub fn to_usfm(&self) -> String {
+        format!("{} {}", self.book.usfm_code(), self.chapter)
+    }
+
+    /// Parse a Paratext style reference to a chapter such as `PSA 119`
+    pub fn from_usfm(text: &str) -> Result<Self, ParseError> {
+        let (book, remain) = BibleBook::split_usfm(text)
+            .ok_or_else(|| NoSuchBookError::new(format!("No matching USFM code: {}", text)))?;
+        Self::from_book_and_remainder(text, book, remain)
+    }
+
+    /// Parse the chapter that follows the book in a reference
+    /// `remain` is None if nothing followed the book
+    fn from_book_and_remainder(
+        text: &str,
+        book: BibleBook,
+        remain: Option<&str>,
+    ) -> Result<Self, ParseError> {
+        match remain {
             None => {
                 // There is no chapter specified
                 // This is invalid, unless the book only has one chapter
                 // In which case, chapter one is implicit
                 match book.number_of_chapters() {
                     1 => Ok(BibleChapter { book, chapter: 1 }),
-                    _ => Err(NoChapterSpecified::new(s.to_string()).into()),
+                    _ => Err(NoChapterSpecified::new(text.to_string()).into()),
                 }
             }
-            Some(pos) => {
-                let remain = &s[pos + 1..];
+            Some(remain) => {
                 // This should be the chapter number
                 match u8::from_str(remain) {
                     Err(_) => Err(NotANumber::new(remain.to_string()).into()),
-                    Ok(chapter) if chapter == 0 => Err(ChapterOutOfRange::new(
+                    Ok(0) => Err(ChapterOutOfRange::new(
                         "0. Chapter numbers start at 1".to_string(),
                     )
                     .into()),
@@ -153,6 +290,78 @@ mod tests {
         ); // But we should also be able to specify it explicitly
     }
 
+    #[test]
+    fn test_parse_in() {
+        assert_eq!(
+            BibleChapter::parse_in("1. Mose 50", Locale::German),
+            BibleChapter::new(BibleBook::Genesis, 50)
+        );
+        assert_eq!(
+            BibleChapter::parse_in("Apocalipsis 22", Locale::Spanish),
+            BibleChapter::new(BibleBook::Revelation, 22)
+        );
+        assert_eq!(
+            BibleChapter::parse_in("시편 23", Locale::Korean),
+            BibleChapter::new(BibleBook::Psalms, 23)
+        );
+        assert_eq!(
+            BibleChapter::parse_in("1 Samuel 3", Locale::English),
+            BibleChapter::new(BibleBook::FirstSamuel, 3)
+        );
+        assert_eq!(
+            BibleChapter::parse_in("犹大书", Locale::Chinese),
+            BibleChapter::new(BibleBook::Jude, 1)
+        );
+        assert_eq!(BibleChapter::parse_in("1. Mose 51", Locale::German), None);
+        assert_eq!(BibleChapter::parse_in("1. Mose", Locale::German), None);
+        assert_eq!(BibleChapter::parse_in("Genesis 1", Locale::German), None);
+        assert!(matches!(
+            BibleChapter::from_str_in("Genesis 1", Locale::German),
+            Err(ParseError::NoSuchBookError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_with() {
+        assert_eq!(
+            BibleChapter::parse_with("Offb 22", &ReferenceStyle::CONTINENTAL),
+            BibleChapter::new(BibleBook::Revelation, 22)
+        );
+        assert_eq!(
+            BibleChapter::parse_with("Revelation 22", &ReferenceStyle::PERIOD),
+            BibleChapter::new(BibleBook::Revelation, 22)
+        );
+    }
+
+    #[test]
+    fn test_osis() {
+        for book in BibleBook::iter() {
+            let last = BibleChapter::new(book, book.number_of_chapters() as u8).unwrap();
+            assert_eq!(BibleChapter::from_osis(&last.to_osis()).unwrap(), last);
+        }
+        assert_eq!(
+            BibleChapter::from_osis("KJV:Ps.119").unwrap(),
+            BibleChapter::new(BibleBook::Psalms, 119).unwrap()
+        );
+        assert!(BibleChapter::from_osis("Ps.151").is_err());
+        assert!(BibleChapter::from_osis("Ps").is_err());
+        assert!(BibleChapter::from_osis("Ps.119.1").is_err());
+    }
+
+    #[test]
+    fn test_usfm() {
+        for book in BibleBook::iter() {
+            let last = BibleChapter::new(book, book.number_of_chapters() as u8).unwrap();
+            assert_eq!(BibleChapter::from_usfm(&last.to_usfm()).unwrap(), last);
+        }
+        assert_eq!(
+            BibleChapter::from_usfm("PSA 119").unwrap(),
+            BibleChapter::new(BibleBook::Psalms, 119).unwrap()
+        );
+        assert!(BibleChapter::from_usfm("PSA 151").is_err());
+        assert!(BibleChapter::from_usfm("Ps 119").is_err());
+    }
+
     #[test]
     fn test_ord() {
         let gen1 = BibleChapter::new(BibleBook::Genesis, 1);
@@ -194,6 +403,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_number_of_verses() {
+        let chapter = |book, chapter| BibleChapter { book, chapter }.number_of_verses();
+        assert_eq!(chapter(BibleBook::Genesis, 1), 31);
+        assert_eq!(chapter(BibleBook::Psalms, 119), 176);
+        assert_eq!(chapter(BibleBook::Jude, 1), 25);
+        assert_eq!(chapter(BibleBook::Genesis, 0), 0);
+        assert_eq!(chapter(BibleBook::Genesis, 51), 0);
+        assert_eq!(chapter(BibleBook::Jude, u8::MAX), 0);
+    }
+
     #[test]
     fn test_new() {
         assert!(BibleChapter::new(BibleBook::Genesis, 50).is_some());
@@ -204,6 +424,13 @@ mod tests {
     #[test]
     fn test_display() {
         let display = format!("{}", BibleChapter::new(BibleBook::Eccesiastes, 2).unwrap());
-        assert_eq!(display, "Ecc 2")
+        assert_eq!(display, "Ecc 2");
+        let display = format!(
+            "{}",
+            BibleChapter::new(BibleBook::Eccesiastes, 2)
+                .unwrap()
+                .display_with(&ReferenceStyle::CONTINENTAL)
+        );
+        assert_eq!(display, "Pred 2");
     }
 }
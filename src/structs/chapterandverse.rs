@@ -1,9 +1,10 @@
 use super::errors::{InvalidFormat, NotANumber, ParseError};
+use super::style::{ReferenceStyle, StyledDisplay};
 use std::cmp::Ordering;
 use std::{fmt::Display, str::FromStr};
 
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct ChapterAndVerse {
     pub chapter: u8,
     pub verse: u8,
@@ -11,7 +12,17 @@ pub struct ChapterAndVerse {
 
 impl Display for ChapterAndVerse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.chapter, self.verse)
+        self.fmt_with(f, &ReferenceStyle::ENGLISH)
+    }
+}
+
+impl StyledDisplay for ChapterAndVerse {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.chapter, style.chapter_verse, self.verse)
     }
 }
 
@@ -40,18 +51,19 @@ impl ChapterAndVerse {
     pub fn new(chapter: u8, verse: u8) -> Self {
         ChapterAndVerse { chapter, verse }
     }
-}
 
-impl FromStr for ChapterAndVerse {
-    type Err = ParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.find(":") {
-            None => {
-                Err(InvalidFormat::new(format!("No ':' found for chapter and verse: {}", s)).into())
-            }
+    /// Parse a chapter and verse using the separator of the given style
+    pub fn from_str_with(s: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        let separator = style.chapter_verse;
+        match s.find(separator) {
+            None => Err(InvalidFormat::new(format!(
+                "No '{}' found for chapter and verse: {}",
+                separator, s
+            ))
+            .into()),
             Some(pos) => {
                 let before = &s[..pos];
-                let after = &s[pos + 1..];
+                let after = &s[pos + separator.len()..];
                 let chapter = u8::from_str(before).map_err(|_| {
                     NotANumber::new(format!("Invalid number for chapter: {}", before))
                 })?;
@@ -63,6 +75,13 @@ impl FromStr for ChapterAndVerse {
     }
 }
 
+impl FromStr for ChapterAndVerse {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &ReferenceStyle::ENGLISH)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +109,28 @@ mod tests {
         assert_eq!(ChapterAndVerse::parse(":1"), None);
         assert_eq!(ChapterAndVerse::parse(":"), None);
         assert_eq!(ChapterAndVerse::parse(""), None);
+        assert_eq!(ChapterAndVerse::parse("1 : 1"), None);
+        assert_eq!(ChapterAndVerse::parse(" 1:1"), None);
+    }
+
+    #[test]
+    fn test_from_str_with() {
+        assert_eq!(
+            ChapterAndVerse::from_str_with("3,16", &ReferenceStyle::CONTINENTAL).unwrap(),
+            ChapterAndVerse::new(3, 16)
+        );
+        assert_eq!(
+            ChapterAndVerse::from_str_with("3.16", &ReferenceStyle::PERIOD).unwrap(),
+            ChapterAndVerse::new(3, 16)
+        );
+        assert!(ChapterAndVerse::from_str_with("3:16", &ReferenceStyle::CONTINENTAL).is_err());
+        assert!(ChapterAndVerse::from_str_with("3 , 16", &ReferenceStyle::CONTINENTAL).is_err());
+        assert_eq!(
+            ChapterAndVerse::new(3, 16)
+                .display_with(&ReferenceStyle::CONTINENTAL)
+                .to_string(),
+            "3,16"
+        );
     }
 
     #[test]
@@ -2,6 +2,7 @@ use crate::structs::errors::NotANumber;
 
 use super::chapterandverse::ChapterAndVerse;
 use super::errors::ParseError;
+use super::style::ReferenceStyle;
 use std::str::FromStr;
 
 #[allow(dead_code)]
@@ -16,17 +17,22 @@ impl ChapterAndVerseOrVerse {
     pub fn parse(text: &str) -> Option<Self> {
         text.parse().ok()
     }
+
+    /// Parse a chapter and verse, or just a verse, using the separator of the given style
+    pub fn from_str_with(s: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        match s.find(style.chapter_verse) {
+            None => u8::from_str(s.trim())
+                .map(ChapterAndVerseOrVerse::JustVerse)
+                .map_err(|_| NotANumber::new(s.to_string()).into()),
+            Some(_) => ChapterAndVerse::from_str_with(s, style).map(ChapterAndVerseOrVerse::Both),
+        }
+    }
 }
 
 impl FromStr for ChapterAndVerseOrVerse {
     type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.find(":") {
-            None => u8::from_str(s)
-                .map(ChapterAndVerseOrVerse::JustVerse)
-                .map_err(|_| NotANumber::new(s.to_string()).into()),
-            Some(_) => ChapterAndVerse::from_str(s).map(ChapterAndVerseOrVerse::Both),
-        }
+        Self::from_str_with(s, &ReferenceStyle::ENGLISH)
     }
 }
 
@@ -3,11 +3,13 @@ use crate::structs::errors::{ImplicitRange, InvalidFormat};
 use super::chapterandverse::ChapterAndVerse;
 use super::chapterandverseorverse::ChapterAndVerseOrVerse;
 use super::errors::ParseError;
+use super::style::ReferenceStyle;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 #[allow(dead_code)]
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChapterAndVerseRange(pub RangeInclusive<ChapterAndVerse>);
 
 /// Chapter and Verse ranges may be full or implicit
@@ -33,18 +35,15 @@ impl ChapterAndVerseRange {
             Err(_) => None,
         }
     }
-}
 
-impl FromStr for ChapterAndVerseRange {
-    type Err = ParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.find("-") {
+    /// Parse a full range using the separators of the given style
+    /// Implicit ranges are returned as [ImplicitRange] errors, as with [FromStr]
+    pub fn from_str_with(s: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        match s.find(style.range) {
             None => {
                 // Single verse range
-                match ChapterAndVerseOrVerse::from_str(s)? {
-                    ChapterAndVerseOrVerse::Both(cv) => {
-                        Ok(ChapterAndVerseRange(cv.clone()..=cv.clone()))
-                    }
+                match ChapterAndVerseOrVerse::from_str_with(s, style)? {
+                    ChapterAndVerseOrVerse::Both(cv) => Ok(ChapterAndVerseRange(cv..=cv)),
                     ChapterAndVerseOrVerse::JustVerse(v) => {
                         Err(ImplicitRange::new(ChapterAndVerseRange(
                             ChapterAndVerse::new(1, v)..=ChapterAndVerse::new(1, v),
@@ -55,9 +54,9 @@ impl FromStr for ChapterAndVerseRange {
             }
             Some(pos) => {
                 let start = &s[..pos];
-                let end = &s[pos + 1..];
-                let cvv_start = ChapterAndVerseOrVerse::from_str(start)?;
-                let cvv_end = ChapterAndVerseOrVerse::from_str(end)?;
+                let end = &s[pos + style.range.len()..];
+                let cvv_start = ChapterAndVerseOrVerse::from_str_with(start, style)?;
+                let cvv_end = ChapterAndVerseOrVerse::from_str_with(end, style)?;
                 let mut implicit = false;
                 let cv_start = match cvv_start {
                     ChapterAndVerseOrVerse::Both(cv) => cv,
@@ -95,6 +94,13 @@ impl FromStr for ChapterAndVerseRange {
     }
 }
 
+impl FromStr for ChapterAndVerseRange {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &ReferenceStyle::ENGLISH)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,222 @@
+//! Mod for the compact serde representation of the reference types
+//!
+//! Only built with the `serde` feature. By default the reference types are serialized
+//! as strings. Fields marked `#[serde(with = "bible_data::compact")]` are instead
+//! serialized as numbers, which suits binary formats and large stores of references:
+//!
+//! | Type                    | Compact form                                  |
+//! |-------------------------|-----------------------------------------------|
+//! | [BibleBook]             | The book number, `43`                         |
+//! | [BibleChapter]          | Book and chapter, `[43, 3]`                   |
+//! | [ChapterAndVerse]       | Chapter and verse, `[3, 16]`                  |
+//! | [BibleVerse]            | `book * 1000000 + chapter * 1000 + verse`, `43003016` |
+//! | [BibleVerseRange]       | The first and last verses, `[43003016, 43003018]` |
+//! | [BibleVerseRangeList]   | A list of ranges, `[[45008028, 45008028], [45008031, 45008039]]` |
+//!
+//! Deserializing checks that the references are valid.
+//!
+//! # Example
+//! ```rust
+//! use bible_data::{BibleBook, BibleVerse};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Bookmark {
+//!     #[serde(with = "bible_data::compact")]
+//!     verse: BibleVerse,
+//! }
+//! ```
+use super::book::BibleBook;
+use super::chapter::BibleChapter;
+use super::chapterandverse::ChapterAndVerse;
+use super::verse::BibleVerse;
+use super::verserange::BibleVerseRange;
+use super::verserangelist::BibleVerseRangeList;
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A type with a compact numeric representation
+pub trait Compact: Sized {
+    type Repr: Serialize + DeserializeOwned;
+
+    fn to_compact(&self) -> Self::Repr;
+
+    /// Returns the value, or a message saying why the representation is not valid
+    fn from_compact(repr: Self::Repr) -> Result<Self, String>;
+}
+
+/// Serialize a value in its compact form
+pub fn serialize<T: Compact, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.to_compact().serialize(serializer)
+}
+
+/// Deserialize a value from its compact form
+pub fn deserialize<'de, T: Compact, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    T::from_compact(T::Repr::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+impl Compact for BibleBook {
+    type Repr = u8;
+
+    fn to_compact(&self) -> u8 {
+        *self as u8
+    }
+
+    fn from_compact(repr: u8) -> Result<Self, String> {
+        BibleBook::from_book_number(repr as u32).map_err(|e| e.to_string())
+    }
+}
+
+impl Compact for BibleChapter {
+    type Repr = (u8, u8);
+
+    fn to_compact(&self) -> (u8, u8) {
+        (self.book.to_compact(), self.chapter)
+    }
+
+    fn from_compact((book, chapter): (u8, u8)) -> Result<Self, String> {
+        let book = BibleBook::from_compact(book)?;
+        BibleChapter::new(book, chapter)
+            .ok_or_else(|| format!("{} has no chapter {}", book, chapter))
+    }
+}
+
+impl Compact for ChapterAndVerse {
+    type Repr = (u8, u8);
+
+    fn to_compact(&self) -> (u8, u8) {
+        (self.chapter, self.verse)
+    }
+
+    fn from_compact((chapter, verse): (u8, u8)) -> Result<Self, String> {
+        match chapter > 0 && verse > 0 {
+            true => Ok(ChapterAndVerse::new(chapter, verse)),
+            false => Err(format!("{}:{} is not a verse", chapter, verse)),
+        }
+    }
+}
+
+impl Compact for BibleVerse {
+    type Repr = u32;
+
+    fn to_compact(&self) -> u32 {
+        self.book as u32 * 1_000_000 + self.chapter as u32 * 1000 + self.verse as u32
+    }
+
+    fn from_compact(repr: u32) -> Result<Self, String> {
+        let book = BibleBook::from_book_number(repr / 1_000_000).map_err(|e| e.to_string())?;
+        let chapter = u8::try_from((repr / 1000) % 1000);
+        let verse = u8::try_from(repr % 1000);
+        match (chapter, verse) {
+            (Ok(chapter), Ok(verse)) if BibleVerse::new(book, chapter, verse).is_valid() => {
+                Ok(BibleVerse::new(book, chapter, verse))
+            }
+            _ => Err(format!("{} is not a verse", repr)),
+        }
+    }
+}
+
+impl Compact for BibleVerseRange {
+    type Repr = (u32, u32);
+
+    fn to_compact(&self) -> (u32, u32) {
+        let start = BibleVerse::new(
+            self.book,
+            self.range.start().chapter,
+            self.range.start().verse,
+        );
+        let end = BibleVerse::new(self.book, self.range.end().chapter, self.range.end().verse);
+        (start.to_compact(), end.to_compact())
+    }
+
+    fn from_compact((start, end): (u32, u32)) -> Result<Self, String> {
+        let start = BibleVerse::from_compact(start)?;
+        let end = BibleVerse::from_compact(end)?;
+        if start.book != end.book {
+            return Err("Range must be within a single book".to_string());
+        }
+        if start > end {
+            return Err("End verse before start".to_string());
+        }
+        Ok(BibleVerseRange::new(
+            start.book,
+            start.chapter,
+            start.verse,
+            end.chapter,
+            end.verse,
+        ))
+    }
+}
+
+impl Compact for BibleVerseRangeList {
+    type Repr = Vec<(u32, u32)>;
+
+    fn to_compact(&self) -> Vec<(u32, u32)> {
+        self.iter().map(|range| range.to_compact()).collect()
+    }
+
+    fn from_compact(repr: Vec<(u32, u32)>) -> Result<Self, String> {
+        let mut ranges = repr.into_iter().map(BibleVerseRange::from_compact);
+        let first = ranges.next().ok_or("A range list cannot be empty")??;
+        let mut list = BibleVerseRangeList {
+            book: first.book,
+            ranges: vec![first.range],
+        };
+        for range in ranges {
+            let range = range?;
+            if range.book != list.book {
+                return Err("Range list must be within a single book".to_string());
+            }
+            list.ranges.push(range.range);
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "super")]
+        book: BibleBook,
+        #[serde(with = "super")]
+        chapter: BibleChapter,
+        #[serde(with = "super")]
+        verse: BibleVerse,
+        #[serde(with = "super")]
+        range: BibleVerseRange,
+        #[serde(with = "super")]
+        list: BibleVerseRangeList,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let record = Record {
+            book: BibleBook::John,
+            chapter: BibleChapter::new(BibleBook::John, 3).unwrap(),
+            verse: BibleVerse::new(BibleBook::John, 3, 16),
+            range: BibleVerseRange::new(BibleBook::John, 3, 16, 3, 18),
+            list: BibleVerseRangeList::parse("Ro 8:28, 31-39").unwrap(),
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            json,
+            r#"{"book":43,"chapter":[43,3],"verse":43003016,"range":[43003016,43003018],"list":[[45008028,45008028],[45008031,45008039]]}"#
+        );
+        assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(BibleBook::from_compact(67).is_err());
+        assert!(BibleChapter::from_compact((43, 22)).is_err());
+        assert!(ChapterAndVerse::from_compact((0, 1)).is_err());
+        assert!(BibleVerse::from_compact(43003099).is_err());
+        assert!(BibleVerse::from_compact(43300001).is_err());
+        assert!(BibleVerseRange::from_compact((43003018, 43003016)).is_err());
+        assert!(BibleVerseRange::from_compact((42001001, 43003016)).is_err());
+        assert!(BibleVerseRangeList::from_compact(vec![]).is_err());
+    }
+}
@@ -6,6 +6,7 @@ macro_rules! create_error {
     ($error_name:ident) => {
         #[allow(dead_code)]
         #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub struct $error_name {
             message: String,
         }
@@ -21,11 +22,16 @@ macro_rules! create_error {
             pub fn new(message: String) -> Self {
                 $error_name { message }
             }
+
+            pub fn message(&self) -> &str {
+                &self.message
+            }
         }
     };
     ($error_name:ident<$data_type:ident>) => {
         #[allow(dead_code)]
         #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub struct $error_name {
             data: $data_type,
         }
@@ -50,6 +56,7 @@ macro_rules! create_error {
     ($error_type:ident : $($sub_type:ident),+) => {
         #[allow(dead_code)]
         #[derive(Debug)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum $error_type {
             $($sub_type($sub_type)),+
         }
@@ -73,11 +80,13 @@ macro_rules! create_error {
 
 create_error!(OutOfRangeError);
 create_error!(NoSuchBookError);
+create_error!(NoSuchLocaleError);
 create_error!(NoChapterSpecified);
 create_error!(NotANumber);
 create_error!(ChapterOutOfRange);
 create_error!(InvalidFormat);
 create_error!(InvalidRange);
+create_error!(QueryError);
 create_error!(ImplicitRange<ChapterAndVerseRange>);
 
 create_error!(ParseError : NoSuchBookError, NoChapterSpecified, NotANumber, ChapterOutOfRange, InvalidFormat, ImplicitRange, InvalidRange);
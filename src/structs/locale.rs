@@ -0,0 +1,160 @@
+use super::errors::NoSuchLocaleError;
+use crate::locales::{de, es, fr, ko, pt, zh};
+use crate::{BOOK_ABBREVS, BOOK_NAMES};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Enum representing a language for which this crate has book names and abbreviations
+///
+/// Locales can be constructed from a locale identifier such as `"de"`, `"pt-BR"` or
+/// `"zh_Hans_CN"` with [from_identifier](Locale::from_identifier) or by parsing.
+/// Only the primary language subtag is significant.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+    Portuguese,
+    German,
+    French,
+    Korean,
+    /// Simplified Chinese
+    Chinese,
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.identifier())
+    }
+}
+
+#[allow(dead_code)]
+impl Locale {
+    /// Return the ISO 639-1 language code for this locale
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::Locale;
+    /// assert_eq!(Locale::German.identifier(), "de");
+    /// ```
+    pub fn identifier(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+            Locale::Portuguese => "pt",
+            Locale::German => "de",
+            Locale::French => "fr",
+            Locale::Korean => "ko",
+            Locale::Chinese => "zh",
+        }
+    }
+
+    /// Construct a Locale from a locale identifier
+    ///
+    /// Accepts a bare language code or a full identifier with region or script
+    /// subtags separated by `-` or `_`. Matching is case-insensitive.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::Locale;
+    /// assert_eq!(Locale::from_identifier("es").unwrap(), Locale::Spanish);
+    /// assert_eq!(Locale::from_identifier("pt-BR").unwrap(), Locale::Portuguese);
+    /// assert_eq!(Locale::from_identifier("zh_Hans_CN").unwrap(), Locale::Chinese);
+    /// assert!(Locale::from_identifier("xx").is_none());
+    /// ```
+    pub fn from_identifier(identifier: &str) -> Option<Self> {
+        let language = identifier.split(['-', '_']).next()?;
+        Self::iter().find(|locale| locale.identifier().eq_ignore_ascii_case(language))
+    }
+
+    /// Return the book names for this locale, in canonical order
+    pub fn book_names(&self) -> &'static [&'static str; 66] {
+        match self {
+            Locale::English => &BOOK_NAMES,
+            Locale::Spanish => &es::BOOK_NAMES,
+            Locale::Portuguese => &pt::BOOK_NAMES,
+            Locale::German => &de::BOOK_NAMES,
+            Locale::French => &fr::BOOK_NAMES,
+            Locale::Korean => &ko::BOOK_NAMES,
+            Locale::Chinese => &zh::BOOK_NAMES,
+        }
+    }
+
+    /// Return the book abbreviations for this locale, in canonical order
+    pub fn book_abbrevs(&self) -> &'static [&'static str; 66] {
+        match self {
+            Locale::English => &BOOK_ABBREVS,
+            Locale::Spanish => &es::BOOK_ABBREVS,
+            Locale::Portuguese => &pt::BOOK_ABBREVS,
+            Locale::German => &de::BOOK_ABBREVS,
+            Locale::French => &fr::BOOK_ABBREVS,
+            Locale::Korean => &ko::BOOK_ABBREVS,
+            Locale::Chinese => &zh::BOOK_ABBREVS,
+        }
+    }
+
+    /// Return an iterator over all the supported locales
+    pub fn iter() -> impl Iterator<Item = Locale> {
+        [
+            Locale::English,
+            Locale::Spanish,
+            Locale::Portuguese,
+            Locale::German,
+            Locale::French,
+            Locale::Korean,
+            Locale::Chinese,
+        ]
+        .into_iter()
+    }
+}
+
+impl TryFrom<&str> for Locale {
+    type Error = NoSuchLocaleError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl FromStr for Locale {
+    type Err = NoSuchLocaleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_identifier(s).ok_or_else(|| NoSuchLocaleError::new(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_identifier() {
+        assert_eq!(Locale::from_identifier("en"), Some(Locale::English));
+        assert_eq!(Locale::from_identifier("en-GB"), Some(Locale::English));
+        assert_eq!(Locale::from_identifier("ES"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_identifier("es_MX"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_identifier("pt-BR"), Some(Locale::Portuguese));
+        assert_eq!(Locale::from_identifier("de-CH"), Some(Locale::German));
+        assert_eq!(Locale::from_identifier("fr"), Some(Locale::French));
+        assert_eq!(Locale::from_identifier("ko-KR"), Some(Locale::Korean));
+        assert_eq!(Locale::from_identifier("zh-Hans"), Some(Locale::Chinese));
+        assert_eq!(Locale::from_identifier("xx"), None);
+        assert_eq!(Locale::from_identifier(""), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for locale in Locale::iter() {
+            assert_eq!(locale.identifier().parse::<Locale>().unwrap(), locale);
+            assert_eq!(format!("{}", locale), locale.identifier());
+        }
+        assert!("klingon".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_tables() {
+        assert_eq!(Locale::English.book_names()[0], "Genesis");
+        assert_eq!(Locale::German.book_names()[0], "1. Mose");
+        assert_eq!(Locale::Korean.book_abbrevs()[65], "계");
+    }
+}
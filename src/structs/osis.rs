@@ -0,0 +1,135 @@
+use super::book::BibleBook;
+use super::errors::{ChapterOutOfRange, InvalidFormat, NotANumber, ParseError};
+use std::fmt::Display;
+
+/// A single OSIS identifier split into its parts
+///
+/// OSIS identifiers may be prefixed by the work they refer to and suffixed by a grain
+/// identifying part of a verse. eg. `Bible.KJV:Gen.1.1!a` has the work `Bible.KJV`,
+/// the id `Gen.1.1` and the grain `a`. References in osisRef attributes use `@` rather
+/// than `!` for the grain; both are accepted.
+///
+/// # Example
+/// ```rust
+/// use bible_data::OsisId;
+/// let id = OsisId::split("Bible.KJV:Gen.1.1!a");
+/// assert_eq!(id.work, Some("Bible.KJV"));
+/// assert_eq!(id.id, "Gen.1.1");
+/// assert_eq!(id.grain, Some("a"));
+/// assert_eq!(id.to_string(), "Bible.KJV:Gen.1.1!a");
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OsisId<'a> {
+    pub work: Option<&'a str>,
+    pub id: &'a str,
+    pub grain: Option<&'a str>,
+}
+
+impl Display for OsisId<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(work) = self.work {
+            write!(f, "{}:", work)?;
+        }
+        write!(f, "{}", self.id)?;
+        if let Some(grain) = self.grain {
+            write!(f, "!{}", grain)?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(dead_code)]
+impl<'a> OsisId<'a> {
+    /// Split an OSIS identifier into work, id and grain
+    pub fn split(text: &'a str) -> Self {
+        let (work, rest) = match text.split_once(':') {
+            Some((work, rest)) => (Some(work), rest),
+            None => (None, text),
+        };
+        let (id, grain) = match rest.split_once(['!', '@']) {
+            Some((id, grain)) => (id, Some(grain)),
+            None => (rest, None),
+        };
+        OsisId { work, id, grain }
+    }
+}
+
+/// Parse the book and chapter/verse numbers of an OSIS identifier
+/// Any work prefix or grain is ignored.
+pub(crate) fn parse_osis_parts(text: &str) -> Result<(BibleBook, Vec<u8>), ParseError> {
+    let id = OsisId::split(text).id;
+    let mut parts = id.split('.');
+    let book = BibleBook::from_osis(parts.next().unwrap_or_default())?;
+    let numbers = parts
+        .map(|part| {
+            part.parse::<u8>()
+                .map_err(|_| NotANumber::new(format!("Invalid number in OSIS id: {}", id)))
+        })
+        .collect::<Result<Vec<u8>, NotANumber>>()?;
+    if let Some(&chapter) = numbers.first()
+        && !(1..=book.number_of_chapters()).contains(&(chapter as u32))
+    {
+        return Err(ChapterOutOfRange::new(format!(
+            "{} has {} chapters. {} is out of range.",
+            book.name(),
+            book.number_of_chapters(),
+            chapter
+        ))
+        .into());
+    }
+    Ok((book, numbers))
+}
+
+/// Error for an OSIS id with the wrong number of parts for the type being parsed
+pub(crate) fn wrong_parts(text: &str, expected: &str) -> ParseError {
+    InvalidFormat::new(format!("Expected OSIS {} id: {}", expected, text)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            OsisId::split("Gen.1.1"),
+            OsisId {
+                work: None,
+                id: "Gen.1.1",
+                grain: None
+            }
+        );
+        assert_eq!(
+            OsisId::split("KJV:Ps.119"),
+            OsisId {
+                work: Some("KJV"),
+                id: "Ps.119",
+                grain: None
+            }
+        );
+        assert_eq!(
+            OsisId::split("John.3.16@s[love]"),
+            OsisId {
+                work: None,
+                id: "John.3.16",
+                grain: Some("s[love]")
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_osis_parts() {
+        assert_eq!(
+            parse_osis_parts("Bible:Gen.1.2!b").unwrap(),
+            (BibleBook::Genesis, vec![1, 2])
+        );
+        assert_eq!(
+            parse_osis_parts("Rev").unwrap(),
+            (BibleBook::Revelation, vec![])
+        );
+        assert!(parse_osis_parts("Gen.51.1").is_err());
+        assert!(parse_osis_parts("Gen.x").is_err());
+        assert!(parse_osis_parts("Genesis.1").is_err());
+    }
+}
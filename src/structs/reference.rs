@@ -0,0 +1,270 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::book::BibleBook;
+use super::bookorchapter::BibleBookOrChapter;
+use super::chapter::BibleChapter;
+use super::errors::ParseError;
+use super::locale::Locale;
+use super::style::{ReferenceStyle, StyledDisplay};
+use super::verse::BibleVerse;
+use super::verserange::BibleVerseRange;
+use super::verserangelist::BibleVerseRangeList;
+
+/// Any reference, from a whole book down to a single verse or a list of ranges
+///
+/// Parsing finds the most specific kind of reference the text is, so it suits input
+/// where any kind of reference may be written.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleChapter, BibleReference};
+/// let reference = BibleReference::parse("John 3").unwrap();
+/// assert_eq!(reference, BibleReference::Chapter(BibleChapter::new(BibleBook::John, 3).unwrap()));
+/// assert_eq!(reference.kind(), "chapter");
+/// assert_eq!(BibleReference::parse("Ro 8:28, 31-39").unwrap().kind(), "list");
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BibleReference {
+    Book(BibleBook),
+    Chapter(BibleChapter),
+    Verse(BibleVerse),
+    Range(BibleVerseRange),
+    List(BibleVerseRangeList),
+}
+
+#[allow(dead_code)]
+impl BibleReference {
+    pub fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    /// Attempt to parse a reference using the conventions of the given locale
+    pub fn parse_in(text: &str, locale: Locale) -> Option<Self> {
+        Self::from_str_in(text, locale).ok()
+    }
+
+    /// Parse a reference using the conventions of the given locale,
+    /// returning the reason for any failure
+    pub fn from_str_in(text: &str, locale: Locale) -> Result<Self, ParseError> {
+        Self::from_str_with(text, &ReferenceStyle::for_locale(locale))
+    }
+
+    /// Attempt to parse a reference using the book names and separators of the given style
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleReference, ReferenceStyle};
+    /// let reference = BibleReference::parse_with("Röm 8,28.31-39", &ReferenceStyle::CONTINENTAL);
+    /// assert_eq!(reference.unwrap().to_string(), "Ro 8:28, 31-39");
+    /// ```
+    pub fn parse_with(text: &str, style: &ReferenceStyle) -> Option<Self> {
+        Self::from_str_with(text, style).ok()
+    }
+
+    /// Parse a reference using the book names and separators of the given style,
+    /// returning the reason for any failure
+    ///
+    /// If nothing matches, the error is the one from parsing as a book or chapter when the
+    /// text has no chapter and verse separator, and from parsing as a range otherwise.
+    pub fn from_str_with(text: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        let book_or_chapter = match BibleBookOrChapter::from_str_with(text, style) {
+            Ok(BibleBookOrChapter::Book(book)) => return Ok(BibleReference::Book(book)),
+            Ok(BibleBookOrChapter::Chapter(chapter)) => {
+                return Ok(BibleReference::Chapter(chapter));
+            }
+            Err(e) => e,
+        };
+        if let Ok(verse) = BibleVerse::from_str_with(text, style) {
+            return Ok(BibleReference::Verse(verse));
+        }
+        let range = match BibleVerseRange::from_str_with(text, style) {
+            Ok(range) => return Ok(BibleReference::Range(range)),
+            Err(e) => e,
+        };
+        match BibleVerseRangeList::from_str_with(text, style) {
+            Ok(list) => Ok(BibleReference::List(list)),
+            Err(_) if !text.contains(style.chapter_verse) => Err(book_or_chapter),
+            Err(_) => Err(range),
+        }
+    }
+
+    /// Return the name of the kind of reference, eg. `"verse"`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BibleReference::Book(_) => "book",
+            BibleReference::Chapter(_) => "chapter",
+            BibleReference::Verse(_) => "verse",
+            BibleReference::Range(_) => "range",
+            BibleReference::List(_) => "list",
+        }
+    }
+
+    /// Return the book the reference is in
+    pub fn book(&self) -> BibleBook {
+        match self {
+            BibleReference::Book(book) => *book,
+            BibleReference::Chapter(chapter) => chapter.book,
+            BibleReference::Verse(verse) => verse.book,
+            BibleReference::Range(range) => range.book,
+            BibleReference::List(list) => list.book,
+        }
+    }
+
+    /// Return the ranges of verses the reference covers
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleReference, BibleVerseRange};
+    /// assert_eq!(
+    ///     BibleReference::parse("Ps 117").unwrap().ranges(),
+    ///     [BibleVerseRange::new(BibleBook::Psalms, 117, 1, 117, 2)]
+    /// );
+    /// ```
+    pub fn ranges(&self) -> Vec<BibleVerseRange> {
+        let verses =
+            |book: BibleBook, chapter: u8| BibleChapter { book, chapter }.number_of_verses() as u8;
+        match self {
+            BibleReference::Book(book) => {
+                let last = book.number_of_chapters() as u8;
+                vec![BibleVerseRange::new(*book, 1, 1, last, verses(*book, last))]
+            }
+            BibleReference::Chapter(BibleChapter { book, chapter }) => vec![BibleVerseRange::new(
+                *book,
+                *chapter,
+                1,
+                *chapter,
+                verses(*book, *chapter),
+            )],
+            BibleReference::Verse(verse) => vec![BibleVerseRange::new(
+                verse.book,
+                verse.chapter,
+                verse.verse,
+                verse.chapter,
+                verse.verse,
+            )],
+            BibleReference::Range(range) => vec![range.clone()],
+            BibleReference::List(list) => list.iter().collect(),
+        }
+    }
+
+    /// Return the OSIS reference
+    /// The ranges of a list are separated by spaces, as in an `osisID`.
+    ///
+    /// ```rust
+    /// use bible_data::BibleReference;
+    /// assert_eq!(BibleReference::parse("1 Kings").unwrap().to_osis(), "1Kgs");
+    /// assert_eq!(
+    ///     BibleReference::parse("Ro 8:28, 31-39").unwrap().to_osis(),
+    ///     "Rom.8.28 Rom.8.31-Rom.8.39"
+    /// );
+    /// ```
+    pub fn to_osis(&self) -> String {
+        match self {
+            BibleReference::Book(book) => book.to_osis().to_string(),
+            BibleReference::Chapter(chapter) => chapter.to_osis(),
+            BibleReference::Verse(verse) => verse.to_osis(),
+            BibleReference::Range(range) => range.to_osis(),
+            BibleReference::List(list) => list
+                .iter()
+                .map(|range| range.to_osis())
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+impl Display for BibleReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, &ReferenceStyle::ENGLISH)
+    }
+}
+
+impl StyledDisplay for BibleReference {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
+        match self {
+            BibleReference::Book(book) => write!(f, "{}", book.abbrev_in(style.locale)),
+            BibleReference::Chapter(chapter) => chapter.fmt_with(f, style),
+            BibleReference::Verse(verse) => verse.fmt_with(f, style),
+            BibleReference::Range(range) => range.fmt_with(f, style),
+            BibleReference::List(list) => list.fmt_with(f, style),
+        }
+    }
+}
+
+impl FromStr for BibleReference {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &ReferenceStyle::ENGLISH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kinds() {
+        let kind = |text: &str| text.parse::<BibleReference>().unwrap().kind();
+        assert_eq!(kind("John"), "book");
+        assert_eq!(kind("Jn 3"), "chapter");
+        assert_eq!(kind("John 3:16"), "verse");
+        assert_eq!(kind("Jn 3:16-18"), "range");
+        assert_eq!(kind("Ro 8:28, 31-39"), "list");
+        let continental = ReferenceStyle::CONTINENTAL;
+        let reference = BibleReference::from_str_with("Röm 8,28.31-39", &continental).unwrap();
+        assert_eq!(reference.to_string(), "Ro 8:28, 31-39");
+        assert_eq!(
+            reference.display_with(&continental).to_string(),
+            "Röm 8,28.31-39"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            "Hezekiah 3:16".parse::<BibleReference>(),
+            Err(ParseError::NoSuchBookError(_))
+        ));
+        assert!(matches!(
+            "Jn 22".parse::<BibleReference>(),
+            Err(ParseError::ChapterOutOfRange(_))
+        ));
+        assert!(BibleReference::parse("Jn 3:x").is_none());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let ranges = |text| BibleReference::parse(text).unwrap().ranges();
+        assert_eq!(
+            ranges("Jude"),
+            [BibleVerseRange::new(BibleBook::Jude, 1, 1, 1, 25)]
+        );
+        assert_eq!(
+            ranges("Jn 3:16"),
+            [BibleVerseRange::new(BibleBook::John, 3, 16, 3, 16)]
+        );
+        assert_eq!(
+            ranges("Ro 8:28, 31-39"),
+            [
+                BibleVerseRange::new(BibleBook::Romans, 8, 28, 8, 28),
+                BibleVerseRange::new(BibleBook::Romans, 8, 31, 8, 39)
+            ]
+        );
+        // A chapter built directly need not exist, and has no verses
+        for chapter in [0, 51] {
+            let reference = BibleReference::Chapter(BibleChapter {
+                book: BibleBook::Genesis,
+                chapter,
+            });
+            let ranges = reference.ranges();
+            assert_eq!(ranges.len(), 1);
+            assert_eq!(ranges[0].verses().count(), 0);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Mod for the JSON Schemas of the reference types
+//!
+//! Only built with the `schema` feature. The schemas describe the strings the types are
+//! serialized as with the `serde` feature, so clients can check references before sending
+//! them.
+//!
+//! Books are matched exactly as the English parsers match them, which is case sensitive:
+//! by the abbreviations in [BOOK_ABBREVS], which references are displayed with, and for
+//! [BibleBook] and [BibleReference] also by the names in [BOOK_NAMES]. So `Jn 3:16` is a
+//! valid [BibleVerse] but `John 3:16` and `jn 3:16` are not, as neither would deserialize.
+//!
+//! The patterns follow the English reference grammar, so they will reject some strings
+//! that parse, but never the strings the types are displayed as.
+//!
+//! # Example
+//! ```rust
+//! use bible_data::BibleVerse;
+//! let schema = schemars::schema_for!(BibleVerse);
+//! assert_eq!(schema.get("type").unwrap(), "string");
+//! assert!(schema.get("pattern").unwrap().as_str().unwrap().contains("|Jn|"));
+//! ```
+use super::book::BibleBook;
+use super::bookorchapter::BibleBookOrChapter;
+use super::chapter::BibleChapter;
+use super::chapterandverse::ChapterAndVerse;
+use super::locale::Locale;
+use super::reference::BibleReference;
+use super::strongs::StrongsNumber;
+use super::verse::BibleVerse;
+use super::verserange::BibleVerseRange;
+use super::verserangelist::BibleVerseRangeList;
+use super::word::BibleWord;
+use crate::{BOOK_ABBREVS, BOOK_NAMES};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use std::borrow::Cow;
+
+/// A chapter, verse or word number
+const NUMBER: &str = "[0-9]{1,3}";
+
+/// The locales' identifiers, which may be followed by region or script subtags
+const LOCALE: &str = "^(en|es|pt|de|fr|ko|zh)([-_][A-Za-z0-9]+)*$";
+
+/// Strong's numbers, with or without the OSIS `strong:` prefix
+const STRONGS: &str = "^(strong:)?[HGhg][0-9]+$";
+
+/// The abbreviations of the books, and their names if asked for, without repeats
+fn books(names: bool) -> Vec<&'static str> {
+    let mut books = BOOK_ABBREVS.to_vec();
+    if names {
+        books.extend(
+            BOOK_NAMES
+                .iter()
+                .filter(|name| !BOOK_ABBREVS.contains(name)),
+        );
+    }
+    books
+}
+
+fn book_pattern(names: bool) -> String {
+    format!("({})", books(names).join("|"))
+}
+
+/// A chapter and verse, or a verse alone for books with a single chapter
+fn verse_pattern() -> String {
+    format!("{0}( *: *{0})?", NUMBER)
+}
+
+fn range_pattern() -> String {
+    format!("{0}( *- *{0})?", verse_pattern())
+}
+
+fn list_pattern() -> String {
+    format!("{0}( *, *{0})*", range_pattern())
+}
+
+fn string_schema(description: &str, pattern: String) -> Schema {
+    json_schema!({
+        "type": "string",
+        "description": description,
+        "pattern": pattern
+    })
+}
+
+macro_rules! schema {
+    ($type:ty, $generator:ident => $schema:expr) => {
+        impl JsonSchema for $type {
+            fn schema_name() -> Cow<'static, str> {
+                stringify!($type).into()
+            }
+
+            fn schema_id() -> Cow<'static, str> {
+                concat!("bible_data::", stringify!($type)).into()
+            }
+
+            fn json_schema($generator: &mut SchemaGenerator) -> Schema {
+                $schema
+            }
+        }
+    };
+}
+
+schema!(BibleBook, _generator => json_schema!({
+    "type": "string",
+    "description": "A book of the Bible, by name or abbreviation",
+    "enum": books(true)
+}));
+
+schema!(BibleChapter, _generator => string_schema(
+    "A chapter of a book, such as `Jn 3`",
+    format!("^{} {}$", book_pattern(false), NUMBER),
+));
+
+schema!(BibleBookOrChapter, _generator => string_schema(
+    "A whole book or one of its chapters, such as `Jn` or `Jn 3`",
+    format!("^{}( {})?$", book_pattern(false), NUMBER),
+));
+
+schema!(ChapterAndVerse, _generator => string_schema(
+    "A chapter and verse, such as `3:16`",
+    format!("^{0} *: *{0}$", NUMBER),
+));
+
+schema!(BibleVerse, _generator => string_schema(
+    "A single verse, such as `Jn 3:16`, with the book abbreviated",
+    format!("^{} {}$", book_pattern(false), verse_pattern()),
+));
+
+schema!(BibleVerseRange, _generator => string_schema(
+    "A range of verses within a book, such as `Jn 3:16-18` or `Jn 3:16-4:2`",
+    format!("^{} {}$", book_pattern(false), range_pattern()),
+));
+
+schema!(BibleVerseRangeList, _generator => string_schema(
+    "A list of verse ranges within a book, such as `Ro 8:28, 31-39`",
+    format!("^{} {}$", book_pattern(false), list_pattern()),
+));
+
+schema!(BibleReference, _generator => string_schema(
+    "Any reference, from a whole book down to a single verse or a list of ranges",
+    format!("^{}( {})?$", book_pattern(true), list_pattern()),
+));
+
+schema!(BibleWord, _generator => string_schema(
+    "A word of a verse by its number, such as `Jn 1:1 word 3`",
+    format!("^{} {} word [0-9]+$", book_pattern(false), verse_pattern()),
+));
+
+schema!(Locale, _generator => string_schema(
+    "A locale identifier such as `de` or `pt-BR`",
+    LOCALE.to_string(),
+));
+
+schema!(StrongsNumber, _generator => string_schema(
+    "A Strong's number such as `H7225` or `G26`",
+    STRONGS.to_string(),
+));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    fn pattern<T: JsonSchema>() -> Regex {
+        let schema = schemars::schema_for!(T);
+        Regex::new(schema.get("pattern").unwrap().as_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_book() {
+        let schema = schemars::schema_for!(BibleBook);
+        let books = schema.get("enum").unwrap().as_array().unwrap();
+        assert!(books.contains(&"Ge".into()));
+        assert!(books.contains(&"Genesis".into()));
+        assert_eq!(books.iter().filter(|book| *book == "Job").count(), 1);
+    }
+
+    #[test]
+    fn test_patterns() {
+        let verse = pattern::<BibleVerse>();
+        assert!(verse.is_match("Jn 3:16"));
+        assert!(verse.is_match("Jude 3"));
+        assert!(!verse.is_match("Jn 3:16-18"));
+
+        let range = pattern::<BibleVerseRange>();
+        assert!(range.is_match("Jn 3:16-18"));
+        assert!(range.is_match("Jn 3:16 - 4:2"));
+        assert!(!range.is_match("Jn 3:16, 18"));
+
+        let list = pattern::<BibleVerseRangeList>();
+        assert!(list.is_match("Mt 5:20-6:5, 7, 8:1"));
+        assert!(!list.is_match("Mt 5:20,"));
+
+        let reference = pattern::<BibleReference>();
+        assert!(reference.is_match("Song of Songs"));
+        assert!(reference.is_match("John 3"));
+        assert!(reference.is_match("Ro 8:28, 31-39"));
+        assert!(!reference.is_match("Hezekiah 3"));
+
+        assert!(pattern::<Locale>().is_match("pt-BR"));
+        assert!(pattern::<StrongsNumber>().is_match("strong:G26"));
+        assert!(!pattern::<StrongsNumber>().is_match("26"));
+    }
+
+    /// The patterns accept a book written in the same ways as the parsers do
+    #[test]
+    fn test_book_case() {
+        fn agrees<T: JsonSchema + std::str::FromStr>(reference: &str) -> bool {
+            pattern::<T>().is_match(reference) == reference.parse::<T>().is_ok()
+        }
+        for reference in ["Jn 3:16", "John 3:16", "jn 3:16", "john 3:16", "JN 3:16"] {
+            assert!(agrees::<BibleVerse>(reference), "{}", reference);
+            assert!(agrees::<BibleVerseRange>(reference), "{}", reference);
+            assert!(agrees::<BibleVerseRangeList>(reference), "{}", reference);
+            assert!(agrees::<BibleReference>(reference), "{}", reference);
+        }
+        for reference in ["Jn 3", "John 3", "jn 3", "john 3"] {
+            assert!(agrees::<BibleChapter>(reference), "{}", reference);
+            assert!(agrees::<BibleBookOrChapter>(reference), "{}", reference);
+        }
+        for book in ["Jn", "John", "jn", "john", "JOHN"] {
+            let schema = schemars::schema_for!(BibleBook);
+            let books = schema.get("enum").unwrap().as_array().unwrap();
+            assert_eq!(
+                books.contains(&book.into()),
+                serde_json::from_str::<BibleBook>(&format!("{:?}", book)).is_ok(),
+                "{}",
+                book
+            );
+        }
+    }
+
+    #[test]
+    fn test_display_matches() {
+        for book in BibleBook::iter() {
+            let chapter = BibleChapter::new(book, 1).unwrap();
+            let verse = BibleVerse::new(book, 1, 1);
+            let range = BibleVerseRange::new(book, 1, 1, 1, 2);
+            assert!(pattern::<BibleChapter>().is_match(&chapter.to_string()));
+            assert!(pattern::<BibleVerse>().is_match(&verse.to_string()));
+            assert!(pattern::<BibleVerseRange>().is_match(&range.to_string()));
+            assert!(pattern::<BibleReference>().is_match(&book.to_string()));
+            assert!(pattern::<BibleBookOrChapter>().is_match(book.abbrev()));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Mod for the serde support of the reference types
+//!
+//! Only built with the `serde` feature. Each type is serialized as the string it is
+//! displayed as, and deserialized by parsing it with [FromStr](std::str::FromStr), so
+//! `"Jn 3:16"` in a JSON or TOML file is read as a [BibleVerse]. The compact numeric
+//! representation is in [compact](super::compact).
+use super::book::BibleBook;
+use super::bookorchapter::BibleBookOrChapter;
+use super::chapter::BibleChapter;
+use super::chapterandverse::ChapterAndVerse;
+use super::locale::Locale;
+use super::reference::BibleReference;
+use super::strongs::StrongsNumber;
+use super::verse::BibleVerse;
+use super::verserange::BibleVerseRange;
+use super::verserangelist::BibleVerseRangeList;
+use super::word::BibleWord;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! string_serde {
+    ($($type:ty),+) => {
+        $(
+            impl Serialize for $type {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $type {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let text = String::deserialize(deserializer)?;
+                    text.parse().map_err(D::Error::custom)
+                }
+            }
+        )+
+    };
+}
+
+string_serde!(
+    BibleBook,
+    BibleBookOrChapter,
+    BibleChapter,
+    ChapterAndVerse,
+    BibleVerse,
+    BibleVerseRange,
+    BibleVerseRangeList,
+    BibleReference,
+    Locale,
+    StrongsNumber,
+    BibleWord
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::errors::ParseError;
+
+    #[test]
+    fn test_round_trip() {
+        let verse = BibleVerse::new(BibleBook::John, 3, 16);
+        assert_eq!(serde_json::to_string(&verse).unwrap(), "\"Jn 3:16\"");
+        assert_eq!(
+            serde_json::from_str::<BibleVerse>("\"Jn 3:16\"").unwrap(),
+            verse
+        );
+
+        let book = BibleBook::FirstKings;
+        let json = serde_json::to_string(&book).unwrap();
+        assert_eq!(serde_json::from_str::<BibleBook>(&json).unwrap(), book);
+
+        let list = BibleVerseRangeList::parse("Ro 8:28, 31-39").unwrap();
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "\"Ro 8:28, 31-39\"");
+        assert_eq!(
+            serde_json::from_str::<BibleVerseRangeList>(&json).unwrap(),
+            list
+        );
+
+        let cv = ChapterAndVerse::new(3, 16);
+        assert_eq!(serde_json::to_string(&cv).unwrap(), "\"3:16\"");
+    }
+
+    #[test]
+    fn test_deserialize_names() {
+        assert_eq!(
+            serde_json::from_str::<BibleBook>("\"Revelation\"").unwrap(),
+            BibleBook::Revelation
+        );
+        assert_eq!(
+            serde_json::from_str::<BibleReference>("\"John 3\"").unwrap(),
+            BibleReference::parse("Jn 3").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(serde_json::from_str::<BibleVerse>("\"Jn three\"").is_err());
+        assert!(serde_json::from_str::<BibleBook>("\"Hezekiah\"").is_err());
+        assert!(serde_json::from_str::<BibleVerse>("316").is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = "Hezekiah 3:16".parse::<BibleVerse>().unwrap_err();
+        let json = serde_json::to_string(&error).unwrap();
+        let error: ParseError = serde_json::from_str(&json).unwrap();
+        assert!(matches!(error, ParseError::NoSuchBookError(_)));
+    }
+}
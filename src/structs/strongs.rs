@@ -0,0 +1,151 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::errors::{InvalidFormat, NotANumber, ParseError};
+
+/// The language of the original text a Strong's number refers to
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StrongsLanguage {
+    /// Hebrew and Aramaic words of the Old Testament, prefixed `H`
+    Hebrew,
+    /// Greek words of the New Testament, prefixed `G`
+    Greek,
+}
+
+#[allow(dead_code)]
+impl StrongsLanguage {
+    /// The letter used before the number eg. `H`
+    pub fn prefix(&self) -> char {
+        match self {
+            StrongsLanguage::Hebrew => 'H',
+            StrongsLanguage::Greek => 'G',
+        }
+    }
+}
+
+/// A number from Strong's Exhaustive Concordance identifying a word of the original text
+///
+/// # Example
+/// ```rust
+/// use bible_data::{StrongsLanguage, StrongsNumber};
+/// let love = StrongsNumber::parse("G26").unwrap();
+/// assert_eq!(love.language, StrongsLanguage::Greek);
+/// assert_eq!(love.number, 26);
+/// assert_eq!(StrongsNumber::parse("strong:H07225").unwrap().to_string(), "H7225");
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StrongsNumber {
+    pub language: StrongsLanguage,
+    pub number: u16,
+}
+
+impl Display for StrongsNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.language.prefix(), self.number)
+    }
+}
+
+#[allow(dead_code)]
+impl StrongsNumber {
+    pub fn new(language: StrongsLanguage, number: u16) -> Self {
+        StrongsNumber { language, number }
+    }
+
+    pub fn hebrew(number: u16) -> Self {
+        StrongsNumber::new(StrongsLanguage::Hebrew, number)
+    }
+
+    pub fn greek(number: u16) -> Self {
+        StrongsNumber::new(StrongsLanguage::Greek, number)
+    }
+
+    /// Attempt to parse a Strong's number such as `H7225` or `G26`
+    /// Leading zeros and an OSIS `strong:` prefix are allowed.
+    pub fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    /// Parse all the Strong's numbers in an OSIS `lemma` attribute such as
+    /// `strong:H853 strong:H8064`, ignoring any other kind of lemma
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::StrongsNumber;
+    /// let numbers = StrongsNumber::parse_lemma("strong:G3588 lemma.TR:ἀγάπη strong:G26");
+    /// assert_eq!(numbers, vec![StrongsNumber::greek(3588), StrongsNumber::greek(26)]);
+    /// ```
+    pub fn parse_lemma(lemma: &str) -> Vec<Self> {
+        lemma
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(|part| part.strip_prefix("strong:"))
+            .filter_map(StrongsNumber::parse)
+            .collect()
+    }
+}
+
+impl FromStr for StrongsNumber {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let text = text.strip_prefix("strong:").unwrap_or(text);
+        let mut chars = text.chars();
+        let language = match chars.next() {
+            Some('H' | 'h') => StrongsLanguage::Hebrew,
+            Some('G' | 'g') => StrongsLanguage::Greek,
+            _ => {
+                return Err(InvalidFormat::new(format!(
+                    "Strong's number must start with H or G: {}",
+                    s
+                ))
+                .into());
+            }
+        };
+        let digits = chars.as_str();
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(NotANumber::new(format!("Invalid Strong's number: {}", s)).into());
+        }
+        match digits.parse::<u16>() {
+            Ok(number) if number > 0 => Ok(StrongsNumber { language, number }),
+            _ => Err(NotANumber::new(format!("Invalid Strong's number: {}", s)).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            StrongsNumber::parse("H7225"),
+            Some(StrongsNumber::hebrew(7225))
+        );
+        assert_eq!(
+            StrongsNumber::parse("g0026"),
+            Some(StrongsNumber::greek(26))
+        );
+        assert_eq!(
+            StrongsNumber::parse("strong:H1877"),
+            Some(StrongsNumber::hebrew(1877))
+        );
+        assert_eq!(StrongsNumber::parse("26"), None);
+        assert_eq!(StrongsNumber::parse("G"), None);
+        assert_eq!(StrongsNumber::parse("G0"), None);
+        assert_eq!(StrongsNumber::parse("G26a"), None);
+        assert_eq!(StrongsNumber::parse("G99999"), None);
+        assert!(matches!(
+            "X26".parse::<StrongsNumber>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_display_and_order() {
+        assert_eq!(StrongsNumber::greek(26).to_string(), "G26");
+        assert!(StrongsNumber::hebrew(8674) < StrongsNumber::greek(1));
+        assert!(StrongsNumber::greek(26) < StrongsNumber::greek(3588));
+    }
+}
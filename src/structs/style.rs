@@ -0,0 +1,145 @@
+use super::locale::Locale;
+use std::fmt::Display;
+
+/// The conventions used to write a reference: which locale the book names come from
+/// and which separators go between chapter and verse, the ends of a range, and the
+/// items of a list.
+///
+/// Parsing trims whitespace around the separators, so the [list](ReferenceStyle::list)
+/// separator may include a space for formatting (eg. `", "`) and still accept `"1,3"`.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleVerse, ReferenceStyle, StyledDisplay};
+/// let verse = BibleVerse::parse_with("Joh 3,16", &ReferenceStyle::CONTINENTAL).unwrap();
+/// assert_eq!(verse.to_string(), "Jn 3:16");
+/// assert_eq!(verse.display_with(&ReferenceStyle::CONTINENTAL).to_string(), "Joh 3,16");
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReferenceStyle {
+    pub locale: Locale,
+    pub chapter_verse: &'static str,
+    pub range: &'static str,
+    pub list: &'static str,
+}
+
+impl Default for ReferenceStyle {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+#[allow(dead_code)]
+impl ReferenceStyle {
+    /// English style. `Jn 3:16`, `Ro 8:28, 31-39`
+    /// This is the style used by the [Display] and [FromStr](std::str::FromStr)
+    /// implementations of the reference types.
+    pub const ENGLISH: ReferenceStyle = ReferenceStyle {
+        locale: Locale::English,
+        chapter_verse: ":",
+        range: "-",
+        list: ", ",
+    };
+
+    /// German / continental European style. `Joh 3,16`, `Röm 8,28.31-39`
+    pub const CONTINENTAL: ReferenceStyle = ReferenceStyle {
+        locale: Locale::German,
+        chapter_verse: ",",
+        range: "-",
+        list: ".",
+    };
+
+    /// Full-stop style common in British academic writing. `Jn 3.16`, `Ro 8.28, 31-39`
+    pub const PERIOD: ReferenceStyle = ReferenceStyle {
+        locale: Locale::English,
+        chapter_verse: ".",
+        range: "-",
+        list: ", ",
+    };
+
+    /// Return the conventional style for the given locale
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{Locale, ReferenceStyle};
+    /// assert_eq!(ReferenceStyle::for_locale(Locale::German), ReferenceStyle::CONTINENTAL);
+    /// assert_eq!(ReferenceStyle::for_locale(Locale::Korean).chapter_verse, ":");
+    /// ```
+    pub fn for_locale(locale: Locale) -> Self {
+        match locale {
+            Locale::German | Locale::French => Self::CONTINENTAL.with_locale(locale),
+            _ => Self::ENGLISH.with_locale(locale),
+        }
+    }
+
+    /// Return a copy of this style using the book names of a different locale
+    pub fn with_locale(self, locale: Locale) -> Self {
+        ReferenceStyle { locale, ..self }
+    }
+}
+
+/// Formatting of references according to a [ReferenceStyle]
+///
+/// [Display] for the reference types is equivalent to formatting with
+/// [ReferenceStyle::ENGLISH], except for [BibleBook](super::book::BibleBook)
+/// where it gives the full name.
+pub trait StyledDisplay {
+    /// Format this value with the given style
+    fn fmt_with(&self, f: &mut std::fmt::Formatter<'_>, style: &ReferenceStyle)
+    -> std::fmt::Result;
+
+    /// Return a wrapper that implements [Display] using the given style
+    fn display_with<'a>(&'a self, style: &'a ReferenceStyle) -> Styled<'a, Self> {
+        Styled { value: self, style }
+    }
+}
+
+/// A reference paired with the [ReferenceStyle] to display it with.
+/// Returned by [StyledDisplay::display_with]
+pub struct Styled<'a, T: ?Sized> {
+    value: &'a T,
+    style: &'a ReferenceStyle,
+}
+
+impl<T: StyledDisplay + ?Sized> Display for Styled<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt_with(f, self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_locale() {
+        assert_eq!(
+            ReferenceStyle::for_locale(Locale::English),
+            ReferenceStyle::ENGLISH
+        );
+        assert_eq!(
+            ReferenceStyle::for_locale(Locale::German),
+            ReferenceStyle::CONTINENTAL
+        );
+        let french = ReferenceStyle::for_locale(Locale::French);
+        assert_eq!(french.locale, Locale::French);
+        assert_eq!(french.chapter_verse, ",");
+        let spanish = ReferenceStyle::for_locale(Locale::Spanish);
+        assert_eq!(spanish.locale, Locale::Spanish);
+        assert_eq!(spanish.chapter_verse, ":");
+    }
+
+    #[test]
+    fn test_separators_distinct() {
+        for style in [
+            ReferenceStyle::ENGLISH,
+            ReferenceStyle::CONTINENTAL,
+            ReferenceStyle::PERIOD,
+        ] {
+            assert_ne!(style.chapter_verse, style.range);
+            assert_ne!(style.chapter_verse, style.list.trim());
+            assert_ne!(style.range, style.list.trim());
+        }
+    }
+}
@@ -1,14 +1,18 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
-use crate::structs::errors::{NoChapterSpecified, NoSuchBookError};
+use crate::structs::errors::{ChapterOutOfRange, NoChapterSpecified, NoSuchBookError};
 
 use super::book::BibleBook;
+use super::chapter::BibleChapter;
 use super::chapterandverseorverse::ChapterAndVerseOrVerse;
 use super::errors::ParseError;
+use super::locale::Locale;
+use super::osis::{parse_osis_parts, wrong_parts};
+use super::style::{ReferenceStyle, StyledDisplay};
 
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct BibleVerse {
     pub book: BibleBook,
     pub chapter: u8,
@@ -17,7 +21,24 @@ pub struct BibleVerse {
 
 impl Display for BibleVerse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}:{}", self.book.abbrev(), self.chapter, self.verse)
+        self.fmt_with(f, &ReferenceStyle::ENGLISH)
+    }
+}
+
+impl StyledDisplay for BibleVerse {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}{}{}",
+            self.book.abbrev_in(style.locale),
+            self.chapter,
+            style.chapter_verse,
+            self.verse
+        )
     }
 }
 
@@ -54,6 +75,20 @@ impl BibleVerse {
             verse,
         }
     }
+
+    /// Check that the chapter and verse exist in the book
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// assert!(BibleVerse::new(BibleBook::John, 21, 25).is_valid());
+    /// assert!(!BibleVerse::new(BibleBook::John, 21, 26).is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        match BibleChapter::new(self.book, self.chapter) {
+            Some(chapter) => (1..=chapter.number_of_verses()).contains(&(self.verse as u32)),
+            None => false,
+        }
+    }
 }
 
 impl TryFrom<&str> for BibleVerse {
@@ -72,11 +107,150 @@ impl FromStr for BibleVerse {
             .ok_or_else(|| NoSuchBookError::new("No matching abbreviation".to_string()))?;
         // Result of parse_book_abbrev ends with end of string or space character
         // We can find rest of string (if any) by looking for the first space character
-        match s.find(" ") {
+        let remain = s.find(" ").map(|pos| &s[pos + 1..]);
+        Self::from_book_and_remainder(book, remain, &ReferenceStyle::ENGLISH)
+    }
+}
+
+#[allow(dead_code)]
+impl BibleVerse {
+    /// Attempt to parse a verse reference using the book names and abbreviations
+    /// of the given locale
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse, Locale};
+    /// assert_eq!(
+    ///     BibleVerse::parse_in("Génesis 1:1", Locale::Spanish),
+    ///     Some(BibleVerse::new(BibleBook::Genesis, 1, 1))
+    /// );
+    /// assert_eq!(
+    ///     BibleVerse::parse_in("창세기 1:1", Locale::Korean),
+    ///     Some(BibleVerse::new(BibleBook::Genesis, 1, 1))
+    /// );
+    /// ```
+    pub fn parse_in(text: &str, locale: Locale) -> Option<Self> {
+        Self::from_str_in(text, locale).ok()
+    }
+
+    /// Parse a verse reference using the book names and abbreviations of the
+    /// given locale, returning the reason for any failure
+    pub fn from_str_in(text: &str, locale: Locale) -> Result<Self, ParseError> {
+        Self::from_str_with(text, &ReferenceStyle::for_locale(locale))
+    }
+
+    /// Attempt to parse using the book names and separators of the given style
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse, ReferenceStyle};
+    /// assert_eq!(
+    ///     BibleVerse::parse_with("Joh 3,16", &ReferenceStyle::CONTINENTAL),
+    ///     Some(BibleVerse::new(BibleBook::John, 3, 16))
+    /// );
+    /// ```
+    pub fn parse_with(text: &str, style: &ReferenceStyle) -> Option<Self> {
+        Self::from_str_with(text, style).ok()
+    }
+
+    /// Parse using the book names and separators of the given style,
+    /// returning the reason for any failure
+    pub fn from_str_with(text: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        let (book, remain) = BibleBook::split_in(text, style.locale)
+            .ok_or_else(|| NoSuchBookError::new(format!("No matching book in {}", style.locale)))?;
+        Self::from_book_and_remainder(book, remain, style)
+    }
+
+    /// Return the OSIS identifier for this verse
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// assert_eq!(BibleVerse::new(BibleBook::Genesis, 1, 1).to_osis(), "Gen.1.1");
+    /// ```
+    pub fn to_osis(&self) -> String {
+        format!("{}.{}.{}", self.book.to_osis(), self.chapter, self.verse)
+    }
+
+    /// Construct a BibleVerse from an OSIS identifier such as `Gen.1.1`
+    /// Any work prefix or grain is ignored.
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// assert_eq!(
+    ///     BibleVerse::from_osis("Bible.KJV:Gen.1.1!a").unwrap(),
+    ///     BibleVerse::new(BibleBook::Genesis, 1, 1)
+    /// );
+    /// ```
+    pub fn from_osis(id: &str) -> Result<Self, ParseError> {
+        match parse_osis_parts(id)? {
+            (book, numbers) if numbers.len() == 2 => BibleVerse {
+                book,
+                chapter: numbers[0],
+                verse: numbers[1],
+            }
+            .check_exists(),
+            _ => Err(wrong_parts(id, "verse")),
+        }
+    }
+
+    /// Return the verse if it exists, or an error if the chapter does not contain it
+    fn check_exists(self) -> Result<Self, ParseError> {
+        if self.is_valid() {
+            return Ok(self);
+        }
+        let verses = BibleChapter {
+            book: self.book,
+            chapter: self.chapter,
+        }
+        .number_of_verses();
+        Err(ChapterOutOfRange::new(format!(
+            "{} {} has {} verses. {} is out of range.",
+            self.book.name(),
+            self.chapter,
+            verses,
+            self.verse
+        ))
+        .into())
+    }
+
+    /// Return the Paratext style reference for this verse
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// assert_eq!(BibleVerse::new(BibleBook::Genesis, 1, 1).to_usfm(), "GEN 1:1");
+    /// ```
+    pub fn to_usfm(&self) -> String {
+        format!("{} {}:{}", self.book.usfm_code(), self.chapter, self.verse)
+    }
+
+    /// Parse a Paratext style reference to a verse such as `GEN 1:1`
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse};
+    /// assert_eq!(
+    ///     BibleVerse::from_usfm("GEN 1:1").unwrap(),
+    ///     BibleVerse::new(BibleBook::Genesis, 1, 1)
+    /// );
+    /// ```
+    pub fn from_usfm(text: &str) -> Result<Self, ParseError> {
+        let (book, remain) = BibleBook::split_usfm(text)
+            .ok_or_else(|| NoSuchBookError::new(format!("No matching USFM code: {}", text)))?;
+        Self::from_book_and_remainder(book, remain, &ReferenceStyle::ENGLISH)?.check_exists()
+    }
+
+    /// Parse the chapter and verse that follow the book in a reference
+    /// `remain` is None if nothing followed the book
+    fn from_book_and_remainder(
+        book: BibleBook,
+        remain: Option<&str>,
+        style: &ReferenceStyle,
+    ) -> Result<Self, ParseError> {
+        match remain {
             None => Err(NoChapterSpecified::new("No chapter/verse specified.".to_string()).into()), // There is no chapter/verse specified
-            Some(pos) => {
-                let remain = &s[pos + 1..];
-                match ChapterAndVerseOrVerse::from_str(remain)? {
+            Some(remain) => {
+                match ChapterAndVerseOrVerse::from_str_with(remain, style)? {
                     ChapterAndVerseOrVerse::JustVerse(verse) => {
                         // No chapter
                         // This is invalid, unless the book only has one chapter
@@ -146,6 +320,115 @@ mod tests {
         );
         // Should not be implicit for other books
         assert_eq!(BibleVerse::parse("Judges 5"), None);
+        // No spaces around the separator
+        assert_eq!(BibleVerse::parse("Ge 1 : 1"), None);
+    }
+
+    #[test]
+    fn test_parse_in() {
+        assert_eq!(
+            BibleVerse::parse_in("Génesis 1:1", Locale::Spanish),
+            Some(BibleVerse::new(BibleBook::Genesis, 1, 1))
+        );
+        assert_eq!(
+            BibleVerse::parse_in("1 Corintios 13:4", Locale::Spanish),
+            Some(BibleVerse::new(BibleBook::FirstCorinthians, 13, 4))
+        );
+        assert_eq!(
+            BibleVerse::parse_in("Jo 3:16", Locale::Portuguese),
+            Some(BibleVerse::new(BibleBook::John, 3, 16))
+        );
+        assert_eq!(
+            BibleVerse::parse_in("창세기 1:1", Locale::Korean),
+            Some(BibleVerse::new(BibleBook::Genesis, 1, 1))
+        );
+        assert_eq!(
+            BibleVerse::parse_in("约翰福音 3:16", Locale::Chinese),
+            Some(BibleVerse::new(BibleBook::John, 3, 16))
+        );
+        assert_eq!(
+            BibleVerse::parse_in("Jude 3", Locale::French),
+            Some(BibleVerse::new(BibleBook::Jude, 1, 3))
+        );
+        assert_eq!(
+            BibleVerse::parse_in("Song of Songs 2:1", Locale::English),
+            Some(BibleVerse::new(BibleBook::SongofSongs, 2, 1))
+        );
+        assert_eq!(BibleVerse::parse_in("Génesis 5", Locale::Spanish), None);
+        assert_eq!(BibleVerse::parse_in("Genesis 1:1", Locale::Spanish), None);
+    }
+
+    #[test]
+    fn test_parse_with() {
+        assert_eq!(
+            BibleVerse::parse_with("Joh 3,16", &ReferenceStyle::CONTINENTAL),
+            Some(BibleVerse::new(BibleBook::John, 3, 16))
+        );
+        assert_eq!(
+            BibleVerse::parse_with("1. Mose 1,1", &ReferenceStyle::CONTINENTAL),
+            Some(BibleVerse::new(BibleBook::Genesis, 1, 1))
+        );
+        assert_eq!(
+            BibleVerse::parse_with("Jn 3.16", &ReferenceStyle::PERIOD),
+            Some(BibleVerse::new(BibleBook::John, 3, 16))
+        );
+        assert_eq!(
+            BibleVerse::parse_with("Jud 3", &ReferenceStyle::CONTINENTAL),
+            Some(BibleVerse::new(BibleBook::Jude, 1, 3))
+        );
+        assert_eq!(
+            BibleVerse::parse_with("Joh 3:16", &ReferenceStyle::CONTINENTAL),
+            None
+        );
+        assert_eq!(
+            BibleVerse::parse_in("1. Mose 1,1", Locale::German),
+            Some(BibleVerse::new(BibleBook::Genesis, 1, 1))
+        );
+        assert_eq!(
+            BibleVerse::parse_in("Jean 3,16", Locale::French),
+            Some(BibleVerse::new(BibleBook::John, 3, 16))
+        );
+    }
+
+    #[test]
+    fn test_osis() {
+        for book in BibleBook::iter() {
+            let verse = BibleVerse::new(book, book.number_of_chapters() as u8, 3);
+            assert_eq!(
+                verse.to_osis(),
+                format!("{}.{}.3", book.to_osis(), book.number_of_chapters())
+            );
+            assert_eq!(BibleVerse::from_osis(&verse.to_osis()).unwrap(), verse);
+        }
+        assert_eq!(
+            BibleVerse::from_osis("John.3.16@s[love]").unwrap(),
+            BibleVerse::new(BibleBook::John, 3, 16)
+        );
+        assert!(BibleVerse::from_osis("John.3").is_err());
+        assert!(BibleVerse::from_osis("John.22.1").is_err());
+        assert!(BibleVerse::from_osis("Gen.1.0").is_err());
+        assert!(BibleVerse::from_osis("Gen.1.99").is_err());
+        assert!(BibleVerse::from_osis("Gen.1.32").is_err());
+        assert!(BibleVerse::from_osis("Gen.1.31").is_ok());
+        assert!(BibleVerse::from_osis("Jn.3.16").is_err());
+    }
+
+    #[test]
+    fn test_usfm() {
+        for book in BibleBook::iter() {
+            let verse = BibleVerse::new(book, book.number_of_chapters() as u8, 3);
+            assert_eq!(BibleVerse::from_usfm(&verse.to_usfm()).unwrap(), verse);
+        }
+        assert_eq!(BibleVerse::new(BibleBook::Jude, 1, 3).to_usfm(), "JUD 1:3");
+        assert_eq!(
+            BibleVerse::from_usfm("JUD 3").unwrap(),
+            BibleVerse::new(BibleBook::Jude, 1, 3)
+        );
+        assert!(BibleVerse::from_usfm("GEN 1").is_err());
+        assert!(BibleVerse::from_usfm("GEN 1:0").is_err());
+        assert!(BibleVerse::from_usfm("GEN 1:99").is_err());
+        assert!(BibleVerse::from_usfm("JUD 26").is_err());
+        assert!(BibleVerse::from_usfm("Ge 1:1").is_err());
     }
 
     #[test]
@@ -161,6 +444,22 @@ mod tests {
             ),
             "Ac 2:1"
         );
+        let verse = BibleVerse::new(BibleBook::Acts, 2, 1);
+        assert_eq!(
+            format!("{}", verse.display_with(&ReferenceStyle::CONTINENTAL)),
+            "Apg 2,1"
+        );
+        assert_eq!(
+            format!("{}", verse.display_with(&ReferenceStyle::PERIOD)),
+            "Ac 2.1"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                verse.display_with(&ReferenceStyle::for_locale(Locale::Korean))
+            ),
+            "행 2:1"
+        );
     }
 
     #[test]
@@ -5,12 +5,16 @@ use std::str::FromStr;
 use super::errors::{InvalidRange, NoChapterSpecified, NoSuchBookError, ParseError};
 
 use super::book::BibleBook;
+use super::chapter::BibleChapter;
 use super::chapterandverse::ChapterAndVerse;
 use super::chapterandverserange::ChapterAndVerseRange;
+use super::locale::Locale;
+use super::osis::OsisId;
+use super::style::{ReferenceStyle, StyledDisplay};
 use super::verse::BibleVerse;
 
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct BibleVerseRange {
     pub book: BibleBook,
     pub range: RangeInclusive<ChapterAndVerse>,
@@ -22,6 +26,188 @@ impl BibleVerseRange {
         text.parse().ok()
     }
 
+    /// Attempt to parse a verse range using the book names and abbreviations
+    /// of the given locale
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerseRange, Locale};
+    /// assert_eq!(
+    ///     BibleVerseRange::parse_in("Mateo 5:3-12", Locale::Spanish),
+    ///     Some(BibleVerseRange::new(BibleBook::Matthew, 5, 3, 5, 12))
+    /// );
+    /// ```
+    pub fn parse_in(text: &str, locale: Locale) -> Option<Self> {
+        Self::from_str_in(text, locale).ok()
+    }
+
+    /// Parse a verse range using the book names and abbreviations of the
+    /// given locale, returning the reason for any failure
+    pub fn from_str_in(text: &str, locale: Locale) -> Result<Self, ParseError> {
+        Self::from_str_with(text, &ReferenceStyle::for_locale(locale))
+    }
+
+    /// Attempt to parse using the book names and separators of the given style
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerseRange, ReferenceStyle};
+    /// assert_eq!(
+    ///     BibleVerseRange::parse_with("Röm 8,31-39", &ReferenceStyle::CONTINENTAL),
+    ///     Some(BibleVerseRange::new(BibleBook::Romans, 8, 31, 8, 39))
+    /// );
+    /// ```
+    pub fn parse_with(text: &str, style: &ReferenceStyle) -> Option<Self> {
+        Self::from_str_with(text, style).ok()
+    }
+
+    /// Parse using the book names and separators of the given style,
+    /// returning the reason for any failure
+    pub fn from_str_with(text: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        let (book, remain) = BibleBook::split_in(text, style.locale)
+            .ok_or_else(|| NoSuchBookError::new(format!("No matching book in {}", style.locale)))?;
+        Self::from_book_and_remainder(book, remain, style)
+    }
+
+    /// Return the OSIS reference for this range
+    /// A range of a single verse is written as a single verse.
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerseRange};
+    /// assert_eq!(
+    ///     BibleVerseRange::new(BibleBook::FirstKings, 3, 5, 3, 9).to_osis(),
+    ///     "1Kgs.3.5-1Kgs.3.9"
+    /// );
+    /// ```
+    pub fn to_osis(&self) -> String {
+        let start = BibleVerse::new(
+            self.book,
+            self.range.start().chapter,
+            self.range.start().verse,
+        );
+        let end = BibleVerse::new(self.book, self.range.end().chapter, self.range.end().verse);
+        match start == end {
+            true => start.to_osis(),
+            false => format!("{}-{}", start.to_osis(), end.to_osis()),
+        }
+    }
+
+    /// Construct a BibleVerseRange from an OSIS reference such as `1Kgs.3.5-1Kgs.3.9`
+    /// or a single verse such as `Gen.1.1`. Both ends must be in the same book.
+    /// Any work prefix or grain is ignored.
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerseRange};
+    /// assert_eq!(
+    ///     BibleVerseRange::from_osis("KJV:1Kgs.3.5-1Kgs.3.9").unwrap(),
+    ///     BibleVerseRange::new(BibleBook::FirstKings, 3, 5, 3, 9)
+    /// );
+    /// ```
+    pub fn from_osis(reference: &str) -> Result<Self, ParseError> {
+        // A work prefix applies to both ends of the range
+        let work = OsisId::split(reference).work;
+        let reference = match work {
+            Some(work) => &reference[work.len() + 1..],
+            None => reference,
+        };
+        match reference.split_once('-') {
+            None => BibleVerse::from_osis(reference).map(BibleVerseRange::from),
+            Some((start, end)) => {
+                let start = BibleVerse::from_osis(start)?;
+                let end = BibleVerse::from_osis(end)?;
+                if start.book != end.book {
+                    return Err(InvalidRange::new(
+                        "Range must be within a single book".to_string(),
+                    )
+                    .into());
+                }
+                if start > end {
+                    return Err(InvalidRange::new("End verse before start".to_string()).into());
+                }
+                Ok(BibleVerseRange::new(
+                    start.book,
+                    start.chapter,
+                    start.verse,
+                    end.chapter,
+                    end.verse,
+                ))
+            }
+        }
+    }
+
+    /// Return the Paratext style reference for this range
+    /// Unlike [Display] the chapter is always given, even for single-chapter books.
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerseRange};
+    /// assert_eq!(
+    ///     BibleVerseRange::new(BibleBook::Matthew, 5, 3, 5, 12).to_usfm(),
+    ///     "MAT 5:3-12"
+    /// );
+    /// ```
+    pub fn to_usfm(&self) -> String {
+        let (start, end) = (self.range.start(), self.range.end());
+        let code = self.book.usfm_code();
+        if start == end {
+            format!("{} {}", code, start)
+        } else if start.chapter == end.chapter {
+            format!("{} {}-{}", code, start, end.verse)
+        } else {
+            format!("{} {}-{}", code, start, end)
+        }
+    }
+
+    /// Parse a Paratext style reference to a range such as `MAT 5:3-12`
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerseRange};
+    /// assert_eq!(
+    ///     BibleVerseRange::from_usfm("MAT 5:3-12").unwrap(),
+    ///     BibleVerseRange::new(BibleBook::Matthew, 5, 3, 5, 12)
+    /// );
+    /// ```
+    pub fn from_usfm(text: &str) -> Result<Self, ParseError> {
+        let (book, remain) = BibleBook::split_usfm(text)
+            .ok_or_else(|| NoSuchBookError::new(format!("No matching USFM code: {}", text)))?;
+        Self::from_book_and_remainder(book, remain, &ReferenceStyle::ENGLISH)
+    }
+
+    /// Parse the range that follows the book in a reference
+    /// `remain` is None if nothing followed the book
+    fn from_book_and_remainder(
+        book: BibleBook,
+        remain: Option<&str>,
+        style: &ReferenceStyle,
+    ) -> Result<Self, ParseError> {
+        match remain {
+            None => Err(NoChapterSpecified::new("No chapter/verse specified.".to_string()).into()),
+            Some(remain) => match ChapterAndVerseRange::from_str_with(remain, style) {
+                Ok(cvr) => match cvr.0.is_empty() {
+                    true => Err(InvalidRange::new("End verse before start".to_string()).into()),
+                    false => Ok(BibleVerseRange { book, range: cvr.0 }),
+                },
+                Err(ParseError::ImplicitRange(e)) => {
+                    let cvr = e.data();
+                    match book.number_of_chapters() {
+                        1 => match cvr.0.is_empty() {
+                            true => {
+                                Err(InvalidRange::new("End verse before start".to_string()).into())
+                            }
+                            false => Ok(BibleVerseRange { book, range: cvr.0 }),
+                        },
+                        _ => Err(NoChapterSpecified::new(
+                            "Chapter can only be ommited for single-chapter books".to_string(),
+                        )
+                        .into()),
+                    }
+                }
+                Err(e) => Err(e),
+            },
+        }
+    }
+
     pub fn contains(&self, verse: &BibleVerse) -> bool {
         self.book == verse.book
             && self.range.contains(&ChapterAndVerse {
@@ -34,6 +220,44 @@ impl BibleVerseRange {
         self.range.start().chapter..=self.range.end().chapter
     }
 
+    /// Iterate over the verses of the range in order
+    ///
+    /// Only verses that exist are included, so a range that runs past the end of a chapter
+    /// stops at its last verse and one starting at verse 0 starts at verse 1.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+    /// let range = BibleVerseRange::parse("Ps 117:2-118:2").unwrap();
+    /// assert_eq!(
+    ///     range.verses().collect::<Vec<_>>(),
+    ///     [
+    ///         BibleVerse::new(BibleBook::Psalms, 117, 2),
+    ///         BibleVerse::new(BibleBook::Psalms, 118, 1),
+    ///         BibleVerse::new(BibleBook::Psalms, 118, 2),
+    ///     ]
+    /// );
+    /// ```
+    pub fn verses(&self) -> impl Iterator<Item = BibleVerse> + '_ {
+        let (start, end) = (self.range.start(), self.range.end());
+        self.chapters().flat_map(move |chapter| {
+            let first = match chapter == start.chapter {
+                true => start.verse.max(1),
+                false => 1,
+            };
+            let verses = BibleChapter {
+                book: self.book,
+                chapter,
+            }
+            .number_of_verses() as u8;
+            let last = match chapter == end.chapter {
+                true => end.verse.min(verses),
+                false => verses,
+            };
+            (first..=last).map(move |verse| BibleVerse::new(self.book, chapter, verse))
+        })
+    }
+
     pub fn new(
         book: BibleBook,
         start_chapter: u8,
@@ -51,22 +275,33 @@ impl BibleVerseRange {
 
 impl Display for BibleVerseRange {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, &ReferenceStyle::ENGLISH)
+    }
+}
+
+impl StyledDisplay for BibleVerseRange {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
         let start = if self.book.number_of_chapters() == 1 {
             format!("{}", self.range.start().verse)
         } else {
-            format!("{}", self.range.start())
+            format!("{}", self.range.start().display_with(style))
         };
+        let abbrev = self.book.abbrev_in(style.locale);
         if self.range.start() == self.range.end() {
-            write!(f, "{} {}", self.book.abbrev(), start)
+            write!(f, "{} {}", abbrev, start)
         } else {
             let end = if self.book.number_of_chapters() == 1
                 || self.range.start().chapter == self.range.end().chapter
             {
                 format!("{}", self.range.end().verse)
             } else {
-                format!("{}", self.range.end())
+                format!("{}", self.range.end().display_with(style))
             };
-            write!(f, "{} {}-{}", self.book.abbrev(), start, end)
+            write!(f, "{} {}{}{}", abbrev, start, style.range, end)
         }
     }
 }
@@ -93,35 +328,8 @@ impl FromStr for BibleVerseRange {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let book = BibleBook::parse_abbrev(s)
             .ok_or_else(|| NoSuchBookError::new("No matching abbreviation".to_string()))?;
-        match s.find(" ") {
-            None => Err(NoChapterSpecified::new("No chapter/verse specified.".to_string()).into()),
-            Some(pos) => {
-                let remain = &s[pos + 1..];
-                match ChapterAndVerseRange::from_str(remain) {
-                    Ok(cvr) => match cvr.0.is_empty() {
-                        true => Err(InvalidRange::new("End verse before start".to_string()).into()),
-                        false => Ok(BibleVerseRange { book, range: cvr.0 }),
-                    },
-                    Err(ParseError::ImplicitRange(e)) => {
-                        let cvr = e.data();
-                        match book.number_of_chapters() {
-                            1 => match cvr.0.is_empty() {
-                                true => {
-                                    Err(InvalidRange::new("End verse before start".to_string())
-                                        .into())
-                                }
-                                false => Ok(BibleVerseRange { book, range: cvr.0 }),
-                            },
-                            _ => Err(NoChapterSpecified::new(
-                                "Chapter can only be ommited for single-chapter books".to_string(),
-                            )
-                            .into()),
-                        }
-                    }
-                    Err(e) => Err(e),
-                }
-            }
-        }
+        let remain = s.find(" ").map(|pos| &s[pos + 1..]);
+        Self::from_book_and_remainder(book, remain, &ReferenceStyle::ENGLISH)
     }
 }
 
@@ -170,6 +378,51 @@ mod tests {
         assert_eq!(BibleVerseRange::parse("Ob 5-2"), None);
     }
 
+    #[test]
+    fn test_parse_in() {
+        assert_eq!(
+            BibleVerseRange::parse_in("Mateo 5:3-12", Locale::Spanish).unwrap(),
+            BibleVerseRange::new(BibleBook::Matthew, 5, 3, 5, 12)
+        );
+        assert_eq!(
+            BibleVerseRange::parse_in("Jean 3,16-4,2", Locale::French).unwrap(),
+            BibleVerseRange::new(BibleBook::John, 3, 16, 4, 2)
+        );
+        assert_eq!(
+            BibleVerseRange::parse_in("요삼 2-4", Locale::Korean).unwrap(),
+            BibleVerseRange::new(BibleBook::ThirdJohn, 1, 2, 1, 4)
+        );
+        assert_eq!(
+            BibleVerseRange::parse_in("1 Pedro 1:3", Locale::Portuguese).unwrap(),
+            BibleVerseRange::new(BibleBook::FirstPeter, 1, 3, 1, 3)
+        );
+        assert_eq!(
+            BibleVerseRange::parse_in("Mateo 5:12-3", Locale::Spanish),
+            None
+        );
+        assert_eq!(BibleVerseRange::parse_in("Mateo", Locale::Spanish), None);
+    }
+
+    #[test]
+    fn test_parse_with() {
+        assert_eq!(
+            BibleVerseRange::parse_with("Röm 8,31-39", &ReferenceStyle::CONTINENTAL).unwrap(),
+            BibleVerseRange::new(BibleBook::Romans, 8, 31, 8, 39)
+        );
+        assert_eq!(
+            BibleVerseRange::parse_with("Mt 5.20-6.5", &ReferenceStyle::PERIOD).unwrap(),
+            BibleVerseRange::new(BibleBook::Matthew, 5, 20, 6, 5)
+        );
+        assert_eq!(
+            BibleVerseRange::parse_with("Jud 2-5", &ReferenceStyle::CONTINENTAL).unwrap(),
+            BibleVerseRange::new(BibleBook::Jude, 1, 2, 1, 5)
+        );
+        assert_eq!(
+            BibleVerseRange::parse_with("Röm 8:31-39", &ReferenceStyle::CONTINENTAL),
+            None
+        );
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(
@@ -192,6 +445,72 @@ mod tests {
             format!("{}", BibleVerseRange::new(BibleBook::Jude, 1, 2, 1, 2)),
             "Jude 2"
         );
+        let range = BibleVerseRange::new(BibleBook::Matthew, 5, 20, 6, 5);
+        assert_eq!(
+            format!("{}", range.display_with(&ReferenceStyle::CONTINENTAL)),
+            "Mt 5,20-6,5"
+        );
+        let range = BibleVerseRange::new(BibleBook::Romans, 8, 31, 8, 39);
+        assert_eq!(
+            format!("{}", range.display_with(&ReferenceStyle::CONTINENTAL)),
+            "Röm 8,31-39"
+        );
+        assert_eq!(
+            format!("{}", range.display_with(&ReferenceStyle::PERIOD)),
+            "Ro 8.31-39"
+        );
+    }
+
+    #[test]
+    fn test_osis() {
+        for book in BibleBook::iter() {
+            let chapters = book.number_of_chapters() as u8;
+            let range = BibleVerseRange::new(book, 1, 2, chapters, 3);
+            assert_eq!(
+                range.to_osis(),
+                format!("{0}.1.2-{0}.{1}.3", book.to_osis(), chapters)
+            );
+            assert_eq!(BibleVerseRange::from_osis(&range.to_osis()).unwrap(), range);
+            let single = BibleVerseRange::new(book, 1, 1, 1, 1);
+            assert_eq!(single.to_osis(), format!("{}.1.1", book.to_osis()));
+            assert_eq!(
+                BibleVerseRange::from_osis(&single.to_osis()).unwrap(),
+                single
+            );
+        }
+        assert_eq!(
+            BibleVerseRange::from_osis("Bible.KJV:Gen.1.1!a-Gen.1.5!b").unwrap(),
+            BibleVerseRange::new(BibleBook::Genesis, 1, 1, 1, 5)
+        );
+        assert!(BibleVerseRange::from_osis("Gen.50.1-Exod.1.1").is_err());
+        assert!(BibleVerseRange::from_osis("Gen.1.5-Gen.1.1").is_err());
+        assert!(BibleVerseRange::from_osis("Ps.119").is_err());
+    }
+
+    #[test]
+    fn test_usfm() {
+        for book in BibleBook::iter() {
+            let chapters = book.number_of_chapters() as u8;
+            let range = BibleVerseRange::new(book, 1, 2, chapters, 3);
+            assert_eq!(BibleVerseRange::from_usfm(&range.to_usfm()).unwrap(), range);
+        }
+        assert_eq!(
+            BibleVerseRange::new(BibleBook::Matthew, 5, 20, 6, 5).to_usfm(),
+            "MAT 5:20-6:5"
+        );
+        assert_eq!(
+            BibleVerseRange::new(BibleBook::Jude, 1, 2, 1, 5).to_usfm(),
+            "JUD 1:2-5"
+        );
+        assert_eq!(
+            BibleVerseRange::new(BibleBook::Jude, 1, 2, 1, 2).to_usfm(),
+            "JUD 1:2"
+        );
+        assert_eq!(
+            BibleVerseRange::from_usfm("JUD 2-5").unwrap(),
+            BibleVerseRange::new(BibleBook::Jude, 1, 2, 1, 5)
+        );
+        assert!(BibleVerseRange::from_usfm("MAT 5:12-3").is_err());
     }
 
     #[test]
@@ -217,6 +536,22 @@ mod tests {
         assert_eq!(it.next(), None);
     }
 
+    #[test]
+    fn test_verses() {
+        let verses = |text| {
+            BibleVerseRange::parse(text)
+                .unwrap()
+                .verses()
+                .collect::<Vec<_>>()
+        };
+        let genesis = |chapter, verse| BibleVerse::new(BibleBook::Genesis, chapter, verse);
+        assert_eq!(verses("Ge 50:1-99").len(), 26);
+        assert_eq!(verses("Ge 50:25-99"), [genesis(50, 25), genesis(50, 26)]);
+        assert_eq!(verses("Ge 1:0-2"), [genesis(1, 1), genesis(1, 2)]);
+        assert_eq!(verses("Ge 49:33-50:99").len(), 27);
+        assert_eq!(verses("Ge 1:40-45"), []);
+    }
+
     #[test]
     fn test_try_from() {
         fn num_chapters(item: impl TryInto<BibleVerseRange>) -> Option<usize> {
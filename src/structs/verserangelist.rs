@@ -0,0 +1,274 @@
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use super::errors::{InvalidRange, NoChapterSpecified, NoSuchBookError, ParseError};
+
+use super::book::BibleBook;
+use super::chapterandverse::ChapterAndVerse;
+use super::chapterandverserange::ChapterAndVerseRange;
+use super::locale::Locale;
+use super::style::{ReferenceStyle, StyledDisplay};
+use super::verse::BibleVerse;
+use super::verserange::BibleVerseRange;
+
+/// A list of verse ranges within a single book
+/// eg. `Ro 8:28, 31-39` or, in continental style, `Röm 8,28.31-39`
+///
+/// Items after the first that have no chapter refer to the chapter the previous item ended in.
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibleVerseRangeList {
+    pub book: BibleBook,
+    pub ranges: Vec<RangeInclusive<ChapterAndVerse>>,
+}
+
+#[allow(dead_code)]
+impl BibleVerseRangeList {
+    pub fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    /// Attempt to parse a list using the conventions of the given locale
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerseRangeList, Locale};
+    /// let list = BibleVerseRangeList::parse_in("Röm 8,28.31-39", Locale::German).unwrap();
+    /// assert_eq!(list.book, BibleBook::Romans);
+    /// assert_eq!(list.ranges.len(), 2);
+    /// ```
+    pub fn parse_in(text: &str, locale: Locale) -> Option<Self> {
+        Self::from_str_in(text, locale).ok()
+    }
+
+    /// Parse a list using the conventions of the given locale,
+    /// returning the reason for any failure
+    pub fn from_str_in(text: &str, locale: Locale) -> Result<Self, ParseError> {
+        Self::from_str_with(text, &ReferenceStyle::for_locale(locale))
+    }
+
+    /// Attempt to parse a list using the book names and separators of the given style
+    pub fn parse_with(text: &str, style: &ReferenceStyle) -> Option<Self> {
+        Self::from_str_with(text, style).ok()
+    }
+
+    /// Parse a list using the book names and separators of the given style,
+    /// returning the reason for any failure
+    pub fn from_str_with(text: &str, style: &ReferenceStyle) -> Result<Self, ParseError> {
+        let (book, remain) = BibleBook::split_in(text, style.locale)
+            .ok_or_else(|| NoSuchBookError::new(format!("No matching book in {}", style.locale)))?;
+        Self::from_book_and_remainder(book, remain, style)
+    }
+
+    /// Return an iterator over the items of the list as [BibleVerseRange]s
+    pub fn iter(&self) -> impl Iterator<Item = BibleVerseRange> + '_ {
+        self.ranges.iter().map(|range| BibleVerseRange {
+            book: self.book,
+            range: range.clone(),
+        })
+    }
+
+    /// Return if any of the ranges in the list contain the verse
+    pub fn contains(&self, verse: &BibleVerse) -> bool {
+        self.iter().any(|range| range.contains(verse))
+    }
+
+    /// Parse the list that follows the book in a reference
+    /// `remain` is None if nothing followed the book
+    fn from_book_and_remainder(
+        book: BibleBook,
+        remain: Option<&str>,
+        style: &ReferenceStyle,
+    ) -> Result<Self, ParseError> {
+        let remain = remain
+            .ok_or_else(|| NoChapterSpecified::new("No chapter/verse specified.".to_string()))?;
+        let mut ranges: Vec<RangeInclusive<ChapterAndVerse>> = Vec::new();
+        for item in remain.split(style.list.trim()) {
+            let range = match ChapterAndVerseRange::from_str_with(item.trim(), style) {
+                Ok(cvr) => cvr.0,
+                Err(ParseError::ImplicitRange(e)) => {
+                    let cvr = e.data();
+                    // Without a chapter, an item continues in the chapter the last one ended in
+                    // The first item may only omit it for single-chapter books
+                    let chapter = match ranges.last() {
+                        Some(last) => last.end().chapter,
+                        None if book.number_of_chapters() == 1 => 1,
+                        None => {
+                            return Err(NoChapterSpecified::new(
+                                "Chapter can only be ommited for single-chapter books".to_string(),
+                            )
+                            .into());
+                        }
+                    };
+                    ChapterAndVerse::new(chapter, cvr.0.start().verse)
+                        ..=ChapterAndVerse::new(chapter, cvr.0.end().verse)
+                }
+                Err(e) => return Err(e),
+            };
+            if range.is_empty() {
+                return Err(InvalidRange::new("End verse before start".to_string()).into());
+            }
+            ranges.push(range);
+        }
+        Ok(BibleVerseRangeList { book, ranges })
+    }
+}
+
+impl Display for BibleVerseRangeList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, &ReferenceStyle::ENGLISH)
+    }
+}
+
+impl StyledDisplay for BibleVerseRangeList {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        style: &ReferenceStyle,
+    ) -> std::fmt::Result {
+        let single_chapter = self.book.number_of_chapters() == 1;
+        write!(f, "{} ", self.book.abbrev_in(style.locale))?;
+        let mut chapter = None;
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", style.list)?;
+            }
+            // The chapter is only needed at the start if it has changed
+            if single_chapter || chapter == Some(range.start().chapter) {
+                write!(f, "{}", range.start().verse)?;
+            } else {
+                write!(f, "{}", range.start().display_with(style))?;
+            }
+            if range.start() != range.end() {
+                if single_chapter || range.start().chapter == range.end().chapter {
+                    write!(f, "{}{}", style.range, range.end().verse)?;
+                } else {
+                    write!(f, "{}{}", style.range, range.end().display_with(style))?;
+                }
+            }
+            chapter = Some(range.end().chapter);
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for BibleVerseRangeList {
+    type Error = ParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<BibleVerseRange> for BibleVerseRangeList {
+    fn from(value: BibleVerseRange) -> Self {
+        BibleVerseRangeList {
+            book: value.book,
+            ranges: vec![value.range],
+        }
+    }
+}
+
+impl FromStr for BibleVerseRangeList {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let book = BibleBook::parse_abbrev(s)
+            .ok_or_else(|| NoSuchBookError::new("No matching abbreviation".to_string()))?;
+        let remain = s.find(" ").map(|pos| &s[pos + 1..]);
+        Self::from_book_and_remainder(book, remain, &ReferenceStyle::ENGLISH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(book: BibleBook, ranges: &[(u8, u8, u8, u8)]) -> BibleVerseRangeList {
+        BibleVerseRangeList {
+            book,
+            ranges: ranges
+                .iter()
+                .map(|(sc, sv, ec, ev)| {
+                    ChapterAndVerse::new(*sc, *sv)..=ChapterAndVerse::new(*ec, *ev)
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            BibleVerseRangeList::parse("Ro 8:28, 31-39").unwrap(),
+            list(BibleBook::Romans, &[(8, 28, 8, 28), (8, 31, 8, 39)])
+        );
+        assert_eq!(
+            BibleVerseRangeList::parse("Ro 8:28,31-39").unwrap(),
+            list(BibleBook::Romans, &[(8, 28, 8, 28), (8, 31, 8, 39)])
+        );
+        assert_eq!(
+            BibleVerseRangeList::parse("Mt 5:20-6:5, 7, 8:1").unwrap(),
+            list(
+                BibleBook::Matthew,
+                &[(5, 20, 6, 5), (6, 7, 6, 7), (8, 1, 8, 1)]
+            )
+        );
+        assert_eq!(
+            BibleVerseRangeList::parse("Jude 2, 5-7").unwrap(),
+            list(BibleBook::Jude, &[(1, 2, 1, 2), (1, 5, 1, 7)])
+        );
+        assert_eq!(BibleVerseRangeList::parse("Ro 8, 31-39"), None);
+        assert_eq!(BibleVerseRangeList::parse("Ro 8:28, 39-31"), None);
+        assert_eq!(BibleVerseRangeList::parse("Ro 8:28,"), None);
+    }
+
+    #[test]
+    fn test_parse_with() {
+        assert_eq!(
+            BibleVerseRangeList::parse_with("Röm 8,28.31-39", &ReferenceStyle::CONTINENTAL)
+                .unwrap(),
+            list(BibleBook::Romans, &[(8, 28, 8, 28), (8, 31, 8, 39)])
+        );
+        assert_eq!(
+            BibleVerseRangeList::parse_with("Ro 8.28, 31-39", &ReferenceStyle::PERIOD).unwrap(),
+            list(BibleBook::Romans, &[(8, 28, 8, 28), (8, 31, 8, 39)])
+        );
+        assert_eq!(
+            BibleVerseRangeList::parse_in("Joh 3,16.18", Locale::German).unwrap(),
+            list(BibleBook::John, &[(3, 16, 3, 16), (3, 18, 3, 18)])
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let romans = list(BibleBook::Romans, &[(8, 28, 8, 28), (8, 31, 8, 39)]);
+        assert_eq!(format!("{}", romans), "Ro 8:28, 31-39");
+        assert_eq!(
+            format!("{}", romans.display_with(&ReferenceStyle::CONTINENTAL)),
+            "Röm 8,28.31-39"
+        );
+        assert_eq!(
+            format!("{}", romans.display_with(&ReferenceStyle::PERIOD)),
+            "Ro 8.28, 31-39"
+        );
+        let matthew = list(
+            BibleBook::Matthew,
+            &[(5, 20, 6, 5), (6, 7, 6, 7), (8, 1, 8, 1)],
+        );
+        assert_eq!(format!("{}", matthew), "Mt 5:20-6:5, 7, 8:1");
+        let jude = list(BibleBook::Jude, &[(1, 2, 1, 2), (1, 5, 1, 7)]);
+        assert_eq!(format!("{}", jude), "Jude 2, 5-7");
+        for text in ["Ro 8:28, 31-39", "Mt 5:20-6:5, 7, 8:1", "Jude 2, 5-7"] {
+            assert_eq!(BibleVerseRangeList::parse(text).unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let romans = list(BibleBook::Romans, &[(8, 28, 8, 28), (8, 31, 8, 39)]);
+        assert!(romans.contains(&BibleVerse::new(BibleBook::Romans, 8, 28)));
+        assert!(!romans.contains(&BibleVerse::new(BibleBook::Romans, 8, 29)));
+        assert!(romans.contains(&BibleVerse::new(BibleBook::Romans, 8, 35)));
+        assert_eq!(romans.iter().count(), 2);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::errors::{InvalidFormat, NotANumber, ParseError};
+use super::osis::OsisId;
+use super::verse::BibleVerse;
+
+/// A single word of a verse, addressed by its position in the verse
+///
+/// Words are numbered from 1. Which text is meant by the number depends on the
+/// word model it is used with, such as a translation or the original language.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleWord};
+/// let word = BibleWord::parse("Jn 1:1 word 3").unwrap();
+/// assert_eq!(word.verse, BibleVerse::new(BibleBook::John, 1, 1));
+/// assert_eq!(word.word, 3);
+/// assert_eq!(word.to_string(), "Jn 1:1 word 3");
+/// assert_eq!(word.to_osis(), "John.1.1!3");
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BibleWord {
+    pub verse: BibleVerse,
+    pub word: u16,
+}
+
+impl Display for BibleWord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} word {}", self.verse, self.word)
+    }
+}
+
+#[allow(dead_code)]
+impl BibleWord {
+    pub fn new(verse: BibleVerse, word: u16) -> Self {
+        BibleWord { verse, word }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    /// Return the OSIS identifier for this word, with the word number as the grain
+    pub fn to_osis(&self) -> String {
+        format!("{}!{}", self.verse.to_osis(), self.word)
+    }
+
+    /// Construct a BibleWord from an OSIS identifier with a numeric grain such as `John.1.1!3`
+    pub fn from_osis(id: &str) -> Result<Self, ParseError> {
+        let verse = BibleVerse::from_osis(id)?;
+        let grain = OsisId::split(id)
+            .grain
+            .ok_or_else(|| InvalidFormat::new(format!("No word number in {}", id)))?;
+        Ok(BibleWord {
+            verse,
+            word: word_number(grain)?,
+        })
+    }
+}
+
+fn word_number(text: &str) -> Result<u16, NotANumber> {
+    match text.trim().parse::<u16>() {
+        Ok(word) if word > 0 => Ok(word),
+        _ => Err(NotANumber::new(format!("Invalid word number: {}", text))),
+    }
+}
+
+impl FromStr for BibleWord {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (verse, word) = s
+            .rsplit_once(" word ")
+            .ok_or_else(|| InvalidFormat::new(format!("Expected <verse> word <n>: {}", s)))?;
+        Ok(BibleWord {
+            verse: verse.parse()?,
+            word: word_number(word)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BibleBook;
+
+    #[test]
+    fn test_parse() {
+        let word = BibleWord::new(BibleVerse::new(BibleBook::Genesis, 1, 1), 4);
+        assert_eq!(BibleWord::parse("Gen 1:1 word 4"), Some(word));
+        assert_eq!(BibleWord::from_osis("Gen.1.1!4").unwrap(), word);
+        assert!(BibleWord::parse("Gen 1:1").is_none());
+        assert!(BibleWord::parse("Gen 1:1 word 0").is_none());
+        assert!(BibleWord::parse("Gen 1:1 word x").is_none());
+        assert!(BibleWord::from_osis("Gen.1.1").is_err());
+        assert!(BibleWord::from_osis("Gen.1.1!a").is_err());
+    }
+
+    #[test]
+    fn test_order() {
+        let first = BibleWord::parse("Jn 1:1 word 9").unwrap();
+        let second = BibleWord::parse("Jn 1:2 word 1").unwrap();
+        assert!(first < second);
+    }
+}
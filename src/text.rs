@@ -0,0 +1,164 @@
+//! Mod for holding the text of the Bible, as opposed to its structure
+//!
+//! Texts are keyed by the reference types in this crate, so anything that can be
+//! parsed as a [BibleVerse](crate::BibleVerse) can be used to look up its text.
+use crate::structs::chapterandverse::ChapterAndVerse;
+use crate::{BOOK_CHAPTERS, BibleBook, BibleVerse, CHAPTER_VERSES};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+pub mod bibletext;
+pub mod binary;
+pub mod metadata;
+pub mod versetext;
+
+pub use bibletext::BibleText;
+pub use metadata::{Metadata, Versification};
+pub use versetext::VerseText;
+
+/// Number of verses in the versification of this crate
+pub const NUMBER_OF_VERSES: usize = 31102;
+
+/// Running totals used to give every verse a position in the whole Bible
+struct VerseIndex {
+    /// Index into `chapter_starts` of the first chapter of each book, plus one past the end
+    book_starts: Vec<usize>,
+    /// Index of the first verse of each chapter, plus one past the end
+    chapter_starts: Vec<usize>,
+}
+
+fn verse_index_table() -> &'static VerseIndex {
+    static INDEX: OnceLock<VerseIndex> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        let mut book_starts = vec![0];
+        let mut chapter_starts = vec![0];
+        for (chapters, verses) in BOOK_CHAPTERS.iter().zip(CHAPTER_VERSES.iter()) {
+            book_starts.push(book_starts.last().unwrap() + *chapters as usize);
+            for count in verses.iter() {
+                chapter_starts.push(chapter_starts.last().unwrap() + *count as usize);
+            }
+        }
+        VerseIndex {
+            book_starts,
+            chapter_starts,
+        }
+    })
+}
+
+/// The position of a verse in the whole Bible, starting from 0 for Genesis 1:1
+/// Returns None if the verse does not exist.
+pub(crate) fn verse_index(verse: &BibleVerse) -> Option<usize> {
+    if !verse.is_valid() {
+        return None;
+    }
+    let table = verse_index_table();
+    let chapter = table.book_starts[verse.book.index()] + verse.chapter as usize - 1;
+    Some(table.chapter_starts[chapter] + verse.verse as usize - 1)
+}
+
+/// The verse at a position in the whole Bible. The reverse of [verse_index].
+pub(crate) fn verse_at(index: usize) -> Option<BibleVerse> {
+    if index >= NUMBER_OF_VERSES {
+        return None;
+    }
+    let table = verse_index_table();
+    let chapter = table
+        .chapter_starts
+        .partition_point(|&start| start <= index)
+        - 1;
+    let book = table.book_starts.partition_point(|&start| start <= chapter) - 1;
+    Some(BibleVerse::new(
+        BibleBook::from_index(book).ok()?,
+        (chapter - table.book_starts[book] + 1) as u8,
+        (index - table.chapter_starts[chapter] + 1) as u8,
+    ))
+}
+
+/// The positions of the verses of a book that fall between `start` and `end`
+/// Chapters and verses past the end of the book or chapter are clamped to it,
+/// so `Ps 23:1-99` covers the whole of Psalm 23.
+pub(crate) fn index_range(
+    book: BibleBook,
+    start: ChapterAndVerse,
+    end: ChapterAndVerse,
+) -> Range<usize> {
+    let table = verse_index_table();
+    let first_chapter = table.book_starts[book.index()];
+    let chapters = book.number_of_chapters() as usize;
+    // Position of the point just before (chapter, verse) or just after it
+    let position = |cv: ChapterAndVerse, after: bool| {
+        if cv.chapter == 0 {
+            return table.chapter_starts[first_chapter];
+        }
+        if cv.chapter as usize > chapters {
+            return table.chapter_starts[first_chapter + chapters];
+        }
+        let chapter = first_chapter + cv.chapter as usize - 1;
+        let length = table.chapter_starts[chapter + 1] - table.chapter_starts[chapter];
+        let verse = match after {
+            true => cv.verse as usize,
+            false => (cv.verse as usize).saturating_sub(1),
+        };
+        table.chapter_starts[chapter] + verse.min(length)
+    };
+    let range = position(start, false)..position(end, true);
+    match range.is_empty() {
+        true => range.start..range.start,
+        false => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verse_index() {
+        let genesis = BibleVerse::new(BibleBook::Genesis, 1, 1);
+        assert_eq!(verse_index(&genesis), Some(0));
+        assert_eq!(verse_at(0), Some(genesis));
+        let revelation = BibleVerse::new(BibleBook::Revelation, 22, 21);
+        assert_eq!(verse_index(&revelation), Some(NUMBER_OF_VERSES - 1));
+        assert_eq!(verse_at(NUMBER_OF_VERSES - 1), Some(revelation));
+        assert_eq!(verse_at(NUMBER_OF_VERSES), None);
+        let exodus = BibleVerse::new(BibleBook::Exodus, 1, 1);
+        assert_eq!(verse_index(&exodus), Some(1533));
+        assert_eq!(
+            verse_at(1532),
+            Some(BibleVerse::new(BibleBook::Genesis, 50, 26))
+        );
+        assert_eq!(
+            verse_index(&BibleVerse::new(BibleBook::Genesis, 1, 32)),
+            None
+        );
+        for index in (0..NUMBER_OF_VERSES).step_by(97) {
+            assert_eq!(verse_index(&verse_at(index).unwrap()), Some(index));
+        }
+    }
+
+    #[test]
+    fn test_index_range() {
+        let psalm = index_range(
+            BibleBook::Psalms,
+            ChapterAndVerse::new(23, 1),
+            ChapterAndVerse::new(23, 99),
+        );
+        assert_eq!(psalm.len(), 6);
+        assert_eq!(
+            verse_at(psalm.start),
+            Some(BibleVerse::new(BibleBook::Psalms, 23, 1))
+        );
+        let across = index_range(
+            BibleBook::John,
+            ChapterAndVerse::new(3, 36),
+            ChapterAndVerse::new(4, 1),
+        );
+        assert_eq!(across.len(), 2);
+        let backwards = index_range(
+            BibleBook::John,
+            ChapterAndVerse::new(4, 1),
+            ChapterAndVerse::new(3, 1),
+        );
+        assert!(backwards.is_empty());
+    }
+}
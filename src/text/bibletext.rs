@@ -0,0 +1,332 @@
+use super::metadata::Metadata;
+use super::versetext::{Markup, VerseText};
+use super::{NUMBER_OF_VERSES, index_range, verse_at, verse_index};
+use crate::structs::chapterandverse::ChapterAndVerse;
+use crate::{BibleBook, BibleChapter, BibleVerse, BibleVerseRange, StrongsNumber};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// The text of a translation of the Bible
+///
+/// All the verse text is held in a single string with an offset for every verse in
+/// the versification, so looking up a verse or passage does no allocation.
+/// Verses the translation does not include have no text.
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse, BibleVerseRange};
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// let text = BibleText::from_verses(
+///     Metadata::new("King James Version", "KJV", "en"),
+///     [
+///         VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept."),
+///         VerseText::new(BibleVerse::new(BibleBook::John, 11, 36), "Then said the Jews, Behold how he loved him!"),
+///     ],
+/// );
+/// assert_eq!(text.get(&BibleVerse::new(BibleBook::John, 11, 35)), Some("Jesus wept."));
+/// let passage = BibleVerseRange::parse("Jn 11:35-36").unwrap();
+/// assert_eq!(text.passage(&passage).count(), 2);
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BibleText {
+    metadata: Metadata,
+    text: String,
+    /// Start of the text of each verse in `text`, plus one past the end
+    /// A verse with no text is not in the translation.
+    offsets: Vec<u32>,
+    markup: BTreeMap<u32, Markup>,
+}
+
+#[allow(dead_code)]
+impl BibleText {
+    /// Construct a BibleText with no verses
+    pub fn new(metadata: Metadata) -> Self {
+        BibleText {
+            metadata,
+            text: String::new(),
+            offsets: vec![0; NUMBER_OF_VERSES + 1],
+            markup: BTreeMap::new(),
+        }
+    }
+
+    /// Construct a BibleText from the verses yielded by one of the
+    /// [import](crate::import) readers, or any other source of [VerseText]
+    /// The verses may be in any order. If a verse occurs more than once the last one wins.
+    pub fn from_verses(metadata: Metadata, verses: impl IntoIterator<Item = VerseText>) -> Self {
+        let mut by_index = BTreeMap::new();
+        for verse in verses {
+            if let Some(index) = verse_index(&verse.verse) {
+                by_index.insert(index, verse);
+            }
+        }
+        let mut text = String::new();
+        let mut offsets = Vec::with_capacity(NUMBER_OF_VERSES + 1);
+        let mut markup = BTreeMap::new();
+        let mut verses = by_index.into_iter().peekable();
+        for index in 0..NUMBER_OF_VERSES {
+            offsets.push(text.len() as u32);
+            if let Some((_, verse)) = verses.next_if(|(i, _)| *i == index) {
+                text.push_str(&verse.text);
+                if let Some(verse_markup) = verse.markup {
+                    markup.insert(index as u32, verse_markup);
+                }
+            }
+        }
+        offsets.push(text.len() as u32);
+        BibleText {
+            metadata,
+            text,
+            offsets,
+            markup,
+        }
+    }
+
+    /// Construct a BibleText from its parts, as stored by the binary format
+    pub(crate) fn from_parts(
+        metadata: Metadata,
+        text: String,
+        offsets: Vec<u32>,
+        markup: BTreeMap<u32, Markup>,
+    ) -> Self {
+        BibleText {
+            metadata,
+            text,
+            offsets,
+            markup,
+        }
+    }
+
+    pub(crate) fn parts(&self) -> (&str, &[u32], &BTreeMap<u32, Markup>) {
+        (&self.text, &self.offsets, &self.markup)
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    fn text_at(&self, index: usize) -> Option<&str> {
+        let start = self.offsets[index] as usize;
+        let end = self.offsets[index + 1] as usize;
+        match start < end {
+            true => Some(&self.text[start..end]),
+            false => None,
+        }
+    }
+
+    fn verses_in(&self, range: Range<usize>) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        range.filter_map(|index| Some((verse_at(index)?, self.text_at(index)?)))
+    }
+
+    /// Return the text of a verse, or None if it is not in this translation
+    pub fn get(&self, verse: &BibleVerse) -> Option<&str> {
+        self.text_at(verse_index(verse)?)
+    }
+
+    /// Return the markup of a verse, if it has any
+    pub fn markup(&self, verse: &BibleVerse) -> Option<&Markup> {
+        self.markup.get(&(verse_index(verse)? as u32))
+    }
+
+    /// Return the text and markup of a verse
+    pub fn verse_text(&self, verse: &BibleVerse) -> Option<VerseText> {
+        Some(VerseText {
+            verse: *verse,
+            text: self.get(verse)?.to_string(),
+            markup: self.markup(verse).cloned(),
+        })
+    }
+
+    pub fn contains(&self, verse: &BibleVerse) -> bool {
+        self.get(verse).is_some()
+    }
+
+    /// Return the verses of a passage that are in this translation
+    /// A range past the end of a chapter or book stops at the end of it.
+    pub fn passage(
+        &self,
+        range: &BibleVerseRange,
+    ) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        self.verses_in(index_range(
+            range.book,
+            *range.range.start(),
+            *range.range.end(),
+        ))
+    }
+
+    /// Return the verses of a chapter that are in this translation
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleChapter, BibleVerse};
+    /// use bible_data::text::{BibleText, Metadata, VerseText};
+    /// let text = BibleText::from_verses(
+    ///     Metadata::default(),
+    ///     [VerseText::new(BibleVerse::new(BibleBook::Psalms, 117, 2), "Praise ye the LORD.")],
+    /// );
+    /// let chapter = BibleChapter::new(BibleBook::Psalms, 117).unwrap();
+    /// let verses: Vec<_> = text.chapter(&chapter).collect();
+    /// assert_eq!(verses, vec![(BibleVerse::new(BibleBook::Psalms, 117, 2), "Praise ye the LORD.")]);
+    /// ```
+    pub fn chapter(&self, chapter: &BibleChapter) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        self.verses_in(index_range(
+            chapter.book,
+            ChapterAndVerse::new(chapter.chapter, 1),
+            ChapterAndVerse::new(chapter.chapter, u8::MAX),
+        ))
+    }
+
+    /// Return the verses of a book that are in this translation
+    pub fn book(&self, book: BibleBook) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        self.verses_in(index_range(
+            book,
+            ChapterAndVerse::new(1, 1),
+            ChapterAndVerse::new(u8::MAX, u8::MAX),
+        ))
+    }
+
+    /// Iterate over all the verses in this translation
+    pub fn iter(&self) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        self.verses_in(0..NUMBER_OF_VERSES)
+    }
+
+    /// Iterate over the verses with markup, in canonical order
+    pub fn markup_iter(&self) -> impl Iterator<Item = (BibleVerse, &Markup)> + '_ {
+        self.markup
+            .iter()
+            .filter_map(|(index, markup)| Some((verse_at(*index as usize)?, markup)))
+    }
+
+    /// Return the verses with a word tagged with a Strong's number
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::{BibleBook, BibleVerse, StrongsNumber};
+    /// use bible_data::import::osis::OsisReader;
+    /// use bible_data::text::{BibleText, Metadata};
+    /// let osis = r#"<osis><verse osisID="1John.4.8">He that loveth not knoweth not God;
+    /// for God is <w lemma="strong:G26">love</w>.</verse></osis>"#;
+    /// let verses = OsisReader::new(osis.as_bytes()).map(|v| v.unwrap());
+    /// let text = BibleText::from_verses(Metadata::default(), verses);
+    /// let love: Vec<BibleVerse> = text.strongs(&StrongsNumber::parse("G26").unwrap()).collect();
+    /// assert_eq!(love, vec![BibleVerse::new(BibleBook::FirstJohn, 4, 8)]);
+    /// ```
+    pub fn strongs<'a>(
+        &'a self,
+        number: &'a StrongsNumber,
+    ) -> impl Iterator<Item = BibleVerse> + 'a {
+        self.markup_iter()
+            .filter(|(_, markup)| markup.has_strongs(number))
+            .map(|(verse, _)| verse)
+    }
+
+    /// Return the number of verses in this translation
+    pub fn len(&self) -> usize {
+        self.offsets.windows(2).filter(|w| w[0] < w[1]).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::versetext::{Span, SpanStyle};
+
+    fn sample() -> BibleText {
+        let mut wept = VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.");
+        wept.markup = Some(Markup {
+            spans: vec![Span {
+                style: SpanStyle::Character("nd".to_string()),
+                range: 0..5,
+            }],
+            ..Default::default()
+        });
+        BibleText::from_verses(
+            Metadata::new("King James Version", "KJV", "en"),
+            [
+                VerseText::new(
+                    BibleVerse::new(BibleBook::Revelation, 22, 21),
+                    "The grace of our Lord Jesus Christ be with you all. Amen.",
+                ),
+                VerseText::new(
+                    BibleVerse::new(BibleBook::Genesis, 1, 1),
+                    "In the beginning God created the heaven and the earth.",
+                ),
+                wept,
+                VerseText::new(
+                    BibleVerse::new(BibleBook::John, 11, 36),
+                    "Then said the Jews, Behold how he loved him!",
+                ),
+                VerseText::new(
+                    BibleVerse::new(BibleBook::John, 12, 1),
+                    "Then Jesus six days before the passover came to Bethany,",
+                ),
+                VerseText::new(BibleVerse::new(BibleBook::John, 99, 1), "Not a verse"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_get() {
+        let text = sample();
+        assert_eq!(text.len(), 5);
+        assert!(!text.is_empty());
+        assert_eq!(
+            text.get(&BibleVerse::new(BibleBook::Genesis, 1, 1)),
+            Some("In the beginning God created the heaven and the earth.")
+        );
+        assert_eq!(text.get(&BibleVerse::new(BibleBook::Genesis, 1, 2)), None);
+        assert_eq!(text.get(&BibleVerse::new(BibleBook::Genesis, 1, 99)), None);
+        assert!(text.contains(&BibleVerse::new(BibleBook::Revelation, 22, 21)));
+        let wept = BibleVerse::new(BibleBook::John, 11, 35);
+        assert_eq!(text.markup(&wept).unwrap().spans[0].range, 0..5);
+        assert_eq!(text.verse_text(&wept).unwrap().text, "Jesus wept.");
+        assert!(
+            text.markup(&BibleVerse::new(BibleBook::John, 11, 36))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_lookups() {
+        let text = sample();
+        let passage = BibleVerseRange::parse("Jn 11:1-12:5").unwrap();
+        let verses: Vec<BibleVerse> = text.passage(&passage).map(|(v, _)| v).collect();
+        assert_eq!(
+            verses,
+            vec![
+                BibleVerse::new(BibleBook::John, 11, 35),
+                BibleVerse::new(BibleBook::John, 11, 36),
+                BibleVerse::new(BibleBook::John, 12, 1)
+            ]
+        );
+        let chapter = BibleChapter::new(BibleBook::John, 11).unwrap();
+        assert_eq!(text.chapter(&chapter).count(), 2);
+        assert_eq!(text.book(BibleBook::John).count(), 3);
+        assert_eq!(text.book(BibleBook::Exodus).count(), 0);
+        let all: Vec<BibleVerse> = text.iter().map(|(v, _)| v).collect();
+        assert_eq!(
+            all.first(),
+            Some(&BibleVerse::new(BibleBook::Genesis, 1, 1))
+        );
+        assert_eq!(
+            all.last(),
+            Some(&BibleVerse::new(BibleBook::Revelation, 22, 21))
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let text = BibleText::new(Metadata::default());
+        assert!(text.is_empty());
+        assert_eq!(text.len(), 0);
+        assert_eq!(text.iter().count(), 0);
+    }
+}
@@ -0,0 +1,744 @@
+//! Compact binary format for a [BibleText]
+//!
+//! The format is designed to be opened straight from a memory-mapped file:
+//! [BinaryBible] reads the header, metadata and index in place and only
+//! decompresses the text of a book when a verse in it is first looked up.
+//!
+//! All numbers are little-endian. Offsets are from the start of the file.
+//!
+//! | Offset | Size | Contents                                              |
+//! |--------|------|-------------------------------------------------------|
+//! | 0      | 4    | Magic `BIBD`                                          |
+//! | 4      | 2    | Format version, currently 2                           |
+//! | 6      | 2    | Reserved, 0                                           |
+//! | 8      | 4    | Number of verses in the versification                 |
+//! | 12     | 8    | Metadata offset and length                            |
+//! | 20     | 4    | Verse index offset                                    |
+//! | 24     | 4    | Book table offset                                     |
+//! | 28     | 12   | Markup block offset, compressed and uncompressed size |
+//!
+//! The verse index has a `u32` for every verse in the order of [BOOK_CHAPTERS](crate::BOOK_CHAPTERS)
+//! and [CHAPTER_VERSES](crate::CHAPTER_VERSES), plus one, giving the start of its text in the
+//! uncompressed text of the whole Bible. The book table has 4 `u32`s for each book: the offset
+//! and size of its compressed block, and the start and size of its uncompressed text.
+//!
+//! Version 2 added the Strong's numbers of tagged words to the markup block. Files
+//! written by version 1 are still read, with no tagged words.
+use super::bibletext::BibleText;
+use super::metadata::{Metadata, Versification};
+use super::versetext::{Break, BreakKind, Markup, Note, NoteKind, Span, SpanStyle, WordTag};
+use super::{NUMBER_OF_VERSES, index_range, verse_at, verse_index};
+use crate::structs::chapterandverse::ChapterAndVerse;
+use crate::{BibleBook, BibleChapter, BibleVerse, BibleVerseRange, StrongsLanguage, StrongsNumber};
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Write;
+use std::ops::Range;
+
+mod lz;
+
+pub const MAGIC: &[u8; 4] = b"BIBD";
+pub const VERSION: u16 = 2;
+const HEADER_LEN: usize = 40;
+const BOOKS: usize = 66;
+
+/// Errors found when reading the binary format
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The data does not start with the magic number
+    NotBinaryBible,
+    /// The data was written by a later version of the format
+    UnsupportedVersion(u16),
+    /// The data was written for a different versification
+    WrongVerseCount(u32),
+    /// The data is truncated or an offset points outside it
+    Corrupt(String),
+}
+
+impl Display for BinaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryError::NotBinaryBible => write!(f, "\"NotBinaryBible\": Missing magic number"),
+            BinaryError::UnsupportedVersion(version) => {
+                write!(f, "\"UnsupportedVersion\": {}", version)
+            }
+            BinaryError::WrongVerseCount(count) => write!(
+                f,
+                "\"WrongVerseCount\": {} verses. Expected {}",
+                count, NUMBER_OF_VERSES
+            ),
+            BinaryError::Corrupt(message) => write!(f, "\"Corrupt\": {}", message),
+        }
+    }
+}
+
+impl Error for BinaryError {}
+
+fn corrupt(message: &str) -> BinaryError {
+    BinaryError::Corrupt(message.to_string())
+}
+
+/// Reads values from a slice, failing with [BinaryError::Corrupt] rather than panicking
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Cursor { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BinaryError> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| corrupt("Unexpected end of data"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BinaryError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, BinaryError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn str(&mut self) -> Result<&'a str, BinaryError> {
+        let len = self.u32()? as usize;
+        std::str::from_utf8(self.bytes(len)?).map_err(|_| corrupt("Invalid UTF-8"))
+    }
+}
+
+fn push_u32(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+fn push_str(out: &mut Vec<u8>, value: &str) {
+    push_u32(out, value.len());
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn encode_metadata(metadata: &Metadata) -> Vec<u8> {
+    let mut out = Vec::new();
+    push_str(&mut out, &metadata.name);
+    push_str(&mut out, &metadata.abbrev);
+    push_str(&mut out, &metadata.language);
+    push_str(&mut out, metadata.copyright.as_deref().unwrap_or_default());
+    out.push(metadata.copyright.is_some() as u8);
+    push_str(&mut out, metadata.versification.identifier());
+    out
+}
+
+fn decode_metadata(data: &[u8]) -> Result<Metadata, BinaryError> {
+    let mut cursor = Cursor::new(data);
+    let name = cursor.str()?.to_string();
+    let abbrev = cursor.str()?.to_string();
+    let language = cursor.str()?.to_string();
+    let copyright = cursor.str()?.to_string();
+    let copyright = match cursor.u8()? {
+        0 => None,
+        _ => Some(copyright),
+    };
+    let versification = cursor.str()?;
+    let versification = Versification::from_identifier(versification)
+        .ok_or_else(|| corrupt("Unknown versification"))?;
+    Ok(Metadata {
+        name,
+        abbrev,
+        language,
+        copyright,
+        versification,
+    })
+}
+
+fn encode_markup(markup: &BTreeMap<u32, Markup>) -> Vec<u8> {
+    let mut out = Vec::new();
+    push_u32(&mut out, markup.len());
+    for (index, verse) in markup {
+        push_u32(&mut out, *index as usize);
+        push_u32(&mut out, verse.spans.len());
+        for span in &verse.spans {
+            match &span.style {
+                SpanStyle::WordsOfJesus => push_str(&mut out, ""),
+                SpanStyle::Character(name) => push_str(&mut out, name),
+            }
+            push_u32(&mut out, span.range.start);
+            push_u32(&mut out, span.range.end);
+        }
+        push_u32(&mut out, verse.notes.len());
+        for note in &verse.notes {
+            out.push(match note.kind {
+                NoteKind::Footnote => 0,
+                NoteKind::CrossReference => 1,
+            });
+            push_u32(&mut out, note.offset);
+            push_str(&mut out, &note.caller);
+            push_str(&mut out, &note.text);
+        }
+        push_u32(&mut out, verse.breaks.len());
+        for item in &verse.breaks {
+            let (kind, level) = match item.kind {
+                BreakKind::Paragraph => (0, 0),
+                BreakKind::Poetry(level) => (1, level),
+                BreakKind::Blank => (2, 0),
+            };
+            out.extend_from_slice(&[kind, level]);
+            push_u32(&mut out, item.offset);
+        }
+        push_u32(&mut out, verse.words.len());
+        for word in &verse.words {
+            push_u32(&mut out, word.range.start);
+            push_u32(&mut out, word.range.end);
+            push_u32(&mut out, word.strongs.len());
+            for number in &word.strongs {
+                out.push(match number.language {
+                    StrongsLanguage::Hebrew => 0,
+                    StrongsLanguage::Greek => 1,
+                });
+                out.extend_from_slice(&number.number.to_le_bytes());
+            }
+            push_str(&mut out, word.morph.as_deref().unwrap_or_default());
+        }
+    }
+    out
+}
+
+fn decode_markup(data: &[u8], version: u16) -> Result<BTreeMap<u32, Markup>, BinaryError> {
+    let mut cursor = Cursor::new(data);
+    let mut result = BTreeMap::new();
+    for _ in 0..cursor.u32()? {
+        let index = cursor.u32()?;
+        let mut markup = Markup::default();
+        for _ in 0..cursor.u32()? {
+            let style = match cursor.str()? {
+                "" => SpanStyle::WordsOfJesus,
+                name => SpanStyle::Character(name.to_string()),
+            };
+            let range = cursor.u32()? as usize..cursor.u32()? as usize;
+            markup.spans.push(Span { style, range });
+        }
+        for _ in 0..cursor.u32()? {
+            let kind = match cursor.u8()? {
+                0 => NoteKind::Footnote,
+                _ => NoteKind::CrossReference,
+            };
+            markup.notes.push(Note {
+                kind,
+                offset: cursor.u32()? as usize,
+                caller: cursor.str()?.to_string(),
+                text: cursor.str()?.to_string(),
+            });
+        }
+        for _ in 0..cursor.u32()? {
+            let kind = match (cursor.u8()?, cursor.u8()?) {
+                (0, _) => BreakKind::Paragraph,
+                (1, level) => BreakKind::Poetry(level),
+                _ => BreakKind::Blank,
+            };
+            markup.breaks.push(Break {
+                kind,
+                offset: cursor.u32()? as usize,
+            });
+        }
+        if version >= 2 {
+            for _ in 0..cursor.u32()? {
+                let range = cursor.u32()? as usize..cursor.u32()? as usize;
+                let mut strongs = Vec::new();
+                for _ in 0..cursor.u32()? {
+                    let language = match cursor.u8()? {
+                        0 => StrongsLanguage::Hebrew,
+                        _ => StrongsLanguage::Greek,
+                    };
+                    strongs.push(StrongsNumber::new(language, cursor.u16()?));
+                }
+                let morph = match cursor.str()? {
+                    "" => None,
+                    morph => Some(morph.to_string()),
+                };
+                markup.words.push(WordTag {
+                    range,
+                    strongs,
+                    morph,
+                });
+            }
+        }
+        result.insert(index, markup);
+    }
+    Ok(result)
+}
+
+/// Check that the markup of every verse lies within the text of the verse
+fn check_markup(
+    markup: &BTreeMap<u32, Markup>,
+    verse_len: impl Fn(usize) -> usize,
+) -> Result<(), BinaryError> {
+    for (&index, verse) in markup {
+        let index = index as usize;
+        if index >= NUMBER_OF_VERSES {
+            return Err(corrupt("Markup for a verse outside the versification"));
+        }
+        let len = verse_len(index);
+        let mut ranges = verse
+            .spans
+            .iter()
+            .map(|span| &span.range)
+            .chain(verse.words.iter().map(|word| &word.range));
+        let mut offsets = verse
+            .notes
+            .iter()
+            .map(|note| note.offset)
+            .chain(verse.breaks.iter().map(|item| item.offset));
+        if ranges.any(|range| range.start > range.end || range.end > len)
+            || offsets.any(|offset| offset > len)
+        {
+            return Err(corrupt("Markup outside the text of its verse"));
+        }
+    }
+    Ok(())
+}
+
+/// Write a [BibleText] in the binary format
+///
+/// # Example
+/// ```rust
+/// use bible_data::{BibleBook, BibleVerse};
+/// use bible_data::text::{BibleText, Metadata, VerseText};
+/// use bible_data::text::binary::{self, BinaryBible};
+/// let text = BibleText::from_verses(
+///     Metadata::new("King James Version", "KJV", "en"),
+///     [VerseText::new(BibleVerse::new(BibleBook::John, 11, 35), "Jesus wept.")],
+/// );
+/// let mut bytes = Vec::new();
+/// binary::write(&text, &mut bytes).unwrap();
+/// let bible = BinaryBible::from_bytes(&bytes).unwrap();
+/// assert_eq!(bible.get(&BibleVerse::new(BibleBook::John, 11, 35)), Some("Jesus wept."));
+/// assert_eq!(bible.metadata().abbrev, "KJV");
+/// ```
+pub fn write(text: &BibleText, mut writer: impl Write) -> std::io::Result<()> {
+    let (body, offsets, markup) = text.parts();
+    let metadata = encode_metadata(text.metadata());
+    let markup = encode_markup(markup);
+    let compressed_markup = lz::compress(&markup);
+
+    let metadata_offset = HEADER_LEN;
+    let index_offset = metadata_offset + metadata.len();
+    let table_offset = index_offset + offsets.len() * 4;
+    let mut block_offset = table_offset + BOOKS * 16;
+
+    let mut table = Vec::with_capacity(BOOKS * 16);
+    let mut blocks = Vec::with_capacity(BOOKS);
+    for book in BibleBook::iter() {
+        let range = index_range(
+            book,
+            ChapterAndVerse::new(1, 1),
+            ChapterAndVerse::new(u8::MAX, u8::MAX),
+        );
+        let start = offsets[range.start] as usize;
+        let end = offsets[range.end] as usize;
+        let block = lz::compress(&body.as_bytes()[start..end]);
+        push_u32(&mut table, block_offset);
+        push_u32(&mut table, block.len());
+        push_u32(&mut table, start);
+        push_u32(&mut table, end - start);
+        block_offset += block.len();
+        blocks.push(block);
+    }
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&VERSION.to_le_bytes());
+    header.extend_from_slice(&0u16.to_le_bytes());
+    push_u32(&mut header, NUMBER_OF_VERSES);
+    push_u32(&mut header, metadata_offset);
+    push_u32(&mut header, metadata.len());
+    push_u32(&mut header, index_offset);
+    push_u32(&mut header, table_offset);
+    push_u32(&mut header, block_offset);
+    push_u32(&mut header, compressed_markup.len());
+    push_u32(&mut header, markup.len());
+
+    writer.write_all(&header)?;
+    writer.write_all(&metadata)?;
+    for offset in offsets {
+        writer.write_all(&offset.to_le_bytes())?;
+    }
+    writer.write_all(&table)?;
+    for block in blocks {
+        writer.write_all(&block)?;
+    }
+    writer.write_all(&compressed_markup)?;
+    writer.flush()
+}
+
+/// Where the text of a book is in the file and in the uncompressed text
+struct Block {
+    compressed: Range<usize>,
+    start: usize,
+    len: usize,
+}
+
+/// A Bible text in the binary format, read in place from a slice of bytes
+///
+/// Opening only checks the header and reads the metadata. The text of each book is
+/// decompressed the first time it is needed, and the markup the first time any is asked for.
+/// To load a file without reading the whole of it, memory-map it and pass the mapped slice.
+pub struct BinaryBible<'a> {
+    data: &'a [u8],
+    version: u16,
+    metadata: Metadata,
+    index: &'a [u8],
+    blocks: Vec<Block>,
+    texts: Vec<OnceCell<String>>,
+    markup_block: (Range<usize>, usize),
+    markup: OnceCell<BTreeMap<u32, Markup>>,
+}
+
+#[allow(dead_code)]
+impl<'a> BinaryBible<'a> {
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, BinaryError> {
+        if !data.starts_with(MAGIC) {
+            return Err(BinaryError::NotBinaryBible);
+        }
+        let mut header = Cursor::new(data);
+        header.bytes(4)?;
+        let version = header.u16()?;
+        if version > VERSION {
+            return Err(BinaryError::UnsupportedVersion(version));
+        }
+        header.u16()?;
+        let verses = header.u32()?;
+        if verses as usize != NUMBER_OF_VERSES {
+            return Err(BinaryError::WrongVerseCount(verses));
+        }
+        let section = |offset: u32, len: u32| {
+            let range = offset as usize..offset as usize + len as usize;
+            match data.get(range.clone()) {
+                Some(_) => Ok(range),
+                None => Err(corrupt("Section outside data")),
+            }
+        };
+        let metadata = section(header.u32()?, header.u32()?)?;
+        let metadata = decode_metadata(&data[metadata])?;
+        let index = section(header.u32()?, (NUMBER_OF_VERSES as u32 + 1) * 4)?;
+        let table = section(header.u32()?, BOOKS as u32 * 16)?;
+        let markup_block = (
+            section(header.u32()?, header.u32()?)?,
+            header.u32()? as usize,
+        );
+
+        let mut cursor = Cursor::new(&data[table]);
+        let mut blocks = Vec::with_capacity(BOOKS);
+        for _ in 0..BOOKS {
+            blocks.push(Block {
+                compressed: section(cursor.u32()?, cursor.u32()?)?,
+                start: cursor.u32()? as usize,
+                len: cursor.u32()? as usize,
+            });
+        }
+        Ok(BinaryBible {
+            data,
+            version,
+            metadata,
+            index: &data[index],
+            blocks,
+            texts: (0..BOOKS).map(|_| OnceCell::new()).collect(),
+            markup_block,
+            markup: OnceCell::new(),
+        })
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    fn offset(&self, index: usize) -> usize {
+        let bytes = &self.index[index * 4..index * 4 + 4];
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+    }
+
+    /// The uncompressed text of a book. Corrupt blocks are treated as empty.
+    fn book_text(&self, book: BibleBook) -> &str {
+        self.texts[book.index()].get_or_init(|| {
+            let block = &self.blocks[book.index()];
+            lz::decompress(&self.data[block.compressed.clone()], block.len)
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .unwrap_or_default()
+        })
+    }
+
+    fn text_at(&self, index: usize, book: BibleBook) -> Option<&str> {
+        let block = &self.blocks[book.index()];
+        let start = self.offset(index).checked_sub(block.start)?;
+        let end = self.offset(index + 1).checked_sub(block.start)?;
+        match start < end {
+            true => self.book_text(book).get(start..end),
+            false => None,
+        }
+    }
+
+    fn verses_in(&self, range: Range<usize>) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        range.filter_map(|index| {
+            let verse = verse_at(index)?;
+            Some((verse, self.text_at(index, verse.book)?))
+        })
+    }
+
+    /// Return the text of a verse, or None if it is not in this translation
+    pub fn get(&self, verse: &BibleVerse) -> Option<&str> {
+        self.text_at(verse_index(verse)?, verse.book)
+    }
+
+    fn all_markup(&self) -> &BTreeMap<u32, Markup> {
+        self.markup.get_or_init(|| {
+            let (range, len) = &self.markup_block;
+            let verse_len = |index| self.offset(index + 1).saturating_sub(self.offset(index));
+            lz::decompress(&self.data[range.clone()], *len)
+                .and_then(|bytes| decode_markup(&bytes, self.version).ok())
+                .filter(|markup| check_markup(markup, verse_len).is_ok())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Return the markup of a verse, if it has any
+    pub fn markup(&self, verse: &BibleVerse) -> Option<&Markup> {
+        self.all_markup().get(&(verse_index(verse)? as u32))
+    }
+
+    /// Return the verses of a passage that are in this translation
+    pub fn passage(
+        &self,
+        range: &BibleVerseRange,
+    ) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        self.verses_in(index_range(
+            range.book,
+            *range.range.start(),
+            *range.range.end(),
+        ))
+    }
+
+    /// Return the verses of a chapter that are in this translation
+    pub fn chapter(&self, chapter: &BibleChapter) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        self.verses_in(index_range(
+            chapter.book,
+            ChapterAndVerse::new(chapter.chapter, 1),
+            ChapterAndVerse::new(chapter.chapter, u8::MAX),
+        ))
+    }
+
+    /// Return the verses of a book that are in this translation
+    pub fn book(&self, book: BibleBook) -> impl Iterator<Item = (BibleVerse, &str)> + '_ {
+        self.verses_in(index_range(
+            book,
+            ChapterAndVerse::new(1, 1),
+            ChapterAndVerse::new(u8::MAX, u8::MAX),
+        ))
+    }
+
+    /// Decompress the whole text into a [BibleText]
+    pub fn to_bible_text(&self) -> Result<BibleText, BinaryError> {
+        let mut text = String::new();
+        for (book, block) in BibleBook::iter().zip(self.blocks.iter()) {
+            if block.start != text.len() {
+                return Err(corrupt("Book blocks are not contiguous"));
+            }
+            let bytes = lz::decompress(&self.data[block.compressed.clone()], block.len)
+                .ok_or_else(|| corrupt(&format!("Invalid block for {}", book.name())))?;
+            text.push_str(&String::from_utf8(bytes).map_err(|_| corrupt("Invalid UTF-8"))?);
+        }
+        let offsets: Vec<u32> = (0..=NUMBER_OF_VERSES)
+            .map(|index| self.offset(index) as u32)
+            .collect();
+        if offsets.windows(2).any(|w| w[0] > w[1])
+            || offsets[NUMBER_OF_VERSES] as usize != text.len()
+        {
+            return Err(corrupt("Invalid verse index"));
+        }
+        let (range, len) = &self.markup_block;
+        let markup = lz::decompress(&self.data[range.clone()], *len)
+            .ok_or_else(|| corrupt("Invalid markup block"))?;
+        let markup = decode_markup(&markup, self.version)?;
+        check_markup(&markup, |index| {
+            (offsets[index + 1] - offsets[index]) as usize
+        })?;
+        Ok(BibleText::from_parts(
+            self.metadata.clone(),
+            text,
+            offsets,
+            markup,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::versetext::VerseText;
+
+    fn sample() -> BibleText {
+        sample_with(|_| {})
+    }
+
+    /// The sample text, with a change to the markup of Matthew 5:3
+    fn sample_with(change: impl FnOnce(&mut Markup)) -> BibleText {
+        let mut blessed = VerseText::new(
+            BibleVerse::new(BibleBook::Matthew, 5, 3),
+            "Blessed are the poor in spirit: for theirs is the kingdom of heaven.",
+        );
+        blessed.markup = Some(Markup {
+            spans: vec![Span {
+                style: SpanStyle::WordsOfJesus,
+                range: 0..68,
+            }],
+            notes: vec![Note {
+                kind: NoteKind::CrossReference,
+                offset: 30,
+                caller: "a".to_string(),
+                text: "Lk 6:20".to_string(),
+            }],
+            breaks: vec![Break {
+                kind: BreakKind::Poetry(2),
+                offset: 0,
+            }],
+            words: vec![WordTag {
+                range: 0..7,
+                strongs: vec![StrongsNumber::greek(3107)],
+                morph: Some("robinson:A-NPM".to_string()),
+            }],
+        });
+        change(blessed.markup.as_mut().unwrap());
+        BibleText::from_verses(
+            Metadata::new("King James Version", "KJV", "en").with_copyright("Public domain"),
+            [
+                VerseText::new(
+                    BibleVerse::new(BibleBook::Genesis, 1, 1),
+                    "In the beginning God created the heaven and the earth.",
+                ),
+                VerseText::new(
+                    BibleVerse::new(BibleBook::Genesis, 1, 2),
+                    "And the earth was without form, and void; and darkness was upon the face of the deep.",
+                ),
+                blessed,
+                VerseText::new(
+                    BibleVerse::new(BibleBook::Revelation, 22, 21),
+                    "The grace of our Lord Jesus Christ be with you all. Amen.",
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = sample();
+        let mut bytes = Vec::new();
+        write(&text, &mut bytes).unwrap();
+        let bible = BinaryBible::from_bytes(&bytes).unwrap();
+        assert_eq!(bible.metadata(), text.metadata());
+        let blessed = BibleVerse::new(BibleBook::Matthew, 5, 3);
+        assert_eq!(bible.get(&blessed), text.get(&blessed));
+        assert_eq!(bible.markup(&blessed), text.markup(&blessed));
+        assert_eq!(bible.get(&BibleVerse::new(BibleBook::Matthew, 5, 4)), None);
+        assert_eq!(bible.book(BibleBook::Genesis).count(), 2);
+        let chapter = BibleChapter::new(BibleBook::Revelation, 22).unwrap();
+        assert_eq!(bible.chapter(&chapter).count(), 1);
+        let passage = BibleVerseRange::parse("Ge 1:2-5").unwrap();
+        assert_eq!(
+            bible.passage(&passage).collect::<Vec<_>>(),
+            text.passage(&passage).collect::<Vec<_>>()
+        );
+        assert_eq!(bible.to_bible_text().unwrap(), text);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut bytes = Vec::new();
+        write(&sample(), &mut bytes).unwrap();
+        assert_eq!(
+            BinaryBible::from_bytes(b"not a bible").err(),
+            Some(BinaryError::NotBinaryBible)
+        );
+        let mut later = bytes.clone();
+        later[4] = 3;
+        assert_eq!(
+            BinaryBible::from_bytes(&later).err(),
+            Some(BinaryError::UnsupportedVersion(3))
+        );
+        assert!(matches!(
+            BinaryBible::from_bytes(&bytes[..bytes.len() - 10]),
+            Err(BinaryError::Corrupt(_))
+        ));
+    }
+
+    /// Set the `u32` in the header at an offset
+    fn set_header(bytes: &mut [u8], offset: usize, value: usize) {
+        bytes[offset..offset + 4].copy_from_slice(&(value as u32).to_le_bytes());
+    }
+
+    #[test]
+    fn test_wrong_lengths() {
+        let mut bytes = Vec::new();
+        write(&sample(), &mut bytes).unwrap();
+        let blessed = BibleVerse::new(BibleBook::Matthew, 5, 3);
+        let mut inflated = bytes.clone();
+        set_header(&mut inflated, 36, u32::MAX as usize);
+        let bible = BinaryBible::from_bytes(&inflated).unwrap();
+        assert_eq!(bible.markup(&blessed), None);
+        assert!(matches!(
+            bible.to_bible_text(),
+            Err(BinaryError::Corrupt(_))
+        ));
+        let mut truncated = bytes.clone();
+        set_header(&mut truncated, 36, 4);
+        let bible = BinaryBible::from_bytes(&truncated).unwrap();
+        assert_eq!(bible.markup(&blessed), None);
+        assert!(bible.to_bible_text().is_err());
+        assert!(bible.get(&blessed).is_some());
+    }
+
+    #[test]
+    fn test_markup_outside_verse() {
+        let text = sample_with(|markup| markup.spans[0].range = 0..1000);
+        let blessed = BibleVerse::new(BibleBook::Matthew, 5, 3);
+        let mut bytes = Vec::new();
+        write(&text, &mut bytes).unwrap();
+        let bible = BinaryBible::from_bytes(&bytes).unwrap();
+        assert_eq!(bible.markup(&blessed), None);
+        assert!(matches!(
+            bible.to_bible_text(),
+            Err(BinaryError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn test_version_1() {
+        let text = sample_with(|markup| markup.words.clear());
+        let blessed = BibleVerse::new(BibleBook::Matthew, 5, 3);
+        let mut bytes = Vec::new();
+        write(&text, &mut bytes).unwrap();
+        // Version 1 markup is the same without the count of tagged words ending each verse
+        let (_, _, markup) = text.parts();
+        let mut markup = encode_markup(markup);
+        markup.truncate(markup.len() - 4);
+        let compressed = lz::compress(&markup);
+        let markup_offset = u32::from_le_bytes(bytes[28..32].try_into().unwrap()) as usize;
+        bytes.truncate(markup_offset);
+        bytes.extend_from_slice(&compressed);
+        bytes[4..6].copy_from_slice(&1u16.to_le_bytes());
+        set_header(&mut bytes, 32, compressed.len());
+        set_header(&mut bytes, 36, markup.len());
+        let bible = BinaryBible::from_bytes(&bytes).unwrap();
+        assert_eq!(bible.markup(&blessed), text.markup(&blessed));
+        assert_eq!(bible.to_bible_text().unwrap(), text);
+    }
+}
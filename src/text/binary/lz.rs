@@ -0,0 +1,165 @@
+//! A small LZ77 compressor in the style of LZ4 block compression
+//!
+//! Each sequence is a token byte holding the number of literals (high nibble) and the
+//! length of the match less 4 (low nibble), with 15 meaning more length bytes follow.
+//! Then the literals, then the match as a 2 byte offset back into the output.
+//! The last sequence has literals only.
+
+const MIN_MATCH: usize = 4;
+const MAX_OFFSET: usize = u16::MAX as usize;
+const HASH_BITS: u32 = 14;
+
+fn hash(bytes: &[u8]) -> usize {
+    let value = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    (value.wrapping_mul(2654435761) >> (32 - HASH_BITS)) as usize
+}
+
+fn push_length(output: &mut Vec<u8>, mut length: usize) {
+    while length >= 255 {
+        output.push(255);
+        length -= 255;
+    }
+    output.push(length as u8);
+}
+
+fn push_sequence(output: &mut Vec<u8>, literals: &[u8], matched: Option<(usize, usize)>) {
+    let literal_nibble = literals.len().min(15) as u8;
+    let match_nibble = match matched {
+        Some((_, length)) => (length - MIN_MATCH).min(15) as u8,
+        None => 0,
+    };
+    output.push((literal_nibble << 4) | match_nibble);
+    if literals.len() >= 15 {
+        push_length(output, literals.len() - 15);
+    }
+    output.extend_from_slice(literals);
+    if let Some((offset, length)) = matched {
+        output.extend_from_slice(&(offset as u16).to_le_bytes());
+        if length - MIN_MATCH >= 15 {
+            push_length(output, length - MIN_MATCH - 15);
+        }
+    }
+}
+
+pub(crate) fn compress(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len() / 2);
+    // Position + 1 of the last occurrence of each hash. 0 is none.
+    let mut table = vec![0usize; 1 << HASH_BITS];
+    let mut anchor = 0;
+    let mut i = 0;
+    while i + MIN_MATCH <= input.len() {
+        let h = hash(&input[i..]);
+        let candidate = table[h];
+        table[h] = i + 1;
+        if candidate > 0 {
+            let start = candidate - 1;
+            if i - start <= MAX_OFFSET && input[start..start + MIN_MATCH] == input[i..i + MIN_MATCH]
+            {
+                let mut length = MIN_MATCH;
+                while i + length < input.len() && input[start + length] == input[i + length] {
+                    length += 1;
+                }
+                push_sequence(&mut output, &input[anchor..i], Some((i - start, length)));
+                i += length;
+                anchor = i;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    push_sequence(&mut output, &input[anchor..], None);
+    output
+}
+
+fn read_length(input: &[u8], pos: &mut usize, nibble: u8) -> Option<usize> {
+    let mut length = nibble as usize;
+    if nibble == 15 {
+        loop {
+            let byte = *input.get(*pos)?;
+            *pos += 1;
+            length += byte as usize;
+            if byte != 255 {
+                break;
+            }
+        }
+    }
+    Some(length)
+}
+
+/// Decompress data produced by [compress]
+/// Returns None if the data is corrupt or does not decompress to `expected_len` bytes.
+pub(crate) fn decompress(input: &[u8], expected_len: usize) -> Option<Vec<u8>> {
+    // No byte of input makes more than 255 bytes of output, so a corrupt length cannot
+    // reserve more memory than the data could fill
+    let mut output = Vec::with_capacity(expected_len.min(input.len().saturating_mul(255)));
+    let mut pos = 0;
+    loop {
+        let token = *input.get(pos)?;
+        pos += 1;
+        let literals = read_length(input, &mut pos, token >> 4)?;
+        if output.len() + literals > expected_len {
+            return None;
+        }
+        output.extend_from_slice(input.get(pos..pos + literals)?);
+        pos += literals;
+        if pos == input.len() {
+            break;
+        }
+        let offset = u16::from_le_bytes([*input.get(pos)?, *input.get(pos + 1)?]) as usize;
+        pos += 2;
+        let length = read_length(input, &mut pos, token & 15)? + MIN_MATCH;
+        if offset == 0 || offset > output.len() || output.len() + length > expected_len {
+            return None;
+        }
+        // The match may overlap the bytes it is copying, so copy one at a time
+        let start = output.len() - offset;
+        for j in 0..length {
+            output.push(output[start + j]);
+        }
+    }
+    match output.len() == expected_len {
+        true => Some(output),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let samples: [&[u8]; 5] = [
+            b"",
+            b"abc",
+            b"And God said, Let there be light: and there was light. And God saw the light, that it was good.",
+            &[b'a'; 1000],
+            "Am Anfang schuf Gott Himmel und Erde. Und die Erde war wüst und leer.".as_bytes(),
+        ];
+        for sample in samples {
+            let compressed = compress(sample);
+            assert_eq!(decompress(&compressed, sample.len()).unwrap(), sample);
+        }
+        assert!(compress(&[b'a'; 1000]).len() < 20);
+        let long: Vec<u8> = (0..100_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        assert_eq!(decompress(&compress(&long), long.len()).unwrap(), long);
+    }
+
+    #[test]
+    fn test_corrupt() {
+        let compressed = compress(b"For God so loved the world, God so loved");
+        assert!(decompress(&compressed, 3).is_none());
+        assert!(decompress(&compressed[..compressed.len() - 3], 40).is_none());
+        assert!(decompress(&[0x01, 0x10, 0x00], 10).is_none());
+    }
+
+    #[test]
+    fn test_wrong_length() {
+        let text = b"In the beginning God created the heaven and the earth.";
+        let compressed = compress(text);
+        assert!(decompress(&compressed, text.len() - 1).is_none());
+        assert!(decompress(&compressed, text.len() + 1).is_none());
+        assert!(decompress(&compressed, usize::MAX).is_none());
+        assert!(decompress(&compress(b"abc"), 2).is_none());
+    }
+}
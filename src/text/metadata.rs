@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+/// Information about a translation of the Bible
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Metadata {
+    /// The full name eg. `King James Version`
+    pub name: String,
+    /// The short name eg. `KJV`
+    pub abbrev: String,
+    /// BCP 47 language tag eg. `en`, `de`
+    pub language: String,
+    pub copyright: Option<String>,
+    pub versification: Versification,
+}
+
+#[allow(dead_code)]
+impl Metadata {
+    /// Construct metadata with the given names and language
+    ///
+    /// # Example
+    /// ```rust
+    /// use bible_data::text::{Metadata, Versification};
+    /// let metadata = Metadata::new("King James Version", "KJV", "en");
+    /// assert_eq!(metadata.abbrev, "KJV");
+    /// assert_eq!(metadata.versification, Versification::Kjv);
+    /// ```
+    pub fn new(
+        name: impl Into<String>,
+        abbrev: impl Into<String>,
+        language: impl Into<String>,
+    ) -> Self {
+        Metadata {
+            name: name.into(),
+            abbrev: abbrev.into(),
+            language: language.into(),
+            copyright: None,
+            versification: Versification::default(),
+        }
+    }
+
+    /// Set the copyright notice
+    pub fn with_copyright(mut self, copyright: impl Into<String>) -> Self {
+        self.copyright = Some(copyright.into());
+        self
+    }
+}
+
+/// The scheme used to divide the text into chapters and verses
+///
+/// Only the KJV versification is known to this crate. Texts using another scheme
+/// must be mapped onto it when they are imported.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Versification {
+    #[default]
+    Kjv,
+}
+
+impl Display for Versification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.identifier())
+    }
+}
+
+#[allow(dead_code)]
+impl Versification {
+    /// The identifier used for this versification by SWORD and OSIS eg. `KJV`
+    pub fn identifier(&self) -> &'static str {
+        match self {
+            Versification::Kjv => "KJV",
+        }
+    }
+
+    /// The versification with the given identifier. Not case-sensitive.
+    pub fn from_identifier(identifier: &str) -> Option<Self> {
+        match identifier.to_ascii_uppercase().as_str() {
+            "KJV" => Some(Versification::Kjv),
+            _ => None,
+        }
+    }
+}
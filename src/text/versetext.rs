@@ -0,0 +1,132 @@
+use crate::{BibleVerse, StrongsNumber};
+use std::fmt::Display;
+use std::ops::Range;
+
+/// The text of a single verse
+///
+/// `text` is always plain text with all markup removed and whitespace collapsed.
+/// Any formatting that was present in the source is kept separately in `markup`,
+/// with positions given as byte offsets into `text`.
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerseText {
+    pub verse: BibleVerse,
+    pub text: String,
+    pub markup: Option<Markup>,
+}
+
+impl Display for VerseText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[allow(dead_code)]
+impl VerseText {
+    /// Construct a VerseText with no markup
+    pub fn new(verse: BibleVerse, text: impl Into<String>) -> Self {
+        VerseText {
+            verse,
+            text: text.into(),
+            markup: None,
+        }
+    }
+}
+
+/// Formatting and annotations for the text of a verse
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Markup {
+    pub spans: Vec<Span>,
+    pub notes: Vec<Note>,
+    pub breaks: Vec<Break>,
+    /// Words tagged with the original words they translate
+    pub words: Vec<WordTag>,
+}
+
+#[allow(dead_code)]
+impl Markup {
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+            && self.notes.is_empty()
+            && self.breaks.is_empty()
+            && self.words.is_empty()
+    }
+
+    /// Return if any part of the verse is spoken by Jesus
+    pub fn has_words_of_jesus(&self) -> bool {
+        self.spans
+            .iter()
+            .any(|span| span.style == SpanStyle::WordsOfJesus)
+    }
+
+    /// Return if any word of the verse is tagged with the given Strong's number
+    pub fn has_strongs(&self, number: &StrongsNumber) -> bool {
+        self.words.iter().any(|word| word.strongs.contains(number))
+    }
+}
+
+/// A range of the verse text with a character style applied
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub style: SpanStyle,
+    pub range: Range<usize>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpanStyle {
+    /// Words of Jesus, traditionally printed in red
+    WordsOfJesus,
+    /// Any other character style, named by its USFM marker eg. `add`, `nd`, `it`
+    Character(String),
+}
+
+/// A word or phrase of the verse text tagged with the original words it translates
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordTag {
+    pub range: Range<usize>,
+    /// The Strong's numbers of the original words. A phrase may translate several.
+    pub strongs: Vec<StrongsNumber>,
+    /// Morphology code as given in the source eg. `strongMorph:TH8804` or `robinson:V-AAI-3S`
+    pub morph: Option<String>,
+}
+
+/// A footnote or cross-reference attached to a point in the verse text
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    pub kind: NoteKind,
+    pub offset: usize,
+    /// The marker for the note as given in the source. `+` means automatically generated.
+    pub caller: String,
+    pub text: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoteKind {
+    Footnote,
+    CrossReference,
+}
+
+/// The start of a new paragraph or line at a point in the verse text
+/// A break at offset 0 comes before the verse number.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Break {
+    pub kind: BreakKind,
+    pub offset: usize,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BreakKind {
+    Paragraph,
+    /// A line of poetry indented to the given level, starting at 1
+    Poetry(u8),
+    /// A blank line
+    Blank,
+}
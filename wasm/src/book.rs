@@ -0,0 +1,120 @@
+use crate::{js_error, parse_locale};
+use bible_data::{BibleBook, BibleChapter};
+use wasm_bindgen::prelude::*;
+
+/// A book of the Bible
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Book(pub(crate) BibleBook);
+
+impl Book {
+    fn find(name: &str, locale: Option<String>) -> Result<Book, String> {
+        let book = match locale {
+            None => name.parse().ok(),
+            Some(code) => BibleBook::parse_in(name, parse_locale(Some(code))?),
+        };
+        book.map(Book)
+            .ok_or_else(|| format!("NoSuchBookError: {}", name))
+    }
+}
+
+#[wasm_bindgen]
+impl Book {
+    /// Find a book by its name or abbreviation in the given locale
+    #[wasm_bindgen(js_name = fromName)]
+    pub fn from_name(name: &str, locale: Option<String>) -> Result<Book, JsError> {
+        Book::find(name, locale).map_err(js_error)
+    }
+
+    /// The book with the given number, from 1 for Genesis to 66 for Revelation
+    #[wasm_bindgen(js_name = fromNumber)]
+    pub fn from_number(number: u32) -> Result<Book, JsError> {
+        BibleBook::from_book_number(number)
+            .map(Book)
+            .map_err(|e| js_error(format!("OutOfRangeError: {}", e.message())))
+    }
+
+    /// All the books in order
+    pub fn all() -> Vec<Book> {
+        BibleBook::iter().map(Book).collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.0.name_in(bible_data::Locale::English).to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn abbrev(&self) -> String {
+        self.0.abbrev_in(bible_data::Locale::English).to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn number(&self) -> u32 {
+        self.0.book_number()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn chapters(&self) -> u32 {
+        self.0.number_of_chapters()
+    }
+
+    #[wasm_bindgen(getter, js_name = isNewTestament)]
+    pub fn is_new_testament(&self) -> bool {
+        self.0.is_new_testament()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn osis(&self) -> String {
+        self.0.to_osis().to_string()
+    }
+
+    /// The number of verses in a chapter, or undefined if the book has no such chapter
+    pub fn verses(&self, chapter: u8) -> Option<u32> {
+        BibleChapter::new(self.0, chapter).map(|chapter| chapter.number_of_verses())
+    }
+
+    /// The name of the book in the given locale
+    #[wasm_bindgen(js_name = nameIn)]
+    pub fn name_in(&self, locale: &str) -> Result<String, JsError> {
+        let locale = parse_locale(Some(locale.to_string())).map_err(js_error)?;
+        Ok(self.0.name_in(locale).to_string())
+    }
+
+    /// The abbreviation of the book in the given locale
+    #[wasm_bindgen(js_name = abbrevIn)]
+    pub fn abbrev_in(&self, locale: &str) -> Result<String, JsError> {
+        let locale = parse_locale(Some(locale.to_string())).map_err(js_error)?;
+        Ok(self.0.abbrev_in(locale).to_string())
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_book() {
+        let john = Book::find("Jn", None).unwrap();
+        assert_eq!(john, Book::find("Juan", Some("es".to_string())).unwrap());
+        assert_eq!(john.name(), "John");
+        assert_eq!(john.abbrev(), "Jn");
+        assert_eq!(john.number(), 43);
+        assert_eq!(john.chapters(), 21);
+        assert_eq!(john.verses(3), Some(36));
+        assert_eq!(john.verses(22), None);
+        assert_eq!(john.osis(), "John");
+        assert_eq!(john.display(), "John");
+        assert!(john.is_new_testament());
+        assert_eq!(Book::all().len(), 66);
+        assert_eq!(
+            Book::find("Hezekiah", None),
+            Err("NoSuchBookError: Hezekiah".to_string())
+        );
+    }
+}
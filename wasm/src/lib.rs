@@ -0,0 +1,111 @@
+//! WebAssembly bindings for the bible-data crate
+//!
+//! Exports reference parsing, validation and formatting and the book metadata to
+//! JavaScript. Build with [wasm-pack](https://rustwasm.github.io/wasm-pack/), which also
+//! writes the TypeScript declarations:
+//!
+//! ```text
+//! wasm-pack build wasm --release --target web
+//! ```
+//!
+//! ```js
+//! import init, { parse, isValid, Book } from "./pkg/bible_data_wasm.js";
+//! await init();
+//! const reference = parse("Ro 8:28, 31-39");
+//! reference.kind;               // "list"
+//! reference.osis;               // "Rom.8.28 Rom.8.31-Rom.8.39"
+//! isValid("Jn 3:99");           // false
+//! Book.fromName("John").chapters; // 21
+//! ```
+//!
+//! Only the reference data tables are linked in, so the module stays small. The text of
+//! translations is only available with the `text` feature, through [Translation].
+//!
+//! Errors are thrown as JavaScript `Error`s with a message starting with the kind of
+//! error, such as `NoSuchBookError: No matching abbreviation`.
+use bible_data::Locale;
+use bible_data::errors::ParseError;
+use wasm_bindgen::prelude::*;
+
+mod book;
+mod range;
+mod reference;
+#[cfg(feature = "text")]
+mod text;
+mod verse;
+
+pub use book::Book;
+pub use range::VerseRange;
+pub use reference::{Reference, is_valid, parse, validate};
+#[cfg(feature = "text")]
+pub use text::{PassageVerse, Translation};
+pub use verse::Verse;
+
+/// Parse an optional locale identifier, defaulting to English
+fn parse_locale(locale: Option<String>) -> Result<Locale, String> {
+    match locale {
+        None => Ok(Locale::English),
+        Some(code) => code
+            .parse()
+            .map_err(|e: bible_data::errors::NoSuchLocaleError| {
+                format!("NoSuchLocaleError: {}", e.message())
+            }),
+    }
+}
+
+/// The message for a parse error, starting with the kind of error
+fn error_message(error: &ParseError) -> String {
+    let (kind, message) = match error {
+        ParseError::NoSuchBookError(e) => ("NoSuchBookError", e.message()),
+        ParseError::NoChapterSpecified(e) => ("NoChapterSpecified", e.message()),
+        ParseError::NotANumber(e) => ("NotANumber", e.message()),
+        ParseError::ChapterOutOfRange(e) => ("ChapterOutOfRange", e.message()),
+        ParseError::InvalidFormat(e) => ("InvalidFormat", e.message()),
+        ParseError::ImplicitRange(_) => ("ImplicitRange", "Chapter needed for this book"),
+        ParseError::InvalidRange(e) => ("InvalidRange", e.message()),
+    };
+    format!("{}: {}", kind, message)
+}
+
+fn js_error(message: String) -> JsError {
+    JsError::new(&message)
+}
+
+/// The identifiers of the locales with book names, such as `"de"`
+#[wasm_bindgen]
+pub fn locales() -> Vec<String> {
+    Locale::iter()
+        .map(|locale| locale.identifier().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(parse_locale(None), Ok(Locale::English));
+        assert_eq!(
+            parse_locale(Some("pt-BR".to_string())),
+            Ok(Locale::Portuguese)
+        );
+        assert!(parse_locale(Some("xx".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_error_message() {
+        let error = "Hezekiah 1:1"
+            .parse::<bible_data::BibleVerse>()
+            .unwrap_err();
+        assert_eq!(
+            error_message(&error),
+            "NoSuchBookError: No matching abbreviation"
+        );
+    }
+
+    #[test]
+    fn test_locales() {
+        assert_eq!(locales()[..2], ["en", "es"]);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::book::Book;
+use crate::verse::Verse;
+use crate::{error_message, js_error, parse_locale};
+use bible_data::{BibleVerse, BibleVerseRange, ReferenceStyle, StyledDisplay};
+use wasm_bindgen::prelude::*;
+
+/// A range of verses within a book
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerseRange(pub(crate) BibleVerseRange);
+
+impl VerseRange {
+    fn parse_in(reference: &str, locale: Option<String>) -> Result<VerseRange, String> {
+        BibleVerseRange::from_str_in(reference, parse_locale(locale)?)
+            .map(VerseRange)
+            .map_err(|e| error_message(&e))
+    }
+}
+
+#[wasm_bindgen]
+impl VerseRange {
+    /// Parse a range such as `Jn 3:16-18`, with the book names and separators of the
+    /// given locale
+    pub fn parse(reference: &str, locale: Option<String>) -> Result<VerseRange, JsError> {
+        VerseRange::parse_in(reference, locale).map_err(js_error)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn book(&self) -> Book {
+        Book(self.0.book)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> Verse {
+        let start = self.0.range.start();
+        Verse(BibleVerse::new(self.0.book, start.chapter, start.verse))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> Verse {
+        let end = self.0.range.end();
+        Verse(BibleVerse::new(self.0.book, end.chapter, end.verse))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn osis(&self) -> String {
+        self.0.to_osis()
+    }
+
+    /// The verses of the range in order
+    pub fn verses(&self) -> Vec<Verse> {
+        self.0.verses().map(Verse).collect()
+    }
+
+    pub fn contains(&self, verse: &Verse) -> bool {
+        self.0.contains(&verse.0)
+    }
+
+    /// Write the range with the book names and separators of the given locale
+    pub fn format(&self, locale: Option<String>) -> Result<String, JsError> {
+        let style = ReferenceStyle::for_locale(parse_locale(locale).map_err(js_error)?);
+        Ok(self.0.display_with(&style).to_string())
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let range = VerseRange::parse_in("Ps 117:2-118:2", None).unwrap();
+        assert_eq!(range.book().name(), "Psalms");
+        assert_eq!(range.start().display(), "Ps 117:2");
+        assert_eq!(range.end().display(), "Ps 118:2");
+        assert_eq!(range.osis(), "Ps.117.2-Ps.118.2");
+        assert_eq!(range.display(), "Ps 117:2-118:2");
+        let verses: Vec<_> = range.verses().iter().map(|v| v.display()).collect();
+        assert_eq!(verses, ["Ps 117:2", "Ps 118:1", "Ps 118:2"]);
+        assert!(range.contains(&range.end()));
+        assert_eq!(
+            VerseRange::parse_in("Jn 3:18-16", None),
+            Err("InvalidRange: End verse before start".to_string())
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::book::Book;
+use crate::range::VerseRange;
+use crate::{error_message, js_error, parse_locale};
+use bible_data::{BibleChapter, BibleReference, BibleVerse, ReferenceStyle, StyledDisplay};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const REFERENCE_KIND: &str = r#"
+/** The kinds of reference, from the least to the most specific */
+export type ReferenceKind = "book" | "chapter" | "list" | "range" | "verse";
+"#;
+
+/// Any reference, from a whole book down to a single verse or a list of ranges
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference(BibleReference);
+
+pub(crate) fn parse_in(reference: &str, locale: Option<String>) -> Result<BibleReference, String> {
+    BibleReference::from_str_in(reference, parse_locale(locale)?).map_err(|e| error_message(&e))
+}
+
+/// Check that every verse the reference starts or ends on exists
+fn check(reference: &BibleReference) -> Result<(), String> {
+    for range in reference.ranges() {
+        for end in [range.range.start(), range.range.end()] {
+            let verse = BibleVerse::new(range.book, end.chapter, end.verse);
+            if verse.is_valid() {
+                continue;
+            }
+            let kind = match BibleChapter::new(range.book, end.chapter) {
+                Some(_) => "VerseOutOfRange",
+                None => "ChapterOutOfRange",
+            };
+            return Err(format!("{}: {} does not exist", kind, verse));
+        }
+    }
+    Ok(())
+}
+
+/// Parse any reference, with the book names and separators of the given locale
+#[wasm_bindgen]
+pub fn parse(reference: &str, locale: Option<String>) -> Result<Reference, JsError> {
+    parse_in(reference, locale).map(Reference).map_err(js_error)
+}
+
+/// Whether the reference parses and all its verses exist
+#[wasm_bindgen(js_name = isValid)]
+pub fn is_valid(reference: &str, locale: Option<String>) -> bool {
+    validate(reference, locale).is_none()
+}
+
+/// Return why the reference is not valid, or undefined if it is
+#[wasm_bindgen]
+pub fn validate(reference: &str, locale: Option<String>) -> Option<String> {
+    parse_in(reference, locale).and_then(|r| check(&r)).err()
+}
+
+#[wasm_bindgen]
+impl Reference {
+    #[wasm_bindgen(getter, unchecked_return_type = "ReferenceKind")]
+    pub fn kind(&self) -> String {
+        self.0.kind().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn book(&self) -> Book {
+        Book(self.0.book())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn osis(&self) -> String {
+        self.0.to_osis()
+    }
+
+    /// The ranges of verses the reference covers
+    pub fn ranges(&self) -> Vec<VerseRange> {
+        self.0.ranges().into_iter().map(VerseRange).collect()
+    }
+
+    /// Write the reference with the book names and separators of the given locale
+    pub fn format(&self, locale: Option<String>) -> Result<String, JsError> {
+        let style = ReferenceStyle::for_locale(parse_locale(locale).map_err(js_error)?);
+        Ok(self.0.display_with(&style).to_string())
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference() {
+        let reference = Reference(parse_in("Ro 8:28, 31-39", None).unwrap());
+        assert_eq!(reference.kind(), "list");
+        assert_eq!(reference.book().name(), "Romans");
+        assert_eq!(reference.osis(), "Rom.8.28 Rom.8.31-Rom.8.39");
+        assert_eq!(reference.ranges().len(), 2);
+        assert_eq!(reference.display(), "Ro 8:28, 31-39");
+        let chapter = Reference(parse_in("Salmos 117", Some("es".to_string())).unwrap());
+        assert_eq!(chapter.kind(), "chapter");
+        assert_eq!(chapter.ranges()[0].display(), "Ps 117:1-2");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate("Jn 3:16", None), None);
+        assert!(is_valid("John 3", None));
+        assert!(!is_valid("Jn 3:99", None));
+        assert_eq!(
+            validate("Jn 3:16-40", None),
+            Some("VerseOutOfRange: Jn 3:40 does not exist".to_string())
+        );
+        assert_eq!(
+            validate("Jn 21:1-22:1", None),
+            Some("ChapterOutOfRange: Jn 22:1 does not exist".to_string())
+        );
+        assert_eq!(
+            validate("Hezekiah 1", None).unwrap(),
+            "NoSuchBookError: No matching book in en"
+        );
+        assert!(validate("Jn 3:16", Some("xx".to_string())).is_some());
+    }
+}
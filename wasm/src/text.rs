@@ -0,0 +1,105 @@
+use crate::reference::parse_in;
+use crate::{js_error, parse_locale};
+use bible_data::text::BibleText;
+use bible_data::text::binary::BinaryBible;
+use wasm_bindgen::prelude::*;
+
+/// A translation, read from a file in the crate's binary format
+#[wasm_bindgen]
+pub struct Translation(BibleText);
+
+/// A verse of a passage and its text
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassageVerse {
+    pub reference: String,
+    pub text: String,
+}
+
+impl Translation {
+    fn from_bytes(data: &[u8]) -> Result<Translation, String> {
+        BinaryBible::from_bytes(data)
+            .and_then(|binary| binary.to_bible_text())
+            .map(Translation)
+            .map_err(|e| format!("BinaryError: {}", e))
+    }
+
+    fn verses(&self, reference: &str, locale: Option<String>) -> Result<Vec<PassageVerse>, String> {
+        let reference = parse_in(reference, locale.clone())?;
+        let style = bible_data::ReferenceStyle::for_locale(parse_locale(locale)?);
+        Ok(reference
+            .ranges()
+            .iter()
+            .flat_map(|range| self.0.passage(range))
+            .map(|(verse, text)| PassageVerse {
+                reference: bible_data::StyledDisplay::display_with(&verse, &style).to_string(),
+                text: text.to_string(),
+            })
+            .collect())
+    }
+}
+
+#[wasm_bindgen]
+impl Translation {
+    /// Read a translation from the contents of a binary file
+    #[wasm_bindgen(constructor)]
+    pub fn new(data: &[u8]) -> Result<Translation, JsError> {
+        Translation::from_bytes(data).map_err(js_error)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.0.metadata().name.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn abbrev(&self) -> String {
+        self.0.metadata().abbrev.clone()
+    }
+
+    /// The verses of a passage that are in the translation
+    pub fn passage(
+        &self,
+        reference: &str,
+        locale: Option<String>,
+    ) -> Result<Vec<PassageVerse>, JsError> {
+        self.verses(reference, locale).map_err(js_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bible_data::text::{Metadata, VerseText};
+    use bible_data::{BibleBook, BibleVerse};
+
+    #[test]
+    fn test_passage() {
+        let text = BibleText::from_verses(
+            Metadata::new("King James Version", "KJV", "en"),
+            [
+                VerseText::new(
+                    BibleVerse::new(BibleBook::Psalms, 117, 1),
+                    "O praise the LORD.",
+                ),
+                VerseText::new(
+                    BibleVerse::new(BibleBook::Psalms, 117, 2),
+                    "Praise ye the LORD.",
+                ),
+            ],
+        );
+        let mut data = Vec::new();
+        bible_data::text::binary::write(&text, &mut data).unwrap();
+        let translation = Translation::from_bytes(&data).unwrap();
+        assert_eq!(translation.abbrev(), "KJV");
+        assert_eq!(
+            translation.verses("Ps 117:2", None).unwrap(),
+            [PassageVerse {
+                reference: "Ps 117:2".to_string(),
+                text: "Praise ye the LORD.".to_string(),
+            }]
+        );
+        assert_eq!(translation.verses("Ps 117", None).unwrap().len(), 2);
+        assert!(Translation::from_bytes(b"not a bible").is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::book::Book;
+use crate::{error_message, js_error, parse_locale};
+use bible_data::{BibleVerse, ReferenceStyle, StyledDisplay};
+use wasm_bindgen::prelude::*;
+
+/// A single verse
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verse(pub(crate) BibleVerse);
+
+impl Verse {
+    fn parse_in(reference: &str, locale: Option<String>) -> Result<Verse, String> {
+        BibleVerse::from_str_in(reference, parse_locale(locale)?)
+            .map(Verse)
+            .map_err(|e| error_message(&e))
+    }
+}
+
+#[wasm_bindgen]
+impl Verse {
+    /// Parse a verse such as `Jn 3:16`, with the book names and separators of the
+    /// given locale
+    pub fn parse(reference: &str, locale: Option<String>) -> Result<Verse, JsError> {
+        Verse::parse_in(reference, locale).map_err(js_error)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn book(&self) -> Book {
+        Book(self.0.book)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn chapter(&self) -> u8 {
+        self.0.chapter
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn verse(&self) -> u8 {
+        self.0.verse
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn osis(&self) -> String {
+        self.0.to_osis()
+    }
+
+    /// Whether the book has this chapter and the chapter has this verse
+    #[wasm_bindgen(js_name = isValid)]
+    pub fn is_valid(&self) -> bool {
+        self.0.is_valid()
+    }
+
+    /// Compare with another verse in Bible order, for sorting
+    pub fn compare(&self, other: &Verse) -> i32 {
+        self.0.cmp(&other.0) as i32
+    }
+
+    /// Write the verse with the book names and separators of the given locale
+    pub fn format(&self, locale: Option<String>) -> Result<String, JsError> {
+        let style = ReferenceStyle::for_locale(parse_locale(locale).map_err(js_error)?);
+        Ok(self.0.display_with(&style).to_string())
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verse() {
+        let verse = Verse::parse_in("Jn 3:16", None).unwrap();
+        assert_eq!(verse.book().name(), "John");
+        assert_eq!((verse.chapter(), verse.verse()), (3, 16));
+        assert_eq!(verse.osis(), "John.3.16");
+        assert_eq!(verse.display(), "Jn 3:16");
+        assert!(verse.is_valid());
+        assert_eq!(
+            Verse::parse_in("Joh 3,16", Some("de".to_string())),
+            Ok(verse)
+        );
+        let earlier = Verse::parse_in("Ge 1:1", None).unwrap();
+        assert_eq!(earlier.compare(&verse), -1);
+        assert_eq!(verse.compare(&verse), 0);
+        assert!(!Verse::parse_in("Jn 3:99", None).unwrap().is_valid());
+        assert_eq!(
+            Verse::parse_in("Jn", None),
+            Err("NoChapterSpecified: No chapter/verse specified.".to_string())
+        );
+    }
+}